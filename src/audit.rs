@@ -0,0 +1,93 @@
+//! Operational audit trail: an append-only JSON Lines file with one entry per successful API
+//! mutation. Separate from the tournament's own match history, and kept when tournaments are
+//! cleaned up.
+
+use crate::models::{epoch_millis, TournamentId};
+use serde::{Deserialize, Serialize};
+use std::fs::{File, OpenOptions};
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread::{self, JoinHandle};
+
+/// One line of the audit log.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct AuditEvent {
+    /// Milliseconds since the Unix epoch.
+    pub timestamp: u64,
+    pub tournament_id: TournamentId,
+    /// What was done, e.g. the endpoint (`POST /api/tournaments/{id}/players`).
+    pub action: String,
+    /// Short description of the tournament afterwards.
+    pub summary: String,
+}
+
+/// Append-only audit log file. Events go over a channel to a writer thread that owns a buffered
+/// file, so recording never waits on disk; each event is one whole line and the buffer is flushed
+/// whenever the queue runs empty. Dropping the log writes out everything recorded so far.
+pub struct AuditLog {
+    lines: Option<Sender<String>>,
+    writer: Option<JoinHandle<()>>,
+}
+
+impl AuditLog {
+    /// Open `path` for appending, creating it if needed; existing lines are kept.
+    pub fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        let (lines, queue) = mpsc::channel();
+        let writer = thread::Builder::new()
+            .name("audit-log".into())
+            .spawn(move || {
+                if let Err(e) = write_lines(&queue, BufWriter::new(file)) {
+                    log::error!("Audit log writer stopped: {}", e);
+                }
+            })?;
+        Ok(Self {
+            lines: Some(lines),
+            writer: Some(writer),
+        })
+    }
+
+    /// Append one event, timestamped now. Fails once the writer has stopped on an I/O error.
+    pub fn record(
+        &self,
+        tournament_id: TournamentId,
+        action: &str,
+        summary: &str,
+    ) -> io::Result<()> {
+        let event = AuditEvent {
+            timestamp: epoch_millis(),
+            tournament_id,
+            action: action.to_string(),
+            summary: summary.to_string(),
+        };
+        let mut line = serde_json::to_string(&event)?;
+        line.push('\n');
+        self.lines
+            .as_ref()
+            .and_then(|lines| lines.send(line).ok())
+            .ok_or_else(|| io::Error::other("audit log writer stopped"))
+    }
+}
+
+impl Drop for AuditLog {
+    fn drop(&mut self) {
+        // Closing the channel lets the writer drain the queue, flush and exit.
+        self.lines.take();
+        if let Some(writer) = self.writer.take() {
+            let _ = writer.join();
+        }
+    }
+}
+
+/// Writer thread: append queued lines until every sender is gone, flushing when the queue is empty.
+fn write_lines(queue: &Receiver<String>, mut out: BufWriter<File>) -> io::Result<()> {
+    while let Ok(line) = queue.recv() {
+        out.write_all(line.as_bytes())?;
+        while let Ok(line) = queue.try_recv() {
+            out.write_all(line.as_bytes())?;
+        }
+        out.flush()?;
+    }
+    Ok(())
+}
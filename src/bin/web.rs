@@ -2,37 +2,317 @@
 //! Run with: cargo run --bin web
 //! Listens on 0.0.0.0:8080 by default so the app is reachable via DNS on a VPS.
 //! Override with env: HOST (e.g. 0.0.0.0), PORT (e.g. 8080).
+//! Cleanup of idle tournaments: CLEANUP_INTERVAL_SECS (default 1800) and INACTIVITY_TIMEOUT_SECS
+//! (default 43200), both at least 60.
+//! Input limits for new tournaments: MAX_PLAYERS (default 256), MAX_NAME_LEN (default 64) and
+//! MAX_AVATAR_URL_LEN (default 2048).
 //! Whole-site password gate: correct password is `SITE_GATE_PLAIN` in this file.
 //! After POST `/api/site-gate`, the client stores the returned token (sessionStorage) and sends
 //! header `X-Dart-Site-Gate` on requests; no cookie (avoids browser cookie UI / SameSite quirks).
+//! Mutating tournament requests honor an optional `Idempotency-Key` header: a key repeated on the
+//! same method and path returns the cached response instead of running the action again (bounded
+//! per tournament).
+//! A paused tournament rejects every mutation except resume, and is never removed by the cleanup.
+//! Mutating requests may also send the tournament `version` they last saw (`If-Match` header or
+//! `expected_version` query parameter); a stale version is rejected with 409 Conflict.
+//! `GET /api/tournaments/{id}/events` streams the tournament JSON as server-sent events after
+//! every successful mutation.
+//! `POST /api/tournaments/{id}/share` creates a read-only share link: `GET /api/share/{share_id}`
+//! serves the tournament without the site password and without exposing the tournament id.
+//! `GET /api/tournaments` lists every tournament id, so it is off unless `ADMIN_TOKEN` is set, and
+//! then needs that token in the `X-Dart-Admin-Token` header.
+//! With `AUDIT_LOG` set to a file path, every successful mutation is appended to that file as a
+//! JSON line (`timestamp`, `tournament_id`, `action`, `summary`).
 
 use actix_files::Files;
 use actix_web::body::BoxBody;
 use actix_web::dev::{ServiceRequest, ServiceResponse};
+use actix_web::http::{header, StatusCode};
 use actix_web::middleware::{from_fn, Next};
 use actix_web::{
     delete, get, post, put,
     web::{self, Data, Json, Path},
     App, Error, HttpRequest, HttpResponse, HttpServer, Responder,
 };
+use dart_tournament_web::audit::AuditLog;
+use dart_tournament_web::idempotency::{IdempotencyCache, IdempotencyKey};
+use dart_tournament_web::openapi::openapi_document;
+use dart_tournament_web::share::{shared_view, ShareLinks};
 use dart_tournament_web::{
-    add_players_back_from_last_eliminated, generate_group_play_matches,
-    generate_semi_final_matches, process_finals_results, process_group_play_results,
-    process_semi_final_results, set_finals_match_winner, start_semi_finals, start_tournament, Team,
-    Tournament, TournamentId,
+    add_players_back_from_last_eliminated, advance_tournament, auto_fill_finalists, bracket_svg,
+    generate_group_play_matches, generate_knockout_matches, generate_practice_round,
+    generate_redemption_matches, generate_semi_final_matches, import_roster_csv,
+    process_finals_results, process_grand_finals_results, process_group_play_results,
+    process_knockout_results, process_redemption_results, process_semi_final_results,
+    quickstart_tournament, record_drawn_final, record_finals_leg, results_html,
+    set_finals_match_winner, set_sudden_death_players, start_grand_finals, start_semi_finals,
+    start_tournament, FinalTieResolution, FinalsFormat, ForfeitPolicy, InputLimits, MatchOutcome,
+    PointsConfig, RosterImport, RoundType, ScoringMode, SeedingStrategy, Team, Tournament,
+    TournamentConfig, TournamentError, TournamentFormat, TournamentId, TournamentSummary,
+    DEFAULT_MAX_AVATAR_URL_LEN, DEFAULT_MAX_NAME_LEN, DEFAULT_MAX_PLAYERS,
 };
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
+use std::cell::RefCell;
 use std::collections::HashMap;
 use std::sync::RwLock;
-use std::time::{Duration, Instant};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 use subtle::ConstantTimeEq;
+use tokio::sync::broadcast::{self, error::RecvError};
 use uuid::Uuid;
 
 /// Per-tournament entry: tournament data + last activity time (for auto-cleanup).
 struct TournamentEntry {
     tournament: Tournament,
     last_activity: Instant,
+    /// Responses to recent mutating requests, keyed by `Idempotency-Key` and route.
+    idempotency: IdempotencyCache<CachedResponse>,
+    /// Tournament JSON published after each successful mutation, for `/events` subscribers.
+    events: broadcast::Sender<String>,
+}
+
+impl TournamentEntry {
+    fn new(tournament: Tournament) -> Self {
+        Self {
+            tournament,
+            last_activity: Instant::now(),
+            idempotency: IdempotencyCache::default(),
+            events: broadcast::channel(EVENT_CHANNEL_CAPACITY).0,
+        }
+    }
+}
+
+/// Snapshots buffered per event stream; a slower client skips ahead to the newest one.
+const EVENT_CHANNEL_CAPACITY: usize = 16;
+
+/// Idle event streams get a keep-alive comment this often so proxies keep them open.
+const EVENT_KEEP_ALIVE: Duration = Duration::from_secs(15);
+
+/// Header a client may send on mutating requests so retries are not re-executed.
+const IDEMPOTENCY_KEY_HEADER: &str = "idempotency-key";
+
+/// Response stored for an idempotency key: replayed verbatim on a repeat request.
+#[derive(Clone)]
+struct CachedResponse {
+    status: StatusCode,
+    body: String,
+}
+
+impl CachedResponse {
+    fn to_response(&self) -> HttpResponse {
+        HttpResponse::build(self.status)
+            .content_type("application/json")
+            .body(self.body.clone())
+    }
+}
+
+/// Tournament payload for API responses: the public view plus computed planning fields.
+fn tournament_json(tournament: &Tournament) -> serde_json::Value {
+    with_derived_fields(
+        tournament,
+        serde_json::to_value(tournament.public_view()).unwrap_or_default(),
+    )
+}
+
+/// Add the computed fields (`can_start`, `next_actions`, ...) to a serialized tournament.
+fn with_derived_fields(tournament: &Tournament, mut json: serde_json::Value) -> serde_json::Value {
+    if let Some(obj) = json.as_object_mut() {
+        obj.insert(
+            "max_possible_rounds".into(),
+            tournament.max_possible_rounds().into(),
+        );
+        obj.insert(
+            "players_required_to_start".into(),
+            tournament.players_required_to_start().into(),
+        );
+        obj.insert(
+            "can_start".into(),
+            tournament.can_start_in_mode(tournament.mode).into(),
+        );
+        obj.insert(
+            "duration_seconds".into(),
+            tournament.duration().map(|d| d.as_secs()).into(),
+        );
+        obj.insert(
+            "next_actions".into(),
+            serde_json::to_value(tournament.available_actions()).unwrap_or_default(),
+        );
+        obj.insert(
+            "final_placements".into(),
+            serde_json::to_value(tournament.final_placements()).unwrap_or_default(),
+        );
+    }
+    json
+}
+
+/// The request's `Idempotency-Key`, scoped to its method and path.
+fn idempotency_key(req: &HttpRequest) -> Option<IdempotencyKey> {
+    let key = req.headers().get(IDEMPOTENCY_KEY_HEADER)?.to_str().ok()?;
+    IdempotencyKey::new(req.method().as_str(), req.path(), key)
+}
+
+/// Query parameter alternative to the `If-Match` header.
+#[derive(Deserialize)]
+struct ExpectedVersionQuery {
+    expected_version: Option<u64>,
+}
+
+/// Version the client expects the tournament to be at: `If-Match` (an optionally quoted number,
+/// `*` meaning any) or `?expected_version=`. `Err` if either is present but not a version.
+fn expected_version(req: &HttpRequest) -> Result<Option<u64>, ()> {
+    if let Some(value) = req.headers().get(header::IF_MATCH) {
+        let value = value.to_str().map_err(|_| ())?.trim();
+        if value == "*" {
+            return Ok(None);
+        }
+        let value = value.strip_prefix("W/").unwrap_or(value).trim_matches('"');
+        return value.parse().map(Some).map_err(|_| ());
+    }
+    web::Query::<ExpectedVersionQuery>::from_query(req.query_string())
+        .map(|q| q.expected_version)
+        .map_err(|_| ())
+}
+
+/// Run a mutating action on the entry's tournament and respond with the tournament (or the error).
+/// If the request carries an `Idempotency-Key` already seen for this tournament on the same route,
+/// the cached response is returned and the action is not executed again. Rejected while the tournament is paused.
+fn apply_mutation(
+    req: &HttpRequest,
+    entry: &mut TournamentEntry,
+    action: impl FnOnce(&mut Tournament) -> Result<(), TournamentError>,
+) -> HttpResponse {
+    apply_mutation_with_extras(req, entry, action, |_| serde_json::Map::new())
+}
+
+/// [`apply_mutation`] without the pause check. Only for pause and resume themselves.
+fn apply_mutation_even_if_paused(
+    req: &HttpRequest,
+    entry: &mut TournamentEntry,
+    action: impl FnOnce(&mut Tournament) -> Result<(), TournamentError>,
+) -> HttpResponse {
+    run_mutation(
+        req,
+        entry,
+        action,
+        |_| serde_json::Map::new(),
+        |_, _| serde_json::Map::new(),
+    )
+}
+
+/// [`apply_mutation`], adding the fields `extras` builds from the updated tournament to the
+/// tournament JSON on success.
+fn apply_mutation_with_extras(
+    req: &HttpRequest,
+    entry: &mut TournamentEntry,
+    action: impl FnOnce(&mut Tournament) -> Result<(), TournamentError>,
+    extras: impl FnOnce(&Tournament) -> serde_json::Map<String, serde_json::Value>,
+) -> HttpResponse {
+    apply_mutation_detailed(req, entry, action, extras, |_, _| serde_json::Map::new())
+}
+
+/// [`apply_mutation_with_extras`], also adding the fields `error_details` builds from the
+/// (unchanged) tournament and the error to a 400 error body.
+fn apply_mutation_detailed(
+    req: &HttpRequest,
+    entry: &mut TournamentEntry,
+    action: impl FnOnce(&mut Tournament) -> Result<(), TournamentError>,
+    extras: impl FnOnce(&Tournament) -> serde_json::Map<String, serde_json::Value>,
+    error_details: impl FnOnce(
+        &Tournament,
+        &TournamentError,
+    ) -> serde_json::Map<String, serde_json::Value>,
+) -> HttpResponse {
+    run_mutation(
+        req,
+        entry,
+        |t| {
+            t.ensure_not_paused()?;
+            action(t)
+        },
+        extras,
+        error_details,
+    )
+}
+
+/// Shared body of the `apply_mutation*` helpers; the caller decides about the pause check.
+fn run_mutation(
+    req: &HttpRequest,
+    entry: &mut TournamentEntry,
+    action: impl FnOnce(&mut Tournament) -> Result<(), TournamentError>,
+    extras: impl FnOnce(&Tournament) -> serde_json::Map<String, serde_json::Value>,
+    error_details: impl FnOnce(
+        &Tournament,
+        &TournamentError,
+    ) -> serde_json::Map<String, serde_json::Value>,
+) -> HttpResponse {
+    let key = idempotency_key(req);
+    if let Some(cached) = key.as_ref().and_then(|k| entry.idempotency.get(k)) {
+        return cached.to_response();
+    }
+    let Ok(expected) = expected_version(req) else {
+        return HttpResponse::BadRequest().json(serde_json::json!({ "error": "Invalid version" }));
+    };
+    let (status, body) = match entry.tournament.apply_versioned(expected, action) {
+        Ok(()) => {
+            audit(req, &entry.tournament);
+            let mut json = tournament_json(&entry.tournament);
+            if entry.events.receiver_count() > 0 {
+                // No subscriber left is not an error; the next one gets a snapshot on connect.
+                let _ = entry.events.send(json.to_string());
+            }
+            if let Some(obj) = json.as_object_mut() {
+                obj.extend(extras(&entry.tournament));
+            }
+            (StatusCode::OK, serde_json::to_string(&json))
+        }
+        Err(e @ TournamentError::VersionConflict { .. }) => (
+            StatusCode::CONFLICT,
+            serde_json::to_string(&serde_json::json!({ "error": e.to_string() })),
+        ),
+        Err(e) => {
+            let mut json = serde_json::json!({ "error": e.to_string() });
+            if let Some(obj) = json.as_object_mut() {
+                obj.extend(error_details(&entry.tournament, &e));
+            }
+            (StatusCode::BAD_REQUEST, serde_json::to_string(&json))
+        }
+    };
+    let Ok(body) = body else {
+        return HttpResponse::InternalServerError().body("serialization error");
+    };
+    let response = CachedResponse { status, body };
+    if let Some(key) = key {
+        entry.idempotency.insert(key, response.clone());
+    }
+    response.to_response()
+}
+
+/// The audit log from `AUDIT_LOG`, when configured.
+type AuditState = Data<Option<AuditLog>>;
+
+/// Append a successful mutation of `tournament` to the audit log, if there is one. The action is
+/// the request's method and route; a failed write is logged and does not fail the request.
+fn audit(req: &HttpRequest, tournament: &Tournament) {
+    let Some(Some(audit_log)) = req.app_data::<AuditState>().map(|d| d.as_ref()) else {
+        return;
+    };
+    let route = req
+        .match_pattern()
+        .unwrap_or_else(|| req.path().to_string());
+    let state = serde_json::to_value(tournament.state).unwrap_or_default();
+    let summary = format!(
+        "{}, version {}, {} active",
+        state.as_str().unwrap_or_default(),
+        tournament.version,
+        tournament.players.len()
+    );
+    if let Err(e) = audit_log.record(
+        tournament.id,
+        &format!("{} {}", req.method(), route),
+        &summary,
+    ) {
+        log::warn!("Could not write audit log: {}", e);
+    }
 }
 
 /// Plaintext site password (intentionally not secret for this deployment).
@@ -94,7 +374,8 @@ async fn site_gate_middleware(
         || path.starts_with("/static/")
         || (path == "/" && method == actix_web::http::Method::GET)
         || (path == "/api/site-gate/check" && method == actix_web::http::Method::GET)
-        || (path == "/api/site-gate" && method == actix_web::http::Method::POST);
+        || (path == "/api/site-gate" && method == actix_web::http::Method::POST)
+        || (path.starts_with("/api/share/") && method == actix_web::http::Method::GET);
 
     if exempt || site_gate_header_ok(req.request(), &gate) {
         return next.call(req).await;
@@ -107,11 +388,75 @@ async fn site_gate_middleware(
     )
 }
 
-/// In-memory state: many tournaments by ID (sessioned). Entries are removed after 6h inactivity.
+/// Token for admin-only endpoints (env `ADMIN_TOKEN`); `None` disables them.
+#[derive(Clone)]
+struct AdminToken(Option<String>);
+
+/// Header carrying the admin token.
+const ADMIN_TOKEN_HEADER: &str = "x-dart-admin-token";
+
+impl AdminToken {
+    /// Whether the request carries the configured admin token (constant-time compared).
+    fn allows(&self, req: &HttpRequest) -> bool {
+        let Some(expected) = &self.0 else {
+            return false;
+        };
+        let Some(s) = req
+            .headers()
+            .get(ADMIN_TOKEN_HEADER)
+            .and_then(|h| h.to_str().ok())
+        else {
+            return false;
+        };
+        s.len() == expected.len() && s.as_bytes().ct_eq(expected.as_bytes()).into()
+    }
+}
+
+/// In-memory state: many tournaments by ID (sessioned). Entries are removed after the inactivity timeout.
 type AppState = Data<RwLock<HashMap<TournamentId, TournamentEntry>>>;
 
-/// Inactivity threshold: tournaments not accessed for this long are removed.
-const INACTIVITY_TIMEOUT: Duration = Duration::from_secs(12 * 3600);
+/// Read-only share links (share id → tournament); links of removed tournaments are dropped by the
+/// cleanup.
+type ShareState = Data<RwLock<ShareLinks>>;
+
+/// Default inactivity threshold: tournaments not accessed for this long are removed.
+const DEFAULT_INACTIVITY_TIMEOUT_SECS: u64 = 12 * 3600;
+
+/// Default interval between cleanup passes.
+const DEFAULT_CLEANUP_INTERVAL_SECS: u64 = 30 * 60;
+
+/// How often the kiosk auto-submit task checks for rounds whose countdown has run out.
+const AUTO_SUBMIT_TICK: Duration = Duration::from_secs(1);
+
+/// Lower bound for both cleanup settings, so a typo cannot make the cleanup spin or wipe active games.
+const MIN_CLEANUP_SECS: u64 = 60;
+
+/// Read a duration in seconds from env `name`, falling back to `default` when unset or invalid,
+/// and never going below [`MIN_CLEANUP_SECS`].
+fn env_duration_secs(name: &str, default: u64) -> Duration {
+    let secs = match std::env::var(name) {
+        Ok(v) => v.trim().parse().unwrap_or_else(|_| {
+            log::warn!("Ignoring invalid {}={:?}, using {}s", name, v, default);
+            default
+        }),
+        Err(_) => default,
+    };
+    Duration::from_secs(secs.max(MIN_CLEANUP_SECS))
+}
+
+/// Limit from env `name` (must be a positive integer), else `default`.
+fn env_limit(name: &str, default: usize) -> usize {
+    match std::env::var(name) {
+        Ok(v) => match v.trim().parse() {
+            Ok(n) if n > 0 => n,
+            _ => {
+                log::warn!("Ignoring invalid {}={:?}, using {}", name, v, default);
+                default
+            }
+        },
+        Err(_) => default,
+    }
+}
 
 #[derive(Serialize)]
 struct HealthResponse {
@@ -119,12 +464,58 @@ struct HealthResponse {
     service: &'static str,
 }
 
+#[derive(Deserialize)]
+struct QuickstartBody {
+    names: Vec<String>,
+    #[serde(default = "default_max_losses")]
+    max_losses: u32,
+    #[serde(default)]
+    mode: dart_tournament_web::TournamentMode,
+}
+
 #[derive(Deserialize)]
 struct CreateTournamentBody {
     #[serde(default = "default_max_losses")]
     max_losses: u32,
     #[serde(default)]
     mode: dart_tournament_web::TournamentMode,
+    #[serde(default)]
+    balance_teams: bool,
+    #[serde(default)]
+    finals_format: FinalsFormat,
+    #[serde(default)]
+    format: TournamentFormat,
+    #[serde(default)]
+    forfeit_policy: ForfeitPolicy,
+    shuffle_match_order: Option<bool>,
+    #[serde(default)]
+    auto_generate_next: bool,
+    #[serde(default)]
+    strict_bye_rotation: bool,
+    #[serde(default)]
+    deterministic: bool,
+    #[serde(default)]
+    single_champion: bool,
+    #[serde(default)]
+    draws_allowed: bool,
+    #[serde(default)]
+    seeding: SeedingStrategy,
+    #[serde(default)]
+    points_config: PointsConfig,
+    #[serde(default)]
+    redemption: bool,
+    legs_per_final_match: Option<u32>,
+    num_boards: Option<u32>,
+    max_concurrent_matches: Option<usize>,
+    reshuffle_every: Option<u32>,
+    knockout_after_rounds: Option<u32>,
+    #[serde(default)]
+    grace_matches: u32,
+    round_cap: Option<u32>,
+    #[serde(default)]
+    auto_start_semis: bool,
+    #[serde(default)]
+    allow_ghost: bool,
 }
 
 #[derive(Deserialize)]
@@ -139,6 +530,109 @@ fn default_max_losses() -> u32 {
 #[derive(Deserialize)]
 struct AddPlayerBody {
     name: String,
+    #[serde(default)]
+    rating: Option<i32>,
+    #[serde(default)]
+    color: Option<String>,
+    #[serde(default)]
+    avatar_url: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct ImportedPlayer {
+    name: String,
+    #[serde(default)]
+    wins: u32,
+    #[serde(default)]
+    losses: u32,
+    #[serde(default)]
+    sat_out: u32,
+}
+
+#[derive(Deserialize)]
+struct ImportPlayersWithStatsBody {
+    players: Vec<ImportedPlayer>,
+}
+
+#[derive(Deserialize)]
+struct SetPlayerAppearanceBody {
+    color: Option<String>,
+    avatar_url: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct EliminateBulkBody {
+    player_ids: Vec<Uuid>,
+}
+
+#[derive(Deserialize)]
+struct SetHandicapSpotBody {
+    handicap_spot: i32,
+}
+
+#[derive(Deserialize)]
+struct SetPlayerRatingBody {
+    rating: Option<i32>,
+}
+
+#[derive(Deserialize)]
+struct SetLegsPerFinalMatchBody {
+    legs_per_final_match: u32,
+}
+
+#[derive(Deserialize)]
+struct SetFinalTieResolutionBody {
+    final_tie_resolution: FinalTieResolution,
+}
+
+#[derive(Deserialize)]
+struct SetNumBoardsBody {
+    num_boards: Option<u32>,
+}
+
+#[derive(Deserialize)]
+struct SetMaxConcurrentMatchesBody {
+    max_concurrent_matches: Option<usize>,
+}
+
+#[derive(Deserialize)]
+struct SetReshuffleEveryBody {
+    reshuffle_every: u32,
+}
+
+#[derive(Deserialize)]
+struct SetKnockoutAfterRoundsBody {
+    knockout_after_rounds: u32,
+}
+
+#[derive(Deserialize)]
+struct SetAutoSubmitAfterBody {
+    auto_submit_after_secs: Option<u64>,
+}
+
+#[derive(Deserialize)]
+struct SetRoundCapBody {
+    round_cap: Option<u32>,
+}
+
+#[derive(Deserialize)]
+struct ResetBody {
+    /// Keep wins/losses and return to group play; otherwise restart from Setup like `/restart`.
+    keep_stats: bool,
+    /// With `keep_stats`: bring group play eliminations back into the active field.
+    #[serde(default)]
+    reactivate_eliminated: bool,
+}
+
+#[derive(Deserialize)]
+struct SetSeedingBody {
+    seeding: SeedingStrategy,
+}
+
+#[derive(Deserialize)]
+struct SetScoringModeBody {
+    round: RoundType,
+    mode: ScoringMode,
 }
 
 #[derive(Deserialize)]
@@ -146,17 +640,78 @@ struct MaxLossesBody {
     max_losses: u32,
 }
 
+#[derive(Deserialize)]
+struct AmendResultBody {
+    team: Team,
+}
+
+/// A match given by `match_id` or by its `match_number` (easier to read out at the board).
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum MatchRef {
+    Id { match_id: Uuid },
+    Number { match_number: u32 },
+}
+
+impl MatchRef {
+    fn resolve(&self, tournament: &Tournament) -> Result<Uuid, TournamentError> {
+        match *self {
+            MatchRef::Id { match_id } => Ok(match_id),
+            MatchRef::Number { match_number } => tournament
+                .match_by_number(match_number)
+                .map(|m| m.id)
+                .ok_or(TournamentError::MatchNumberNotFound(match_number)),
+        }
+    }
+}
+
 #[derive(Deserialize)]
 struct SetMatchWinnerBody {
-    match_id: Uuid,
+    #[serde(flatten)]
+    target: MatchRef,
     team: Team,
 }
 
+#[derive(Deserialize)]
+struct FinalsDrawBody {
+    #[serde(flatten)]
+    target: MatchRef,
+}
+
+#[derive(Deserialize)]
+struct SuddenDeathPlayersBody {
+    team_1_player: Uuid,
+    team_2_player: Uuid,
+}
+
+#[derive(Deserialize)]
+struct SwapPlayersBody {
+    player_a: Uuid,
+    player_b: Uuid,
+}
+
+#[derive(Deserialize)]
+struct SetMatchOutcomeBody {
+    #[serde(flatten)]
+    target: MatchRef,
+    team: MatchOutcome,
+}
+
+#[derive(Deserialize)]
+struct SetDrawsAllowedBody {
+    draws_allowed: bool,
+}
+
 #[derive(Deserialize)]
 struct FinalSelectionAddBackBody {
     player_ids: Vec<Uuid>,
 }
 
+#[derive(Deserialize)]
+struct PlayerOrderBody {
+    player_ids: Vec<Uuid>,
+}
+
 #[derive(Deserialize)]
 struct SetPlayerLossesBody {
     losses: u32,
@@ -167,12 +722,124 @@ struct SetModeBody {
     mode: dart_tournament_web::TournamentMode,
 }
 
+#[derive(Deserialize)]
+struct SetBalanceTeamsBody {
+    balance_teams: bool,
+}
+
+#[derive(Deserialize)]
+struct SetShuffleMatchOrderBody {
+    shuffle_match_order: bool,
+}
+
+#[derive(Deserialize)]
+struct SetAutoGenerateNextBody {
+    auto_generate_next: bool,
+}
+
+#[derive(Deserialize)]
+struct SetGraceMatchesBody {
+    grace_matches: u32,
+}
+
+#[derive(Deserialize)]
+struct SetAutoStartSemisBody {
+    auto_start_semis: bool,
+}
+
+#[derive(Deserialize)]
+struct SetAllowGhostBody {
+    allow_ghost: bool,
+}
+
+#[derive(Deserialize)]
+struct SetStrictByeRotationBody {
+    strict_bye_rotation: bool,
+}
+
+#[derive(Deserialize)]
+struct SetSingleChampionBody {
+    single_champion: bool,
+}
+
+#[derive(Deserialize)]
+struct SetChampionNameBody {
+    champion_team_name: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct SetDeterministicBody {
+    deterministic: bool,
+}
+
+#[derive(Deserialize)]
+struct SetFinalsFormatBody {
+    finals_format: FinalsFormat,
+}
+
+#[derive(Deserialize)]
+struct SetFormatBody {
+    format: TournamentFormat,
+}
+
+#[derive(Deserialize)]
+struct SetForfeitPolicyBody {
+    forfeit_policy: ForfeitPolicy,
+}
+
+/// Default and maximum page size for `GET /api/tournaments/{id}/history`.
+const HISTORY_DEFAULT_LIMIT: usize = 50;
+const HISTORY_MAX_LIMIT: usize = 200;
+
+/// Query for the match history endpoint: `?offset=&limit=` (limit is clamped to [`HISTORY_MAX_LIMIT`]).
+#[derive(Deserialize)]
+struct HistoryQuery {
+    #[serde(default)]
+    offset: usize,
+    limit: Option<usize>,
+}
+
+/// Query for the tournament list: `?state=&mode=` (snake_case names, e.g. `group_play`, `two_v_two`).
+#[derive(Deserialize)]
+struct ListTournamentsQuery {
+    state: Option<String>,
+    mode: Option<String>,
+}
+
+/// Parse an optional snake_case enum name from a query string; `Err` names the bad value.
+fn parse_query_enum<T: serde::de::DeserializeOwned>(
+    value: &Option<String>,
+    field: &str,
+) -> Result<Option<T>, String> {
+    value
+        .as_ref()
+        .map(|v| {
+            serde_json::from_value(serde_json::Value::String(v.clone()))
+                .map_err(|_| format!("unknown {field}: {v}"))
+        })
+        .transpose()
+}
+
+/// Query for the current round: `?names=true` adds `match_views` with names inline.
+#[derive(Deserialize)]
+struct CurrentRoundQuery {
+    #[serde(default)]
+    names: bool,
+}
+
 /// Path segment: tournament id (e.g. /api/tournaments/{id})
 #[derive(Deserialize)]
 struct TournamentPath {
     id: TournamentId,
 }
 
+/// Path segments: tournament id and match id (e.g. /api/tournaments/{id}/matches/{match_id}/result)
+#[derive(Deserialize)]
+struct TournamentMatchPath {
+    id: TournamentId,
+    match_id: Uuid,
+}
+
 /// Path segments: tournament id and player id (e.g. /api/tournaments/{id}/players/{player_id})
 #[derive(Deserialize)]
 struct TournamentPlayerPath {
@@ -180,6 +847,12 @@ struct TournamentPlayerPath {
     player_id: Uuid,
 }
 
+/// Path segment: share id (e.g. /api/share/{share_id})
+#[derive(Deserialize)]
+struct SharePath {
+    share_id: Uuid,
+}
+
 #[get("/api/health")]
 async fn api_health() -> impl Responder {
     HttpResponse::Ok().json(HealthResponse {
@@ -188,90 +861,1891 @@ async fn api_health() -> impl Responder {
     })
 }
 
+/// OpenAPI 3 description of this API (see `dart_tournament_web::openapi`).
+#[get("/api/openapi.json")]
+async fn api_openapi() -> HttpResponse {
+    HttpResponse::Ok().json(openapi_document())
+}
+
 /// Avoid 404 in browser tab: favicon not required for app logic.
 #[get("/favicon.ico")]
 async fn favicon() -> HttpResponse {
     HttpResponse::NoContent().finish()
 }
 
-/// Returns 204 if `X-Dart-Site-Gate` matches; 401 if not unlocked yet.
-#[get("/api/site-gate/check")]
-async fn api_site_gate_check(req: HttpRequest, gate: web::Data<SiteGate>) -> HttpResponse {
-    if site_gate_header_ok(&req, gate.get_ref()) {
-        HttpResponse::NoContent().finish()
-    } else {
-        HttpResponse::Unauthorized().finish()
-    }
+/// Returns 204 if `X-Dart-Site-Gate` matches; 401 if not unlocked yet.
+#[get("/api/site-gate/check")]
+async fn api_site_gate_check(req: HttpRequest, gate: web::Data<SiteGate>) -> HttpResponse {
+    if site_gate_header_ok(&req, gate.get_ref()) {
+        HttpResponse::NoContent().finish()
+    } else {
+        HttpResponse::Unauthorized().finish()
+    }
+}
+
+/// POST JSON `{ "password": "..." }` — returns `{ "token": "<hex>" }` on success (must match [`SITE_GATE_PLAIN`]).
+#[post("/api/site-gate")]
+async fn api_site_gate_login(
+    gate: web::Data<SiteGate>,
+    body: Json<SiteGateLoginBody>,
+) -> HttpResponse {
+    let expected = gate.expected_token.as_str();
+    let got = site_gate_token(body.password.trim());
+    if got.as_bytes().ct_eq(expected.as_bytes()).into() {
+        HttpResponse::Ok().json(serde_json::json!({ "token": expected }))
+    } else {
+        HttpResponse::Unauthorized().json(serde_json::json!({ "error": "Wrong password" }))
+    }
+}
+
+/// Create a new tournament.
+#[post("/api/tournaments")]
+async fn api_create_tournament(
+    req: HttpRequest,
+    state: AppState,
+    limits: Data<InputLimits>,
+    body: Option<Json<CreateTournamentBody>>,
+) -> HttpResponse {
+    let mut tournament = Tournament::new(
+        default_max_losses(),
+        dart_tournament_web::TournamentMode::TwoVTwo,
+    );
+    tournament.limits = **limits;
+    if let Some(b) = body.as_ref() {
+        // Checked as one bulk update, so zero values and mode mismatches are rejected here just
+        // like by `PUT /config`.
+        let mut config = tournament.config();
+        config.max_losses = b.max_losses;
+        config.mode = b.mode;
+        config.balance_teams = b.balance_teams;
+        config.finals_format = b.finals_format;
+        config.forfeit_policy = b.forfeit_policy;
+        config.format = b.format;
+        config.draws_allowed = b.draws_allowed;
+        if let Some(shuffle) = b.shuffle_match_order {
+            config.shuffle_match_order = shuffle;
+        }
+        config.auto_generate_next = b.auto_generate_next;
+        config.grace_matches = b.grace_matches;
+        config.auto_start_semis = b.auto_start_semis;
+        config.allow_ghost = b.allow_ghost;
+        config.strict_bye_rotation = b.strict_bye_rotation;
+        config.deterministic = b.deterministic;
+        config.single_champion = b.single_champion;
+        config.seeding = b.seeding;
+        config.points_config = b.points_config;
+        config.redemption = b.redemption;
+        config.num_boards = b.num_boards;
+        config.max_concurrent_matches = b.max_concurrent_matches;
+        if let Some(rounds) = b.reshuffle_every {
+            config.reshuffle_every = rounds;
+        }
+        if let Some(rounds) = b.knockout_after_rounds {
+            config.knockout_after_rounds = rounds;
+        }
+        config.round_cap = b.round_cap;
+        if let Some(legs) = b.legs_per_final_match {
+            config.legs_per_final_match = legs;
+        }
+        if let Err(e) = tournament.set_config(config) {
+            return HttpResponse::BadRequest().json(serde_json::json!({ "error": e.to_string() }));
+        }
+    }
+    let id = tournament.id;
+    let mut g = match state.write() {
+        Ok(guard) => guard,
+        Err(_) => return HttpResponse::InternalServerError().body("lock error"),
+    };
+    audit(&req, &tournament);
+    g.insert(id, TournamentEntry::new(tournament));
+    let entry = g.get(&id).unwrap();
+    HttpResponse::Ok().json(tournament_json(&entry.tournament))
+}
+
+/// Create a tournament, add all `names` and start it in one call. The tournament is stored even
+/// when the start fails (so it can be fixed); rejected names are listed in `rejected` either way.
+#[post("/api/tournaments/quickstart")]
+async fn api_quickstart_tournament(
+    req: HttpRequest,
+    state: AppState,
+    limits: Data<InputLimits>,
+    body: Json<QuickstartBody>,
+) -> HttpResponse {
+    let body = body.into_inner();
+    if body.max_losses == 0 {
+        return HttpResponse::BadRequest()
+            .json(serde_json::json!({ "error": TournamentError::InvalidMaxLosses.to_string() }));
+    }
+    let mut tournament = Tournament::new(body.max_losses, body.mode);
+    tournament.limits = **limits;
+    let report = quickstart_tournament(&mut tournament, body.names);
+    let rejected: Vec<serde_json::Value> = report
+        .rejected
+        .iter()
+        .map(|(name, e)| serde_json::json!({ "name": name, "error": e.to_string() }))
+        .collect();
+    let response = match &report.started {
+        Ok(()) => {
+            let mut json = tournament_json(&tournament);
+            if let Some(obj) = json.as_object_mut() {
+                obj.insert("rejected".into(), rejected.into());
+            }
+            HttpResponse::Ok().json(json)
+        }
+        Err(e) => HttpResponse::BadRequest().json(serde_json::json!({
+            "error": e.to_string(),
+            "rejected": rejected,
+            "tournament": tournament_json(&tournament),
+        })),
+    };
+    let mut g = match state.write() {
+        Ok(guard) => guard,
+        Err(_) => return HttpResponse::InternalServerError().body("lock error"),
+    };
+    audit(&req, &tournament);
+    g.insert(tournament.id, TournamentEntry::new(tournament));
+    response
+}
+
+/// Every tournament on the server, most recently active first (an admin overview), optionally
+/// only those in `?state=` and/or `?mode=` (400 on an unknown value). The ids it lists control the
+/// tournaments, so it needs the admin token (403 without it, or when no `ADMIN_TOKEN` is set).
+/// Listing does not count as activity.
+#[get("/api/tournaments")]
+async fn api_list_tournaments(
+    req: HttpRequest,
+    state: AppState,
+    admin: Data<AdminToken>,
+    query: web::Query<ListTournamentsQuery>,
+) -> HttpResponse {
+    if !admin.allows(&req) {
+        return HttpResponse::Forbidden()
+            .json(serde_json::json!({ "error": "Admin token required" }));
+    }
+    let state_filter = match parse_query_enum(&query.state, "state") {
+        Ok(s) => s,
+        Err(e) => return HttpResponse::BadRequest().json(serde_json::json!({ "error": e })),
+    };
+    let mode_filter = match parse_query_enum(&query.mode, "mode") {
+        Ok(m) => m,
+        Err(e) => return HttpResponse::BadRequest().json(serde_json::json!({ "error": e })),
+    };
+    let g = match state.read() {
+        Ok(guard) => guard,
+        Err(_) => return HttpResponse::InternalServerError().body("lock error"),
+    };
+    let mut summaries: Vec<TournamentSummary> = g
+        .values()
+        .map(|entry| entry.tournament.summary(entry.last_activity))
+        .filter(|s| s.matches_filter(state_filter, mode_filter))
+        .collect();
+    summaries.sort_by_key(|s| s.last_activity_seconds_ago);
+    HttpResponse::Ok().json(summaries)
+}
+
+/// Get a tournament by id (404 if not found). Touching it refreshes last_activity; the response's
+/// `last_activity_seconds_ago` is the idle time before this request.
+#[get("/api/tournaments/{id}")]
+async fn api_get_tournament(state: AppState, path: Path<TournamentPath>) -> HttpResponse {
+    let mut g = match state.write() {
+        Ok(guard) => guard,
+        Err(_) => return HttpResponse::InternalServerError().body("lock error"),
+    };
+    match g.get_mut(&path.id) {
+        Some(entry) => {
+            let idle = entry.last_activity.elapsed().as_secs();
+            entry.last_activity = Instant::now();
+            let mut json = tournament_json(&entry.tournament);
+            if let Some(obj) = json.as_object_mut() {
+                obj.insert("last_activity_seconds_ago".into(), idle.into());
+            }
+            HttpResponse::Ok().json(json)
+        }
+        None => HttpResponse::NotFound().json(serde_json::json!({ "error": "No tournament" })),
+    }
+}
+
+/// Create (or return the existing) read-only share link for a tournament: `{ "share_id": ... }`.
+#[post("/api/tournaments/{id}/share")]
+async fn api_share_tournament(
+    req: HttpRequest,
+    state: AppState,
+    shares: ShareState,
+    path: Path<TournamentPath>,
+) -> HttpResponse {
+    let mut g = match state.write() {
+        Ok(guard) => guard,
+        Err(_) => return HttpResponse::InternalServerError().body("lock error"),
+    };
+    let Some(entry) = g.get_mut(&path.id) else {
+        return HttpResponse::NotFound().json(serde_json::json!({ "error": "No tournament" }));
+    };
+    entry.last_activity = Instant::now();
+    let mut shares = match shares.write() {
+        Ok(guard) => guard,
+        Err(_) => return HttpResponse::InternalServerError().body("lock error"),
+    };
+    let share_id = shares.share(path.id);
+    audit(&req, &entry.tournament);
+    HttpResponse::Ok().json(serde_json::json!({ "share_id": share_id }))
+}
+
+/// Revoke a tournament's share link (204 whether or not it had one).
+#[delete("/api/tournaments/{id}/share")]
+async fn api_revoke_share(
+    req: HttpRequest,
+    state: AppState,
+    shares: ShareState,
+    path: Path<TournamentPath>,
+) -> HttpResponse {
+    let mut g = match state.write() {
+        Ok(guard) => guard,
+        Err(_) => return HttpResponse::InternalServerError().body("lock error"),
+    };
+    let Some(entry) = g.get_mut(&path.id) else {
+        return HttpResponse::NotFound().json(serde_json::json!({ "error": "No tournament" }));
+    };
+    entry.last_activity = Instant::now();
+    match shares.write() {
+        Ok(mut guard) => {
+            guard.revoke(path.id);
+            audit(&req, &entry.tournament);
+            HttpResponse::NoContent().finish()
+        }
+        Err(_) => HttpResponse::InternalServerError().body("lock error"),
+    }
+}
+
+/// Read-only tournament view through a share link (404 if unknown or revoked). Viewing does not
+/// count as activity.
+#[get("/api/share/{share_id}")]
+async fn api_get_shared(
+    state: AppState,
+    shares: ShareState,
+    path: Path<SharePath>,
+) -> HttpResponse {
+    let id = match shares.read() {
+        Ok(guard) => guard.resolve(path.share_id),
+        Err(_) => return HttpResponse::InternalServerError().body("lock error"),
+    };
+    let g = match state.read() {
+        Ok(guard) => guard,
+        Err(_) => return HttpResponse::InternalServerError().body("lock error"),
+    };
+    match id.and_then(|id| g.get(&id)) {
+        Some(entry) => HttpResponse::Ok().json(with_derived_fields(
+            &entry.tournament,
+            shared_view(&entry.tournament),
+        )),
+        None => {
+            HttpResponse::NotFound().json(serde_json::json!({ "error": "No shared tournament" }))
+        }
+    }
+}
+
+/// Submitted matches in chronological order, paginated with `?offset=&limit=` (default 50, max 200).
+#[get("/api/tournaments/{id}/history")]
+async fn api_get_history(
+    state: AppState,
+    path: Path<TournamentPath>,
+    query: web::Query<HistoryQuery>,
+) -> HttpResponse {
+    let limit = query.limit.unwrap_or(HISTORY_DEFAULT_LIMIT);
+    if limit == 0 {
+        return HttpResponse::BadRequest()
+            .json(serde_json::json!({ "error": "limit must be at least 1" }));
+    }
+    let limit = limit.min(HISTORY_MAX_LIMIT);
+    let mut g = match state.write() {
+        Ok(guard) => guard,
+        Err(_) => return HttpResponse::InternalServerError().body("lock error"),
+    };
+    match g.get_mut(&path.id) {
+        Some(entry) => {
+            entry.last_activity = Instant::now();
+            let (matches, total) = entry.tournament.history_page(query.offset, limit);
+            HttpResponse::Ok().json(serde_json::json!({
+                "matches": matches,
+                "total": total,
+                "offset": query.offset,
+                "limit": limit,
+            }))
+        }
+        None => HttpResponse::NotFound().json(serde_json::json!({ "error": "No tournament" })),
+    }
+}
+
+/// Server-sent events: the tournament JSON on connect and after every change, with a keep-alive
+/// comment while idle. The stream ends when the tournament is removed.
+#[get("/api/tournaments/{id}/events")]
+async fn api_events(state: AppState, path: Path<TournamentPath>) -> HttpResponse {
+    let (snapshot, events) = {
+        let mut g = match state.write() {
+            Ok(guard) => guard,
+            Err(_) => return HttpResponse::InternalServerError().body("lock error"),
+        };
+        let Some(entry) = g.get_mut(&path.id) else {
+            return HttpResponse::NotFound().json(serde_json::json!({ "error": "No tournament" }));
+        };
+        entry.last_activity = Instant::now();
+        (
+            tournament_json(&entry.tournament).to_string(),
+            entry.events.subscribe(),
+        )
+    };
+    let stream = futures_util::stream::unfold(
+        (Some(snapshot), events),
+        |(snapshot, mut events)| async move {
+            if let Some(json) = snapshot {
+                return Some((Ok::<_, Error>(sse_data(&json)), (None, events)));
+            }
+            loop {
+                let chunk = match tokio::time::timeout(EVENT_KEEP_ALIVE, events.recv()).await {
+                    Ok(Ok(json)) => sse_data(&json),
+                    Ok(Err(RecvError::Lagged(_))) => continue,
+                    Ok(Err(RecvError::Closed)) => return None,
+                    Err(_) => web::Bytes::from_static(b": keep-alive\n\n"),
+                };
+                return Some((Ok(chunk), (None, events)));
+            }
+        },
+    );
+    HttpResponse::Ok()
+        .content_type("text/event-stream")
+        .insert_header((header::CACHE_CONTROL, "no-cache"))
+        .streaming(stream)
+}
+
+/// One server-sent `data:` event (the JSON is a single line).
+fn sse_data(json: &str) -> web::Bytes {
+    web::Bytes::from(format!("data: {json}\n\n"))
+}
+
+/// Current round only (matches, entered results, involved player names): a light poll target.
+/// With `?names=true` the matches are also given with names inline (`match_views`).
+#[get("/api/tournaments/{id}/matches/current")]
+async fn api_get_current_round(
+    state: AppState,
+    path: Path<TournamentPath>,
+    query: web::Query<CurrentRoundQuery>,
+) -> HttpResponse {
+    let mut g = match state.write() {
+        Ok(guard) => guard,
+        Err(_) => return HttpResponse::InternalServerError().body("lock error"),
+    };
+    match g.get_mut(&path.id) {
+        Some(entry) => {
+            entry.last_activity = Instant::now();
+            if query.names {
+                HttpResponse::Ok().json(entry.tournament.current_round_view_with_names())
+            } else {
+                HttpResponse::Ok().json(entry.tournament.current_round_view())
+            }
+        }
+        None => HttpResponse::NotFound().json(serde_json::json!({ "error": "No tournament" })),
+    }
+}
+
+/// Eliminated players in elimination order, each with the group play round they went out in.
+#[get("/api/tournaments/{id}/eliminated")]
+async fn api_get_eliminated(state: AppState, path: Path<TournamentPath>) -> HttpResponse {
+    let mut g = match state.write() {
+        Ok(guard) => guard,
+        Err(_) => return HttpResponse::InternalServerError().body("lock error"),
+    };
+    match g.get_mut(&path.id) {
+        Some(entry) => {
+            entry.last_activity = Instant::now();
+            HttpResponse::Ok().json(entry.tournament.eliminated_view())
+        }
+        None => HttpResponse::NotFound().json(serde_json::json!({ "error": "No tournament" })),
+    }
+}
+
+/// Group play: who would reach the semi-finals if group play ended now (best records first).
+#[get("/api/tournaments/{id}/projected-finalists")]
+async fn api_get_projected_finalists(state: AppState, path: Path<TournamentPath>) -> HttpResponse {
+    let mut g = match state.write() {
+        Ok(guard) => guard,
+        Err(_) => return HttpResponse::InternalServerError().body("lock error"),
+    };
+    match g.get_mut(&path.id) {
+        Some(entry) => {
+            entry.last_activity = Instant::now();
+            HttpResponse::Ok().json(entry.tournament.projected_finalists())
+        }
+        None => HttpResponse::NotFound().json(serde_json::json!({ "error": "No tournament" })),
+    }
+}
+
+/// Every setting of the tournament in one object.
+#[get("/api/tournaments/{id}/config")]
+async fn api_get_config(state: AppState, path: Path<TournamentPath>) -> HttpResponse {
+    let mut g = match state.write() {
+        Ok(guard) => guard,
+        Err(_) => return HttpResponse::InternalServerError().body("lock error"),
+    };
+    match g.get_mut(&path.id) {
+        Some(entry) => {
+            entry.last_activity = Instant::now();
+            HttpResponse::Ok().json(entry.tournament.config())
+        }
+        None => HttpResponse::NotFound().json(serde_json::json!({ "error": "No tournament" })),
+    }
+}
+
+/// Replace every setting at once (Setup only); nothing changes if any value is invalid.
+#[put("/api/tournaments/{id}/config")]
+async fn api_set_config(
+    req: HttpRequest,
+    state: AppState,
+    path: Path<TournamentPath>,
+    body: Json<TournamentConfig>,
+) -> HttpResponse {
+    let mut g = match state.write() {
+        Ok(guard) => guard,
+        Err(_) => return HttpResponse::InternalServerError().body("lock error"),
+    };
+    let entry = match g.get_mut(&path.id) {
+        Some(e) => e,
+        None => {
+            return HttpResponse::NotFound().json(serde_json::json!({ "error": "No tournament" }))
+        }
+    };
+    entry.last_activity = Instant::now();
+    apply_mutation(&req, entry, |t| t.set_config(body.clone()))
+}
+
+/// Player list sizes, `max_losses` and state, for a UI header.
+#[get("/api/tournaments/{id}/counts")]
+async fn api_get_counts(state: AppState, path: Path<TournamentPath>) -> HttpResponse {
+    let mut g = match state.write() {
+        Ok(guard) => guard,
+        Err(_) => return HttpResponse::InternalServerError().body("lock error"),
+    };
+    match g.get_mut(&path.id) {
+        Some(entry) => {
+            entry.last_activity = Instant::now();
+            HttpResponse::Ok().json(entry.tournament.counts())
+        }
+        None => HttpResponse::NotFound().json(serde_json::json!({ "error": "No tournament" })),
+    }
+}
+
+/// Consistency diagnostics for debugging: a list of broken invariants (empty when healthy).
+#[get("/api/tournaments/{id}/validate")]
+async fn api_validate(state: AppState, path: Path<TournamentPath>) -> HttpResponse {
+    let mut g = match state.write() {
+        Ok(guard) => guard,
+        Err(_) => return HttpResponse::InternalServerError().body("lock error"),
+    };
+    match g.get_mut(&path.id) {
+        Some(entry) => {
+            entry.last_activity = Instant::now();
+            HttpResponse::Ok().json(entry.tournament.check_invariants())
+        }
+        None => HttpResponse::NotFound().json(serde_json::json!({ "error": "No tournament" })),
+    }
+}
+
+/// 2v2 team leaderboard: each pairing's record in the matches it played together.
+#[get("/api/tournaments/{id}/team-standings")]
+async fn api_get_team_standings(state: AppState, path: Path<TournamentPath>) -> HttpResponse {
+    let mut g = match state.write() {
+        Ok(guard) => guard,
+        Err(_) => return HttpResponse::InternalServerError().body("lock error"),
+    };
+    match g.get_mut(&path.id) {
+        Some(entry) => {
+            entry.last_activity = Instant::now();
+            HttpResponse::Ok().json(entry.tournament.team_standings())
+        }
+        None => HttpResponse::NotFound().json(serde_json::json!({ "error": "No tournament" })),
+    }
+}
+
+/// Whether the tournament is over and who won (`completed: false` with empty lists until then).
+#[get("/api/tournaments/{id}/result")]
+async fn api_get_result(state: AppState, path: Path<TournamentPath>) -> HttpResponse {
+    let mut g = match state.write() {
+        Ok(guard) => guard,
+        Err(_) => return HttpResponse::InternalServerError().body("lock error"),
+    };
+    match g.get_mut(&path.id) {
+        Some(entry) => {
+            entry.last_activity = Instant::now();
+            HttpResponse::Ok().json(entry.tournament.final_result().unwrap_or_default())
+        }
+        None => HttpResponse::NotFound().json(serde_json::json!({ "error": "No tournament" })),
+    }
+}
+
+/// Possible finals pairings given the semi-final results recorded so far.
+#[get("/api/tournaments/{id}/projection")]
+async fn api_get_projection(state: AppState, path: Path<TournamentPath>) -> HttpResponse {
+    let mut g = match state.write() {
+        Ok(guard) => guard,
+        Err(_) => return HttpResponse::InternalServerError().body("lock error"),
+    };
+    match g.get_mut(&path.id) {
+        Some(entry) => {
+            entry.last_activity = Instant::now();
+            HttpResponse::Ok().json(entry.tournament.projected_bracket())
+        }
+        None => HttpResponse::NotFound().json(serde_json::json!({ "error": "No tournament" })),
+    }
+}
+
+/// The playoff matches so far (semi-finals, finals, grand final) with names inline.
+#[get("/api/tournaments/{id}/bracket")]
+async fn api_get_bracket(state: AppState, path: Path<TournamentPath>) -> HttpResponse {
+    let mut g = match state.write() {
+        Ok(guard) => guard,
+        Err(_) => return HttpResponse::InternalServerError().body("lock error"),
+    };
+    match g.get_mut(&path.id) {
+        Some(entry) => {
+            entry.last_activity = Instant::now();
+            HttpResponse::Ok().json(entry.tournament.bracket_view())
+        }
+        None => HttpResponse::NotFound().json(serde_json::json!({ "error": "No tournament" })),
+    }
+}
+
+/// The semi-finals → finals bracket as a printable SVG (placeholder before the semi-finals).
+#[get("/api/tournaments/{id}/bracket.svg")]
+async fn api_get_bracket_svg(state: AppState, path: Path<TournamentPath>) -> HttpResponse {
+    let mut g = match state.write() {
+        Ok(guard) => guard,
+        Err(_) => return HttpResponse::InternalServerError().body("lock error"),
+    };
+    match g.get_mut(&path.id) {
+        Some(entry) => {
+            entry.last_activity = Instant::now();
+            HttpResponse::Ok()
+                .content_type("image/svg+xml")
+                .body(bracket_svg(&entry.tournament))
+        }
+        None => HttpResponse::NotFound().json(serde_json::json!({ "error": "No tournament" })),
+    }
+}
+
+/// Printable results sheet as an HTML fragment (current standings until completed).
+#[get("/api/tournaments/{id}/results.html")]
+async fn api_get_results_html(state: AppState, path: Path<TournamentPath>) -> HttpResponse {
+    let mut g = match state.write() {
+        Ok(guard) => guard,
+        Err(_) => return HttpResponse::InternalServerError().body("lock error"),
+    };
+    match g.get_mut(&path.id) {
+        Some(entry) => {
+            entry.last_activity = Instant::now();
+            HttpResponse::Ok()
+                .content_type("text/html; charset=utf-8")
+                .body(results_html(&entry.tournament))
+        }
+        None => HttpResponse::NotFound().json(serde_json::json!({ "error": "No tournament" })),
+    }
+}
+
+/// Add players from a `text/csv` body of `name[,rating]` rows (Setup only). Good rows are added
+/// even when others fail; the response lists `malformed` rows by line and `rejected` names.
+#[post("/api/tournaments/{id}/players/import-csv")]
+async fn api_import_players_csv(
+    req: HttpRequest,
+    state: AppState,
+    path: Path<TournamentPath>,
+    body: String,
+) -> HttpResponse {
+    let mut g = match state.write() {
+        Ok(guard) => guard,
+        Err(_) => return HttpResponse::InternalServerError().body("lock error"),
+    };
+    let entry = match g.get_mut(&path.id) {
+        Some(e) => e,
+        None => {
+            return HttpResponse::NotFound().json(serde_json::json!({ "error": "No tournament" }))
+        }
+    };
+    entry.last_activity = Instant::now();
+    let import = RefCell::new(RosterImport::default());
+    apply_mutation_with_extras(
+        &req,
+        entry,
+        |t| {
+            *import.borrow_mut() = import_roster_csv(t, &body)?;
+            Ok(())
+        },
+        |_| {
+            let import = import.borrow();
+            let malformed: Vec<serde_json::Value> = import
+                .malformed
+                .iter()
+                .map(|e| serde_json::json!({ "line": e.line, "error": e.message }))
+                .collect();
+            let rejected: Vec<serde_json::Value> = import
+                .rejected
+                .iter()
+                .map(|(name, e)| serde_json::json!({ "name": name, "error": e.to_string() }))
+                .collect();
+            let mut extras = serde_json::Map::new();
+            extras.insert("added".into(), import.added.into());
+            extras.insert("malformed".into(), malformed.into());
+            extras.insert("rejected".into(), rejected.into());
+            extras
+        },
+    )
+}
+
+/// Add players who already have wins, losses and sit-outs (Setup only), e.g. to continue a
+/// tournament tracked elsewhere. Players with `max_losses` losses start eliminated. Names the
+/// tournament rejects are listed in `rejected`; the others are still added.
+#[post("/api/tournaments/{id}/players/import-stats")]
+async fn api_import_players_with_stats(
+    req: HttpRequest,
+    state: AppState,
+    path: Path<TournamentPath>,
+    body: Json<ImportPlayersWithStatsBody>,
+) -> HttpResponse {
+    let mut g = match state.write() {
+        Ok(guard) => guard,
+        Err(_) => return HttpResponse::InternalServerError().body("lock error"),
+    };
+    let entry = match g.get_mut(&path.id) {
+        Some(e) => e,
+        None => {
+            return HttpResponse::NotFound().json(serde_json::json!({ "error": "No tournament" }))
+        }
+    };
+    entry.last_activity = Instant::now();
+    let rejected = RefCell::new(Vec::new());
+    apply_mutation_with_extras(
+        &req,
+        entry,
+        |t| {
+            if t.state != dart_tournament_web::TournamentState::Setup {
+                return Err(TournamentError::InvalidState);
+            }
+            for p in &body.players {
+                if let Err(e) = t.add_player_with_stats(p.name.trim(), p.wins, p.losses, p.sat_out)
+                {
+                    rejected
+                        .borrow_mut()
+                        .push(serde_json::json!({ "name": p.name, "error": e.to_string() }));
+                }
+            }
+            Ok(())
+        },
+        |_| {
+            let mut extras = serde_json::Map::new();
+            extras.insert("rejected".into(), rejected.take().into());
+            extras
+        },
+    )
+}
+
+#[post("/api/tournaments/{id}/players")]
+async fn api_add_player(
+    req: HttpRequest,
+    state: AppState,
+    path: Path<TournamentPath>,
+    body: Json<AddPlayerBody>,
+) -> HttpResponse {
+    let mut g = match state.write() {
+        Ok(guard) => guard,
+        Err(_) => return HttpResponse::InternalServerError().body("lock error"),
+    };
+    let entry = match g.get_mut(&path.id) {
+        Some(e) => e,
+        None => {
+            return HttpResponse::NotFound().json(serde_json::json!({ "error": "No tournament" }))
+        }
+    };
+    entry.last_activity = Instant::now();
+    apply_mutation(&req, entry, |t| {
+        t.add_player_with_appearance(
+            body.name.trim(),
+            body.rating,
+            body.color.as_deref(),
+            body.avatar_url.as_deref(),
+        )
+    })
+}
+
+/// One player's record: id, name, stats, where they are (active / sitting out / eliminated), and
+/// their submitted matches from history. 404 if the player is not in the tournament.
+#[get("/api/tournaments/{id}/players/{player_id}")]
+async fn api_get_player(state: AppState, path: Path<TournamentPlayerPath>) -> HttpResponse {
+    let mut g = match state.write() {
+        Ok(guard) => guard,
+        Err(_) => return HttpResponse::InternalServerError().body("lock error"),
+    };
+    let entry = match g.get_mut(&path.id) {
+        Some(e) => e,
+        None => {
+            return HttpResponse::NotFound().json(serde_json::json!({ "error": "No tournament" }))
+        }
+    };
+    entry.last_activity = Instant::now();
+    let t = &entry.tournament;
+    let Some((player, location)) = t.find_player_anywhere(path.player_id) else {
+        return HttpResponse::NotFound().json(serde_json::json!({ "error": "No player" }));
+    };
+    let matches: Vec<_> = t
+        .match_history
+        .iter()
+        .filter(|m| m.team_1.contains(&player.id) || m.team_2.contains(&player.id))
+        .collect();
+    HttpResponse::Ok().json(serde_json::json!({
+        "player": t.player_summary(player),
+        "status": location,
+        "matches": matches,
+    }))
+}
+
+/// Every match a player has been in (history, then the current round), each with `won`
+/// (`null` while unplayed or for a draw). 404 if the player is not in the tournament.
+#[get("/api/tournaments/{id}/players/{player_id}/matches")]
+async fn api_get_player_matches(state: AppState, path: Path<TournamentPlayerPath>) -> HttpResponse {
+    let mut g = match state.write() {
+        Ok(guard) => guard,
+        Err(_) => return HttpResponse::InternalServerError().body("lock error"),
+    };
+    let entry = match g.get_mut(&path.id) {
+        Some(e) => e,
+        None => {
+            return HttpResponse::NotFound().json(serde_json::json!({ "error": "No tournament" }))
+        }
+    };
+    entry.last_activity = Instant::now();
+    let t = &entry.tournament;
+    if t.find_player_anywhere(path.player_id).is_none() {
+        return HttpResponse::NotFound().json(serde_json::json!({ "error": "No player" }));
+    }
+    let matches: Vec<serde_json::Value> = t
+        .player_match_log(path.player_id)
+        .into_iter()
+        .map(|m| {
+            let mut json = serde_json::to_value(m).unwrap_or_default();
+            if let Some(obj) = json.as_object_mut() {
+                obj.insert("won".into(), m.won_by(path.player_id).into());
+            }
+            json
+        })
+        .collect();
+    HttpResponse::Ok().json(matches)
+}
+
+/// Reorder the roster (Setup only); `player_ids` must list every player exactly once.
+#[put("/api/tournaments/{id}/players/order")]
+async fn api_reorder_players(
+    req: HttpRequest,
+    state: AppState,
+    path: Path<TournamentPath>,
+    body: Json<PlayerOrderBody>,
+) -> HttpResponse {
+    let mut g = match state.write() {
+        Ok(guard) => guard,
+        Err(_) => return HttpResponse::InternalServerError().body("lock error"),
+    };
+    let entry = match g.get_mut(&path.id) {
+        Some(e) => e,
+        None => {
+            return HttpResponse::NotFound().json(serde_json::json!({ "error": "No tournament" }))
+        }
+    };
+    entry.last_activity = Instant::now();
+    apply_mutation(&req, entry, |t| t.reorder_players(&body.player_ids))
+}
+
+/// Remove a player by id (tournament must be in Setup).
+#[delete("/api/tournaments/{id}/players/{player_id}")]
+async fn api_remove_player(
+    req: HttpRequest,
+    state: AppState,
+    path: Path<TournamentPlayerPath>,
+) -> HttpResponse {
+    let mut g = match state.write() {
+        Ok(guard) => guard,
+        Err(_) => return HttpResponse::InternalServerError().body("lock error"),
+    };
+    let entry = match g.get_mut(&path.id) {
+        Some(e) => e,
+        None => {
+            return HttpResponse::NotFound().json(serde_json::json!({ "error": "No tournament" }))
+        }
+    };
+    entry.last_activity = Instant::now();
+    apply_mutation(&req, entry, |t| t.remove_player(path.player_id))
+}
+
+/// Update max losses (tournament must be in Setup).
+#[put("/api/tournaments/{id}/max-losses")]
+async fn api_set_max_losses(
+    req: HttpRequest,
+    state: AppState,
+    path: Path<TournamentPath>,
+    body: Json<MaxLossesBody>,
+) -> HttpResponse {
+    let mut g = match state.write() {
+        Ok(guard) => guard,
+        Err(_) => return HttpResponse::InternalServerError().body("lock error"),
+    };
+    let entry = match g.get_mut(&path.id) {
+        Some(e) => e,
+        None => {
+            return HttpResponse::NotFound().json(serde_json::json!({ "error": "No tournament" }))
+        }
+    };
+    entry.last_activity = Instant::now();
+    apply_mutation(&req, entry, |t| t.set_max_losses(body.max_losses))
+}
+
+/// Start the tournament (Setup -> GroupPlay or FinalSelection). The response adds a `warnings`
+/// array of non-blocking config issues (see `Tournament::start_feasibility`).
+#[post("/api/tournaments/{id}/start")]
+async fn api_start_tournament(
+    req: HttpRequest,
+    state: AppState,
+    path: Path<TournamentPath>,
+) -> HttpResponse {
+    let mut g = match state.write() {
+        Ok(guard) => guard,
+        Err(_) => return HttpResponse::InternalServerError().body("lock error"),
+    };
+    let entry = match g.get_mut(&path.id) {
+        Some(e) => e,
+        None => {
+            return HttpResponse::NotFound().json(serde_json::json!({ "error": "No tournament" }))
+        }
+    };
+    entry.last_activity = Instant::now();
+    let mut extras = serde_json::Map::new();
+    extras.insert(
+        "warnings".into(),
+        entry.tournament.start_feasibility().messages().into(),
+    );
+    apply_mutation_with_extras(&req, entry, start_tournament, move |_| extras)
+}
+
+/// Generate group play matches (tournament must be in GroupPlay).
+#[post("/api/tournaments/{id}/matches/generate")]
+async fn api_generate_matches(
+    req: HttpRequest,
+    state: AppState,
+    path: Path<TournamentPath>,
+) -> HttpResponse {
+    let mut g = match state.write() {
+        Ok(guard) => guard,
+        Err(_) => return HttpResponse::InternalServerError().body("lock error"),
+    };
+    let entry = match g.get_mut(&path.id) {
+        Some(e) => e,
+        None => {
+            return HttpResponse::NotFound().json(serde_json::json!({ "error": "No tournament" }))
+        }
+    };
+    entry.last_activity = Instant::now();
+    apply_mutation(&req, entry, generate_group_play_matches)
+}
+
+/// Generate a practice round: results are kept in history only (tournament must be in GroupPlay).
+#[post("/api/tournaments/{id}/matches/practice")]
+async fn api_generate_practice_round(
+    req: HttpRequest,
+    state: AppState,
+    path: Path<TournamentPath>,
+) -> HttpResponse {
+    let mut g = match state.write() {
+        Ok(guard) => guard,
+        Err(_) => return HttpResponse::InternalServerError().body("lock error"),
+    };
+    let entry = match g.get_mut(&path.id) {
+        Some(e) => e,
+        None => {
+            return HttpResponse::NotFound().json(serde_json::json!({ "error": "No tournament" }))
+        }
+    };
+    entry.last_activity = Instant::now();
+    apply_mutation(&req, entry, generate_practice_round)
+}
+
+/// Set the outcome for one match: `team` is `"one"`, `"two"` or (if allowed) `"draw"` (tournament must be in GroupPlay).
+#[put("/api/tournaments/{id}/matches/winner")]
+async fn api_set_match_winner(
+    req: HttpRequest,
+    state: AppState,
+    path: Path<TournamentPath>,
+    body: Json<SetMatchOutcomeBody>,
+) -> HttpResponse {
+    let mut g = match state.write() {
+        Ok(guard) => guard,
+        Err(_) => return HttpResponse::InternalServerError().body("lock error"),
+    };
+    let entry = match g.get_mut(&path.id) {
+        Some(e) => e,
+        None => {
+            return HttpResponse::NotFound().json(serde_json::json!({ "error": "No tournament" }))
+        }
+    };
+    entry.last_activity = Instant::now();
+    apply_mutation(&req, entry, |t| {
+        t.set_match_outcome(body.target.resolve(t)?, body.team)
+    })
+}
+
+/// Preview a group play result without entering it: who it would eliminate and, if it is the
+/// round's last result, the state after submitting.
+#[post("/api/tournaments/{id}/matches/simulate")]
+async fn api_simulate_match_winner(
+    state: AppState,
+    path: Path<TournamentPath>,
+    body: Json<SetMatchOutcomeBody>,
+) -> HttpResponse {
+    let mut g = match state.write() {
+        Ok(guard) => guard,
+        Err(_) => return HttpResponse::InternalServerError().body("lock error"),
+    };
+    let entry = match g.get_mut(&path.id) {
+        Some(e) => e,
+        None => {
+            return HttpResponse::NotFound().json(serde_json::json!({ "error": "No tournament" }))
+        }
+    };
+    entry.last_activity = Instant::now();
+    let simulated = body
+        .target
+        .resolve(&entry.tournament)
+        .and_then(|id| entry.tournament.simulate_winner(id, body.team));
+    match simulated {
+        Ok(result) => HttpResponse::Ok().json(result),
+        Err(e) => HttpResponse::BadRequest().json(serde_json::json!({ "error": e.to_string() })),
+    }
+}
+
+/// Swap two players between the current round's teams (GroupPlay, before any result is entered).
+#[post("/api/tournaments/{id}/matches/swap-players")]
+async fn api_swap_players(
+    req: HttpRequest,
+    state: AppState,
+    path: Path<TournamentPath>,
+    body: Json<SwapPlayersBody>,
+) -> HttpResponse {
+    let mut g = match state.write() {
+        Ok(guard) => guard,
+        Err(_) => return HttpResponse::InternalServerError().body("lock error"),
+    };
+    let entry = match g.get_mut(&path.id) {
+        Some(e) => e,
+        None => {
+            return HttpResponse::NotFound().json(serde_json::json!({ "error": "No tournament" }))
+        }
+    };
+    entry.last_activity = Instant::now();
+    apply_mutation(&req, entry, |t| {
+        t.swap_players_in_matches(body.player_a, body.player_b)
+    })
+}
+
+/// Pause the tournament: every other change is rejected until resumed.
+#[post("/api/tournaments/{id}/pause")]
+async fn api_pause_tournament(
+    req: HttpRequest,
+    state: AppState,
+    path: Path<TournamentPath>,
+) -> HttpResponse {
+    let mut g = match state.write() {
+        Ok(guard) => guard,
+        Err(_) => return HttpResponse::InternalServerError().body("lock error"),
+    };
+    let entry = match g.get_mut(&path.id) {
+        Some(e) => e,
+        None => {
+            return HttpResponse::NotFound().json(serde_json::json!({ "error": "No tournament" }))
+        }
+    };
+    entry.last_activity = Instant::now();
+    apply_mutation_even_if_paused(&req, entry, |t| t.pause())
+}
+
+/// Resume a paused tournament.
+#[post("/api/tournaments/{id}/resume")]
+async fn api_resume_tournament(
+    req: HttpRequest,
+    state: AppState,
+    path: Path<TournamentPath>,
+) -> HttpResponse {
+    let mut g = match state.write() {
+        Ok(guard) => guard,
+        Err(_) => return HttpResponse::InternalServerError().body("lock error"),
+    };
+    let entry = match g.get_mut(&path.id) {
+        Some(e) => e,
+        None => {
+            return HttpResponse::NotFound().json(serde_json::json!({ "error": "No tournament" }))
+        }
+    };
+    entry.last_activity = Instant::now();
+    apply_mutation_even_if_paused(&req, entry, |t| t.resume())
+}
+
+/// Allow or forbid drawn group play matches (Setup only).
+#[put("/api/tournaments/{id}/draws-allowed")]
+async fn api_set_draws_allowed(
+    req: HttpRequest,
+    state: AppState,
+    path: Path<TournamentPath>,
+    body: Json<SetDrawsAllowedBody>,
+) -> HttpResponse {
+    let mut g = match state.write() {
+        Ok(guard) => guard,
+        Err(_) => return HttpResponse::InternalServerError().body("lock error"),
+    };
+    let entry = match g.get_mut(&path.id) {
+        Some(e) => e,
+        None => {
+            return HttpResponse::NotFound().json(serde_json::json!({ "error": "No tournament" }))
+        }
+    };
+    entry.last_activity = Instant::now();
+    apply_mutation(&req, entry, |t| t.set_draws_allowed(body.draws_allowed))
+}
+
+/// Void one recorded (not yet submitted) result so it can be re-entered (group play or final rounds).
+#[delete("/api/tournaments/{id}/matches/{match_id}/result")]
+async fn api_void_match_result(
+    req: HttpRequest,
+    state: AppState,
+    path: Path<TournamentMatchPath>,
+) -> HttpResponse {
+    let mut g = match state.write() {
+        Ok(guard) => guard,
+        Err(_) => return HttpResponse::InternalServerError().body("lock error"),
+    };
+    let entry = match g.get_mut(&path.id) {
+        Some(e) => e,
+        None => {
+            return HttpResponse::NotFound().json(serde_json::json!({ "error": "No tournament" }))
+        }
+    };
+    entry.last_activity = Instant::now();
+    apply_mutation(&req, entry, |t| t.void_match_result(path.match_id))
+}
+
+/// Correct the winner of a submitted group play match, re-checking eliminations (between rounds).
+#[put("/api/tournaments/{id}/history/{match_id}/result")]
+async fn api_amend_history_result(
+    req: HttpRequest,
+    state: AppState,
+    path: Path<TournamentMatchPath>,
+    body: Json<AmendResultBody>,
+) -> HttpResponse {
+    let mut g = match state.write() {
+        Ok(guard) => guard,
+        Err(_) => return HttpResponse::InternalServerError().body("lock error"),
+    };
+    let entry = match g.get_mut(&path.id) {
+        Some(e) => e,
+        None => {
+            return HttpResponse::NotFound().json(serde_json::json!({ "error": "No tournament" }))
+        }
+    };
+    entry.last_activity = Instant::now();
+    apply_mutation(&req, entry, |t| {
+        t.amend_history_result(path.match_id, body.team)
+    })
+}
+
+/// Submit group play results and process (tournament must be in GroupPlay). The response's
+/// `last_round_summary` lists who was eliminated this round, and `recently_eliminated` gives the
+/// same players as summaries (empty when nobody went out) for the client to animate.
+#[post("/api/tournaments/{id}/matches/submit")]
+async fn api_submit_match_results(
+    req: HttpRequest,
+    state: AppState,
+    path: Path<TournamentPath>,
+) -> HttpResponse {
+    let mut g = match state.write() {
+        Ok(guard) => guard,
+        Err(_) => return HttpResponse::InternalServerError().body("lock error"),
+    };
+    let entry = match g.get_mut(&path.id) {
+        Some(e) => e,
+        None => {
+            return HttpResponse::NotFound().json(serde_json::json!({ "error": "No tournament" }))
+        }
+    };
+    entry.last_activity = Instant::now();
+    apply_mutation_with_extras(&req, entry, process_group_play_results, |t| {
+        let mut extras = serde_json::Map::new();
+        extras.insert(
+            "recently_eliminated".into(),
+            serde_json::to_value(t.recently_eliminated()).unwrap_or_default(),
+        );
+        extras
+    })
+}
+
+/// Set a player's losses manually (GroupPlay or FinalSelection).
+#[put("/api/tournaments/{id}/players/{player_id}/losses")]
+async fn api_set_player_losses(
+    req: HttpRequest,
+    state: AppState,
+    path: Path<TournamentPlayerPath>,
+    body: Json<SetPlayerLossesBody>,
+) -> HttpResponse {
+    let mut g = match state.write() {
+        Ok(guard) => guard,
+        Err(_) => return HttpResponse::InternalServerError().body("lock error"),
+    };
+    let entry = match g.get_mut(&path.id) {
+        Some(e) => e,
+        None => {
+            return HttpResponse::NotFound().json(serde_json::json!({ "error": "No tournament" }))
+        }
+    };
+    entry.last_activity = Instant::now();
+    apply_mutation(&req, entry, |t| {
+        t.set_player_losses(path.player_id, body.losses)
+    })
+}
+
+/// Set or clear (`null`) a player's external rating.
+#[put("/api/tournaments/{id}/players/{player_id}/rating")]
+async fn api_set_player_rating(
+    req: HttpRequest,
+    state: AppState,
+    path: Path<TournamentPlayerPath>,
+    body: Json<SetPlayerRatingBody>,
+) -> HttpResponse {
+    let mut g = match state.write() {
+        Ok(guard) => guard,
+        Err(_) => return HttpResponse::InternalServerError().body("lock error"),
+    };
+    let entry = match g.get_mut(&path.id) {
+        Some(e) => e,
+        None => {
+            return HttpResponse::NotFound().json(serde_json::json!({ "error": "No tournament" }))
+        }
+    };
+    entry.last_activity = Instant::now();
+    apply_mutation(&req, entry, |t| {
+        t.set_player_rating(path.player_id, body.rating)
+    })
+}
+
+/// Set or clear (`null`) a player's scoreboard color (`#rgb` / `#rrggbb`) and avatar URL.
+#[put("/api/tournaments/{id}/players/{player_id}/appearance")]
+async fn api_set_player_appearance(
+    req: HttpRequest,
+    state: AppState,
+    path: Path<TournamentPlayerPath>,
+    body: Json<SetPlayerAppearanceBody>,
+) -> HttpResponse {
+    let mut g = match state.write() {
+        Ok(guard) => guard,
+        Err(_) => return HttpResponse::InternalServerError().body("lock error"),
+    };
+    let entry = match g.get_mut(&path.id) {
+        Some(e) => e,
+        None => {
+            return HttpResponse::NotFound().json(serde_json::json!({ "error": "No tournament" }))
+        }
+    };
+    entry.last_activity = Instant::now();
+    apply_mutation(&req, entry, |t| {
+        t.set_player_appearance(
+            path.player_id,
+            body.color.as_deref(),
+            body.avatar_url.as_deref(),
+        )
+    })
+}
+
+/// Set a player's handicap spot (starting points shown on their matches).
+#[put("/api/tournaments/{id}/players/{player_id}/handicap-spot")]
+async fn api_set_player_handicap_spot(
+    req: HttpRequest,
+    state: AppState,
+    path: Path<TournamentPlayerPath>,
+    body: Json<SetHandicapSpotBody>,
+) -> HttpResponse {
+    let mut g = match state.write() {
+        Ok(guard) => guard,
+        Err(_) => return HttpResponse::InternalServerError().body("lock error"),
+    };
+    let entry = match g.get_mut(&path.id) {
+        Some(e) => e,
+        None => {
+            return HttpResponse::NotFound().json(serde_json::json!({ "error": "No tournament" }))
+        }
+    };
+    entry.last_activity = Instant::now();
+    apply_mutation(&req, entry, |t| {
+        t.set_player_handicap_spot(path.player_id, body.handicap_spot)
+    })
+}
+
+/// Manually eliminate a player (GroupPlay or FinalSelection).
+#[post("/api/tournaments/{id}/players/{player_id}/eliminate")]
+async fn api_eliminate_player(
+    req: HttpRequest,
+    state: AppState,
+    path: Path<TournamentPlayerPath>,
+) -> HttpResponse {
+    let mut g = match state.write() {
+        Ok(guard) => guard,
+        Err(_) => return HttpResponse::InternalServerError().body("lock error"),
+    };
+    let entry = match g.get_mut(&path.id) {
+        Some(e) => e,
+        None => {
+            return HttpResponse::NotFound().json(serde_json::json!({ "error": "No tournament" }))
+        }
+    };
+    entry.last_activity = Instant::now();
+    apply_mutation(&req, entry, |t| t.eliminate_player(path.player_id))
+}
+
+/// A player forfeits (GroupPlay only); the tournament's forfeit policy decides the outcome.
+#[post("/api/tournaments/{id}/players/{player_id}/forfeit")]
+async fn api_forfeit_player(
+    req: HttpRequest,
+    state: AppState,
+    path: Path<TournamentPlayerPath>,
+) -> HttpResponse {
+    let mut g = match state.write() {
+        Ok(guard) => guard,
+        Err(_) => return HttpResponse::InternalServerError().body("lock error"),
+    };
+    let entry = match g.get_mut(&path.id) {
+        Some(e) => e,
+        None => {
+            return HttpResponse::NotFound().json(serde_json::json!({ "error": "No tournament" }))
+        }
+    };
+    entry.last_activity = Instant::now();
+    apply_mutation(&req, entry, |t| t.forfeit_player(path.player_id))
+}
+
+/// A player volunteers to sit out the next group play round.
+#[post("/api/tournaments/{id}/players/{player_id}/sit-out")]
+async fn api_request_sit_out(
+    req: HttpRequest,
+    state: AppState,
+    path: Path<TournamentPlayerPath>,
+) -> HttpResponse {
+    let mut g = match state.write() {
+        Ok(guard) => guard,
+        Err(_) => return HttpResponse::InternalServerError().body("lock error"),
+    };
+    let entry = match g.get_mut(&path.id) {
+        Some(e) => e,
+        None => {
+            return HttpResponse::NotFound().json(serde_json::json!({ "error": "No tournament" }))
+        }
+    };
+    entry.last_activity = Instant::now();
+    apply_mutation(&req, entry, |t| t.request_sit_out(path.player_id))
+}
+
+/// Eliminate several players at once; nothing changes if any id is not an active player.
+#[post("/api/tournaments/{id}/players/eliminate-bulk")]
+async fn api_eliminate_players_bulk(
+    req: HttpRequest,
+    state: AppState,
+    path: Path<TournamentPath>,
+    body: Json<EliminateBulkBody>,
+) -> HttpResponse {
+    let mut g = match state.write() {
+        Ok(guard) => guard,
+        Err(_) => return HttpResponse::InternalServerError().body("lock error"),
+    };
+    let entry = match g.get_mut(&path.id) {
+        Some(e) => e,
+        None => {
+            return HttpResponse::NotFound().json(serde_json::json!({ "error": "No tournament" }))
+        }
+    };
+    entry.last_activity = Instant::now();
+    apply_mutation(&req, entry, |t| t.eliminate_players(&body.player_ids))
+}
+
+/// Set tournament mode 1v1 or 2v2 (Setup only).
+#[put("/api/tournaments/{id}/mode")]
+async fn api_set_mode(
+    req: HttpRequest,
+    state: AppState,
+    path: Path<TournamentPath>,
+    body: Json<SetModeBody>,
+) -> HttpResponse {
+    let mut g = match state.write() {
+        Ok(guard) => guard,
+        Err(_) => return HttpResponse::InternalServerError().body("lock error"),
+    };
+    let entry = match g.get_mut(&path.id) {
+        Some(e) => e,
+        None => {
+            return HttpResponse::NotFound().json(serde_json::json!({ "error": "No tournament" }))
+        }
+    };
+    entry.last_activity = Instant::now();
+    apply_mutation(&req, entry, |t| t.set_mode(body.mode))
+}
+
+/// Toggle balanced 2v2 team assignment in group play (Setup only).
+#[put("/api/tournaments/{id}/balance-teams")]
+async fn api_set_balance_teams(
+    req: HttpRequest,
+    state: AppState,
+    path: Path<TournamentPath>,
+    body: Json<SetBalanceTeamsBody>,
+) -> HttpResponse {
+    let mut g = match state.write() {
+        Ok(guard) => guard,
+        Err(_) => return HttpResponse::InternalServerError().body("lock error"),
+    };
+    let entry = match g.get_mut(&path.id) {
+        Some(e) => e,
+        None => {
+            return HttpResponse::NotFound().json(serde_json::json!({ "error": "No tournament" }))
+        }
+    };
+    entry.last_activity = Instant::now();
+    apply_mutation(&req, entry, |t| t.set_balance_teams(body.balance_teams))
+}
+
+/// Toggle shuffling the display order of group play matches (Setup only).
+#[put("/api/tournaments/{id}/shuffle-match-order")]
+async fn api_set_shuffle_match_order(
+    req: HttpRequest,
+    state: AppState,
+    path: Path<TournamentPath>,
+    body: Json<SetShuffleMatchOrderBody>,
+) -> HttpResponse {
+    let mut g = match state.write() {
+        Ok(guard) => guard,
+        Err(_) => return HttpResponse::InternalServerError().body("lock error"),
+    };
+    let entry = match g.get_mut(&path.id) {
+        Some(e) => e,
+        None => {
+            return HttpResponse::NotFound().json(serde_json::json!({ "error": "No tournament" }))
+        }
+    };
+    entry.last_activity = Instant::now();
+    apply_mutation(&req, entry, |t| {
+        t.set_shuffle_match_order(body.shuffle_match_order)
+    })
+}
+
+/// Toggle generating the next group play round when results are submitted.
+#[put("/api/tournaments/{id}/auto-generate-next")]
+async fn api_set_auto_generate_next(
+    req: HttpRequest,
+    state: AppState,
+    path: Path<TournamentPath>,
+    body: Json<SetAutoGenerateNextBody>,
+) -> HttpResponse {
+    let mut g = match state.write() {
+        Ok(guard) => guard,
+        Err(_) => return HttpResponse::InternalServerError().body("lock error"),
+    };
+    let entry = match g.get_mut(&path.id) {
+        Some(e) => e,
+        None => {
+            return HttpResponse::NotFound().json(serde_json::json!({ "error": "No tournament" }))
+        }
+    };
+    entry.last_activity = Instant::now();
+    apply_mutation(&req, entry, |t| {
+        t.set_auto_generate_next(body.auto_generate_next)
+    })
+}
+
+/// Set how many matches a player plays before losses can eliminate them.
+#[put("/api/tournaments/{id}/grace-matches")]
+async fn api_set_grace_matches(
+    req: HttpRequest,
+    state: AppState,
+    path: Path<TournamentPath>,
+    body: Json<SetGraceMatchesBody>,
+) -> HttpResponse {
+    let mut g = match state.write() {
+        Ok(guard) => guard,
+        Err(_) => return HttpResponse::InternalServerError().body("lock error"),
+    };
+    let entry = match g.get_mut(&path.id) {
+        Some(e) => e,
+        None => {
+            return HttpResponse::NotFound().json(serde_json::json!({ "error": "No tournament" }))
+        }
+    };
+    entry.last_activity = Instant::now();
+    apply_mutation(&req, entry, |t| t.set_grace_matches(body.grace_matches))
+}
+
+/// Toggle going straight to the semi-finals when group play ends on exactly the semi-final size.
+#[put("/api/tournaments/{id}/auto-start-semis")]
+async fn api_set_auto_start_semis(
+    req: HttpRequest,
+    state: AppState,
+    path: Path<TournamentPath>,
+    body: Json<SetAutoStartSemisBody>,
+) -> HttpResponse {
+    let mut g = match state.write() {
+        Ok(guard) => guard,
+        Err(_) => return HttpResponse::InternalServerError().body("lock error"),
+    };
+    let entry = match g.get_mut(&path.id) {
+        Some(e) => e,
+        None => {
+            return HttpResponse::NotFound().json(serde_json::json!({ "error": "No tournament" }))
+        }
+    };
+    entry.last_activity = Instant::now();
+    apply_mutation(&req, entry, |t| {
+        t.set_auto_start_semis(body.auto_start_semis)
+    })
+}
+
+/// Toggle letting one player play twice so an odd 2v2 round needs nobody to sit out three.
+#[put("/api/tournaments/{id}/allow-ghost")]
+async fn api_set_allow_ghost(
+    req: HttpRequest,
+    state: AppState,
+    path: Path<TournamentPath>,
+    body: Json<SetAllowGhostBody>,
+) -> HttpResponse {
+    let mut g = match state.write() {
+        Ok(guard) => guard,
+        Err(_) => return HttpResponse::InternalServerError().body("lock error"),
+    };
+    let entry = match g.get_mut(&path.id) {
+        Some(e) => e,
+        None => {
+            return HttpResponse::NotFound().json(serde_json::json!({ "error": "No tournament" }))
+        }
+    };
+    entry.last_activity = Instant::now();
+    apply_mutation(&req, entry, |t| t.set_allow_ghost(body.allow_ghost))
+}
+
+/// Toggle strict bye rotation: nobody sits out twice before everyone has sat out once.
+#[put("/api/tournaments/{id}/strict-bye-rotation")]
+async fn api_set_strict_bye_rotation(
+    req: HttpRequest,
+    state: AppState,
+    path: Path<TournamentPath>,
+    body: Json<SetStrictByeRotationBody>,
+) -> HttpResponse {
+    let mut g = match state.write() {
+        Ok(guard) => guard,
+        Err(_) => return HttpResponse::InternalServerError().body("lock error"),
+    };
+    let entry = match g.get_mut(&path.id) {
+        Some(e) => e,
+        None => {
+            return HttpResponse::NotFound().json(serde_json::json!({ "error": "No tournament" }))
+        }
+    };
+    entry.last_activity = Instant::now();
+    apply_mutation(&req, entry, |t| {
+        t.set_strict_bye_rotation(body.strict_bye_rotation)
+    })
+}
+
+/// Toggle the 1v1 grand final that crowns a single champion (until the finals are played).
+#[put("/api/tournaments/{id}/single-champion")]
+async fn api_set_single_champion(
+    req: HttpRequest,
+    state: AppState,
+    path: Path<TournamentPath>,
+    body: Json<SetSingleChampionBody>,
+) -> HttpResponse {
+    let mut g = match state.write() {
+        Ok(guard) => guard,
+        Err(_) => return HttpResponse::InternalServerError().body("lock error"),
+    };
+    let entry = match g.get_mut(&path.id) {
+        Some(e) => e,
+        None => {
+            return HttpResponse::NotFound().json(serde_json::json!({ "error": "No tournament" }))
+        }
+    };
+    entry.last_activity = Instant::now();
+    apply_mutation(&req, entry, |t| t.set_single_champion(body.single_champion))
+}
+
+/// Name the winning finals team for the completion screen (Finals, GrandFinals or Completed).
+#[put("/api/tournaments/{id}/champion-name")]
+async fn api_set_champion_name(
+    req: HttpRequest,
+    state: AppState,
+    path: Path<TournamentPath>,
+    body: Json<SetChampionNameBody>,
+) -> HttpResponse {
+    let mut g = match state.write() {
+        Ok(guard) => guard,
+        Err(_) => return HttpResponse::InternalServerError().body("lock error"),
+    };
+    let entry = match g.get_mut(&path.id) {
+        Some(e) => e,
+        None => {
+            return HttpResponse::NotFound().json(serde_json::json!({ "error": "No tournament" }))
+        }
+    };
+    entry.last_activity = Instant::now();
+    apply_mutation(&req, entry, |t| {
+        t.set_champion_team_name(body.champion_team_name.as_deref())
+    })
+}
+
+/// Toggle deterministic (shuffle-free) match generation for demos (Setup only).
+#[put("/api/tournaments/{id}/deterministic")]
+async fn api_set_deterministic(
+    req: HttpRequest,
+    state: AppState,
+    path: Path<TournamentPath>,
+    body: Json<SetDeterministicBody>,
+) -> HttpResponse {
+    let mut g = match state.write() {
+        Ok(guard) => guard,
+        Err(_) => return HttpResponse::InternalServerError().body("lock error"),
+    };
+    let entry = match g.get_mut(&path.id) {
+        Some(e) => e,
+        None => {
+            return HttpResponse::NotFound().json(serde_json::json!({ "error": "No tournament" }))
+        }
+    };
+    entry.last_activity = Instant::now();
+    apply_mutation(&req, entry, |t| t.set_deterministic(body.deterministic))
+}
+
+/// Set the finals format: standard or small field with byes (until semi-finals start).
+#[put("/api/tournaments/{id}/finals-format")]
+async fn api_set_finals_format(
+    req: HttpRequest,
+    state: AppState,
+    path: Path<TournamentPath>,
+    body: Json<SetFinalsFormatBody>,
+) -> HttpResponse {
+    let mut g = match state.write() {
+        Ok(guard) => guard,
+        Err(_) => return HttpResponse::InternalServerError().body("lock error"),
+    };
+    let entry = match g.get_mut(&path.id) {
+        Some(e) => e,
+        None => {
+            return HttpResponse::NotFound().json(serde_json::json!({ "error": "No tournament" }))
+        }
+    };
+    entry.last_activity = Instant::now();
+    apply_mutation(&req, entry, |t| t.set_finals_format(body.finals_format))
+}
+
+/// Choose a tournament with finals or a group-only one (Setup only).
+#[put("/api/tournaments/{id}/format")]
+async fn api_set_format(
+    req: HttpRequest,
+    state: AppState,
+    path: Path<TournamentPath>,
+    body: Json<SetFormatBody>,
+) -> HttpResponse {
+    let mut g = match state.write() {
+        Ok(guard) => guard,
+        Err(_) => return HttpResponse::InternalServerError().body("lock error"),
+    };
+    let entry = match g.get_mut(&path.id) {
+        Some(e) => e,
+        None => {
+            return HttpResponse::NotFound().json(serde_json::json!({ "error": "No tournament" }))
+        }
+    };
+    entry.last_activity = Instant::now();
+    apply_mutation(&req, entry, |t| t.set_format(body.format))
+}
+
+/// Set what a forfeit does: immediate elimination or a loss (until completed).
+#[put("/api/tournaments/{id}/forfeit-policy")]
+async fn api_set_forfeit_policy(
+    req: HttpRequest,
+    state: AppState,
+    path: Path<TournamentPath>,
+    body: Json<SetForfeitPolicyBody>,
+) -> HttpResponse {
+    let mut g = match state.write() {
+        Ok(guard) => guard,
+        Err(_) => return HttpResponse::InternalServerError().body("lock error"),
+    };
+    let entry = match g.get_mut(&path.id) {
+        Some(e) => e,
+        None => {
+            return HttpResponse::NotFound().json(serde_json::json!({ "error": "No tournament" }))
+        }
+    };
+    entry.last_activity = Instant::now();
+    apply_mutation(&req, entry, |t| t.set_forfeit_policy(body.forfeit_policy))
+}
+
+/// Set (or clear with `null`) the number of boards matches are assigned to.
+#[put("/api/tournaments/{id}/num-boards")]
+async fn api_set_num_boards(
+    req: HttpRequest,
+    state: AppState,
+    path: Path<TournamentPath>,
+    body: Json<SetNumBoardsBody>,
+) -> HttpResponse {
+    let mut g = match state.write() {
+        Ok(guard) => guard,
+        Err(_) => return HttpResponse::InternalServerError().body("lock error"),
+    };
+    let entry = match g.get_mut(&path.id) {
+        Some(e) => e,
+        None => {
+            return HttpResponse::NotFound().json(serde_json::json!({ "error": "No tournament" }))
+        }
+    };
+    entry.last_activity = Instant::now();
+    apply_mutation(&req, entry, |t| t.set_num_boards(body.num_boards))
+}
+
+/// Set (or clear with `null`) the cap on group play matches per round.
+#[put("/api/tournaments/{id}/max-concurrent-matches")]
+async fn api_set_max_concurrent_matches(
+    req: HttpRequest,
+    state: AppState,
+    path: Path<TournamentPath>,
+    body: Json<SetMaxConcurrentMatchesBody>,
+) -> HttpResponse {
+    let mut g = match state.write() {
+        Ok(guard) => guard,
+        Err(_) => return HttpResponse::InternalServerError().body("lock error"),
+    };
+    let entry = match g.get_mut(&path.id) {
+        Some(e) => e,
+        None => {
+            return HttpResponse::NotFound().json(serde_json::json!({ "error": "No tournament" }))
+        }
+    };
+    entry.last_activity = Instant::now();
+    apply_mutation(&req, entry, |t| {
+        t.set_max_concurrent_matches(body.max_concurrent_matches)
+    })
+}
+
+/// Set how many rounds 2v2 teams stay together before new teams are drawn.
+#[put("/api/tournaments/{id}/reshuffle-every")]
+async fn api_set_reshuffle_every(
+    req: HttpRequest,
+    state: AppState,
+    path: Path<TournamentPath>,
+    body: Json<SetReshuffleEveryBody>,
+) -> HttpResponse {
+    let mut g = match state.write() {
+        Ok(guard) => guard,
+        Err(_) => return HttpResponse::InternalServerError().body("lock error"),
+    };
+    let entry = match g.get_mut(&path.id) {
+        Some(e) => e,
+        None => {
+            return HttpResponse::NotFound().json(serde_json::json!({ "error": "No tournament" }))
+        }
+    };
+    entry.last_activity = Instant::now();
+    apply_mutation(&req, entry, |t| t.set_reshuffle_every(body.reshuffle_every))
+}
+
+/// Set how many group play rounds a knockout-all tournament plays before the knockout.
+#[put("/api/tournaments/{id}/knockout-after-rounds")]
+async fn api_set_knockout_after_rounds(
+    req: HttpRequest,
+    state: AppState,
+    path: Path<TournamentPath>,
+    body: Json<SetKnockoutAfterRoundsBody>,
+) -> HttpResponse {
+    let mut g = match state.write() {
+        Ok(guard) => guard,
+        Err(_) => return HttpResponse::InternalServerError().body("lock error"),
+    };
+    let entry = match g.get_mut(&path.id) {
+        Some(e) => e,
+        None => {
+            return HttpResponse::NotFound().json(serde_json::json!({ "error": "No tournament" }))
+        }
+    };
+    entry.last_activity = Instant::now();
+    apply_mutation(&req, entry, |t| {
+        t.set_knockout_after_rounds(body.knockout_after_rounds)
+    })
+}
+
+/// Set (or clear with `null`) the group play rounds after which the finals field is filled by record.
+#[put("/api/tournaments/{id}/round-cap")]
+async fn api_set_round_cap(
+    req: HttpRequest,
+    state: AppState,
+    path: Path<TournamentPath>,
+    body: Json<SetRoundCapBody>,
+) -> HttpResponse {
+    let mut g = match state.write() {
+        Ok(guard) => guard,
+        Err(_) => return HttpResponse::InternalServerError().body("lock error"),
+    };
+    let entry = match g.get_mut(&path.id) {
+        Some(e) => e,
+        None => {
+            return HttpResponse::NotFound().json(serde_json::json!({ "error": "No tournament" }))
+        }
+    };
+    entry.last_activity = Instant::now();
+    apply_mutation(&req, entry, |t| t.set_round_cap(body.round_cap))
+}
+
+/// Turn kiosk auto-submit on (seconds after the last change once all results are in) or off
+/// with `null`.
+#[put("/api/tournaments/{id}/auto-submit-after")]
+async fn api_set_auto_submit_after(
+    req: HttpRequest,
+    state: AppState,
+    path: Path<TournamentPath>,
+    body: Json<SetAutoSubmitAfterBody>,
+) -> HttpResponse {
+    let mut g = match state.write() {
+        Ok(guard) => guard,
+        Err(_) => return HttpResponse::InternalServerError().body("lock error"),
+    };
+    let entry = match g.get_mut(&path.id) {
+        Some(e) => e,
+        None => {
+            return HttpResponse::NotFound().json(serde_json::json!({ "error": "No tournament" }))
+        }
+    };
+    entry.last_activity = Instant::now();
+    apply_mutation(&req, entry, |t| {
+        t.set_auto_submit_after(body.auto_submit_after_secs)
+    })
+}
+
+/// Set the semi-final seeding strategy (random, snake_by_wins, snake_by_rating).
+#[put("/api/tournaments/{id}/seeding")]
+async fn api_set_seeding(
+    req: HttpRequest,
+    state: AppState,
+    path: Path<TournamentPath>,
+    body: Json<SetSeedingBody>,
+) -> HttpResponse {
+    let mut g = match state.write() {
+        Ok(guard) => guard,
+        Err(_) => return HttpResponse::InternalServerError().body("lock error"),
+    };
+    let entry = match g.get_mut(&path.id) {
+        Some(e) => e,
+        None => {
+            return HttpResponse::NotFound().json(serde_json::json!({ "error": "No tournament" }))
+        }
+    };
+    entry.last_activity = Instant::now();
+    apply_mutation(&req, entry, |t| t.set_seeding(body.seeding))
+}
+
+/// Set whether a round type's matches take a bare winner or are entered leg by leg.
+#[put("/api/tournaments/{id}/scoring-mode")]
+async fn api_set_scoring_mode(
+    req: HttpRequest,
+    state: AppState,
+    path: Path<TournamentPath>,
+    body: Json<SetScoringModeBody>,
+) -> HttpResponse {
+    let mut g = match state.write() {
+        Ok(guard) => guard,
+        Err(_) => return HttpResponse::InternalServerError().body("lock error"),
+    };
+    let entry = match g.get_mut(&path.id) {
+        Some(e) => e,
+        None => {
+            return HttpResponse::NotFound().json(serde_json::json!({ "error": "No tournament" }))
+        }
+    };
+    entry.last_activity = Instant::now();
+    apply_mutation(&req, entry, |t| t.set_scoring_mode(body.round, body.mode))
 }
 
-/// POST JSON `{ "password": "..." }` — returns `{ "token": "<hex>" }` on success (must match [`SITE_GATE_PLAIN`]).
-#[post("/api/site-gate")]
-async fn api_site_gate_login(
-    gate: web::Data<SiteGate>,
-    body: Json<SiteGateLoginBody>,
+/// Restart tournament: back to Setup with same player names.
+#[post("/api/tournaments/{id}/restart")]
+async fn api_restart_tournament(
+    req: HttpRequest,
+    state: AppState,
+    path: Path<TournamentPath>,
 ) -> HttpResponse {
-    let expected = gate.expected_token.as_str();
-    let got = site_gate_token(body.password.trim());
-    if got.as_bytes().ct_eq(expected.as_bytes()).into() {
-        HttpResponse::Ok().json(serde_json::json!({ "token": expected }))
-    } else {
-        HttpResponse::Unauthorized().json(serde_json::json!({ "error": "Wrong password" }))
-    }
+    let mut g = match state.write() {
+        Ok(guard) => guard,
+        Err(_) => return HttpResponse::InternalServerError().body("lock error"),
+    };
+    let entry = match g.get_mut(&path.id) {
+        Some(e) => e,
+        None => {
+            return HttpResponse::NotFound().json(serde_json::json!({ "error": "No tournament" }))
+        }
+    };
+    entry.last_activity = Instant::now();
+    apply_mutation(&req, entry, |t| t.restart_tournament())
 }
 
-/// Create a new tournament.
-#[post("/api/tournaments")]
-async fn api_create_tournament(
+/// Reset the tournament: a full restart, or back to group play keeping stats (`keep_stats`).
+#[post("/api/tournaments/{id}/reset")]
+async fn api_reset_tournament(
+    req: HttpRequest,
     state: AppState,
-    body: Option<Json<CreateTournamentBody>>,
+    path: Path<TournamentPath>,
+    body: Json<ResetBody>,
 ) -> HttpResponse {
-    let max_losses = body
-        .as_ref()
-        .map(|b| b.max_losses)
-        .unwrap_or_else(default_max_losses);
-    let mode = body
-        .as_ref()
-        .map(|b| b.mode)
-        .unwrap_or(dart_tournament_web::TournamentMode::TwoVTwo);
-
-    let tournament = Tournament::new(max_losses, mode);
-    let id = tournament.id;
     let mut g = match state.write() {
         Ok(guard) => guard,
         Err(_) => return HttpResponse::InternalServerError().body("lock error"),
     };
-    g.insert(
-        id,
-        TournamentEntry {
-            tournament,
-            last_activity: Instant::now(),
-        },
-    );
-    let entry = g.get(&id).unwrap();
-    HttpResponse::Ok().json(&entry.tournament)
+    let entry = match g.get_mut(&path.id) {
+        Some(e) => e,
+        None => {
+            return HttpResponse::NotFound().json(serde_json::json!({ "error": "No tournament" }))
+        }
+    };
+    entry.last_activity = Instant::now();
+    apply_mutation(&req, entry, |t| {
+        if body.keep_stats {
+            t.reset_to_group_play_keeping_stats(body.reactivate_eliminated)
+        } else {
+            t.restart_tournament()
+        }
+    })
 }
 
-/// Get a tournament by id (404 if not found). Touching it refreshes last_activity.
-#[get("/api/tournaments/{id}")]
-async fn api_get_tournament(state: AppState, path: Path<TournamentPath>) -> HttpResponse {
+/// The order the semi-final field was drawn in, with names (once the semi-finals are generated).
+#[get("/api/tournaments/{id}/finals/seeding")]
+async fn api_get_finals_seeding(state: AppState, path: Path<TournamentPath>) -> HttpResponse {
     let mut g = match state.write() {
         Ok(guard) => guard,
         Err(_) => return HttpResponse::InternalServerError().body("lock error"),
     };
-    match g.get_mut(&path.id) {
-        Some(entry) => {
-            entry.last_activity = Instant::now();
-            HttpResponse::Ok().json(&entry.tournament)
+    let entry = match g.get_mut(&path.id) {
+        Some(e) => e,
+        None => {
+            return HttpResponse::NotFound().json(serde_json::json!({ "error": "No tournament" }))
         }
-        None => HttpResponse::NotFound().json(serde_json::json!({ "error": "No tournament" })),
+    };
+    entry.last_activity = Instant::now();
+    match entry.tournament.finals_seeding_view() {
+        Ok(seeding) => HttpResponse::Ok().json(seeding),
+        Err(e) => HttpResponse::BadRequest().json(serde_json::json!({ "error": e.to_string() })),
     }
 }
 
-#[post("/api/tournaments/{id}/players")]
-async fn api_add_player(
+/// How many players to add back and the eligible candidates (FinalSelection only).
+#[get("/api/tournaments/{id}/final-selection/candidates")]
+async fn api_final_selection_candidates(
     state: AppState,
     path: Path<TournamentPath>,
-    body: Json<AddPlayerBody>,
 ) -> HttpResponse {
     let mut g = match state.write() {
         Ok(guard) => guard,
@@ -284,16 +2758,21 @@ async fn api_add_player(
         }
     };
     entry.last_activity = Instant::now();
-    let t = &mut entry.tournament;
-    match t.add_player(body.name.trim()) {
-        Ok(()) => HttpResponse::Ok().json(t),
+    match entry.tournament.final_selection_info() {
+        Ok(info) => HttpResponse::Ok().json(info),
         Err(e) => HttpResponse::BadRequest().json(serde_json::json!({ "error": e.to_string() })),
     }
 }
 
-/// Remove a player by id (tournament must be in Setup).
-#[delete("/api/tournaments/{id}/players/{player_id}")]
-async fn api_remove_player(state: AppState, path: Path<TournamentPlayerPath>) -> HttpResponse {
+/// Add selected players from last eliminated back to reach 8 (FinalSelection only).
+/// A rejected selection says why (`reason`) and lists the `eligible_ids`.
+#[post("/api/tournaments/{id}/final-selection/add-back")]
+async fn api_final_selection_add_back(
+    req: HttpRequest,
+    state: AppState,
+    path: Path<TournamentPath>,
+    body: Json<FinalSelectionAddBackBody>,
+) -> HttpResponse {
     let mut g = match state.write() {
         Ok(guard) => guard,
         Err(_) => return HttpResponse::InternalServerError().body("lock error"),
@@ -305,19 +2784,24 @@ async fn api_remove_player(state: AppState, path: Path<TournamentPlayerPath>) ->
         }
     };
     entry.last_activity = Instant::now();
-    let t = &mut entry.tournament;
-    match t.remove_player(path.player_id) {
-        Ok(()) => HttpResponse::Ok().json(t),
-        Err(e) => HttpResponse::BadRequest().json(serde_json::json!({ "error": e.to_string() })),
-    }
+    apply_mutation_detailed(
+        &req,
+        entry,
+        |t| add_players_back_from_last_eliminated(t, &body.player_ids),
+        |_| serde_json::Map::new(),
+        |t, e| match serde_json::to_value(t.add_back_rejection(e)) {
+            Ok(serde_json::Value::Object(details)) => details,
+            _ => serde_json::Map::new(),
+        },
+    )
 }
 
-/// Update max losses (tournament must be in Setup).
-#[put("/api/tournaments/{id}/max-losses")]
-async fn api_set_max_losses(
+/// Add back the best records from the last eliminated round instead of picking (FinalSelection only).
+#[post("/api/tournaments/{id}/final-selection/auto-fill")]
+async fn api_final_selection_auto_fill(
+    req: HttpRequest,
     state: AppState,
     path: Path<TournamentPath>,
-    body: Json<MaxLossesBody>,
 ) -> HttpResponse {
     let mut g = match state.write() {
         Ok(guard) => guard,
@@ -330,16 +2814,17 @@ async fn api_set_max_losses(
         }
     };
     entry.last_activity = Instant::now();
-    let t = &mut entry.tournament;
-    match t.set_max_losses(body.max_losses) {
-        Ok(()) => HttpResponse::Ok().json(t),
-        Err(e) => HttpResponse::BadRequest().json(serde_json::json!({ "error": e.to_string() })),
-    }
+    apply_mutation(&req, entry, auto_fill_finalists)
 }
 
-/// Start the tournament (Setup -> GroupPlay or FinalSelection).
-#[post("/api/tournaments/{id}/start")]
-async fn api_start_tournament(state: AppState, path: Path<TournamentPath>) -> HttpResponse {
+/// Fallback add-back from all eliminated players when the last eliminated round cannot reach 8 (FinalSelection only).
+#[post("/api/tournaments/{id}/final-selection/add-back-any")]
+async fn api_final_selection_add_back_any(
+    req: HttpRequest,
+    state: AppState,
+    path: Path<TournamentPath>,
+    body: Json<FinalSelectionAddBackBody>,
+) -> HttpResponse {
     let mut g = match state.write() {
         Ok(guard) => guard,
         Err(_) => return HttpResponse::InternalServerError().body("lock error"),
@@ -351,16 +2836,18 @@ async fn api_start_tournament(state: AppState, path: Path<TournamentPath>) -> Ht
         }
     };
     entry.last_activity = Instant::now();
-    let t = &mut entry.tournament;
-    match start_tournament(t) {
-        Ok(()) => HttpResponse::Ok().json(t),
-        Err(e) => HttpResponse::BadRequest().json(serde_json::json!({ "error": e.to_string() })),
-    }
+    apply_mutation(&req, entry, |t| {
+        t.add_back_from_all_eliminated(&body.player_ids)
+    })
 }
 
-/// Generate group play matches (tournament must be in GroupPlay).
-#[post("/api/tournaments/{id}/matches/generate")]
-async fn api_generate_matches(state: AppState, path: Path<TournamentPath>) -> HttpResponse {
+/// Transition to semi-finals when 8 players in final selection (no add-back needed).
+#[post("/api/tournaments/{id}/final-selection/start-semi")]
+async fn api_final_selection_start_semi(
+    req: HttpRequest,
+    state: AppState,
+    path: Path<TournamentPath>,
+) -> HttpResponse {
     let mut g = match state.write() {
         Ok(guard) => guard,
         Err(_) => return HttpResponse::InternalServerError().body("lock error"),
@@ -372,19 +2859,16 @@ async fn api_generate_matches(state: AppState, path: Path<TournamentPath>) -> Ht
         }
     };
     entry.last_activity = Instant::now();
-    let t = &mut entry.tournament;
-    match generate_group_play_matches(t) {
-        Ok(()) => HttpResponse::Ok().json(t),
-        Err(e) => HttpResponse::BadRequest().json(serde_json::json!({ "error": e.to_string() })),
-    }
+    apply_mutation(&req, entry, start_semi_finals)
 }
 
-/// Set winner for one match (tournament must be in GroupPlay).
-#[put("/api/tournaments/{id}/matches/winner")]
-async fn api_set_match_winner(
+/// Reopen a 2v2 tournament that just completed its finals for a 1v1 grand final between the
+/// winning pair.
+#[post("/api/tournaments/{id}/grand-finals/start")]
+async fn api_start_grand_finals(
+    req: HttpRequest,
     state: AppState,
     path: Path<TournamentPath>,
-    body: Json<SetMatchWinnerBody>,
 ) -> HttpResponse {
     let mut g = match state.write() {
         Ok(guard) => guard,
@@ -397,17 +2881,16 @@ async fn api_set_match_winner(
         }
     };
     entry.last_activity = Instant::now();
-    let t = &mut entry.tournament;
-    if !t.matches.iter().any(|m| m.id == body.match_id) {
-        return HttpResponse::BadRequest().json(serde_json::json!({ "error": "Match not found" }));
-    }
-    t.match_results.insert(body.match_id, body.team);
-    HttpResponse::Ok().json(t)
+    apply_mutation(&req, entry, start_grand_finals)
 }
 
-/// Submit group play results and process (tournament must be in GroupPlay).
-#[post("/api/tournaments/{id}/matches/submit")]
-async fn api_submit_match_results(state: AppState, path: Path<TournamentPath>) -> HttpResponse {
+/// Generate the redemption round (Redemption only). Winners are set with `PUT .../finals/winner`.
+#[post("/api/tournaments/{id}/redemption/matches")]
+async fn api_redemption_generate_matches(
+    req: HttpRequest,
+    state: AppState,
+    path: Path<TournamentPath>,
+) -> HttpResponse {
     let mut g = match state.write() {
         Ok(guard) => guard,
         Err(_) => return HttpResponse::InternalServerError().body("lock error"),
@@ -419,19 +2902,15 @@ async fn api_submit_match_results(state: AppState, path: Path<TournamentPath>) -
         }
     };
     entry.last_activity = Instant::now();
-    let t = &mut entry.tournament;
-    match process_group_play_results(t) {
-        Ok(()) => HttpResponse::Ok().json(t),
-        Err(e) => HttpResponse::BadRequest().json(serde_json::json!({ "error": e.to_string() })),
-    }
+    apply_mutation(&req, entry, generate_redemption_matches)
 }
 
-/// Set a player's losses manually (GroupPlay or FinalSelection).
-#[put("/api/tournaments/{id}/players/{player_id}/losses")]
-async fn api_set_player_losses(
+/// Submit the redemption round: winners rejoin, then SemiFinals (field full) or FinalSelection.
+#[post("/api/tournaments/{id}/redemption/submit")]
+async fn api_redemption_submit(
+    req: HttpRequest,
     state: AppState,
-    path: Path<TournamentPlayerPath>,
-    body: Json<SetPlayerLossesBody>,
+    path: Path<TournamentPath>,
 ) -> HttpResponse {
     let mut g = match state.write() {
         Ok(guard) => guard,
@@ -444,16 +2923,16 @@ async fn api_set_player_losses(
         }
     };
     entry.last_activity = Instant::now();
-    let t = &mut entry.tournament;
-    match t.set_player_losses(path.player_id, body.losses) {
-        Ok(()) => HttpResponse::Ok().json(t),
-        Err(e) => HttpResponse::BadRequest().json(serde_json::json!({ "error": e.to_string() })),
-    }
+    apply_mutation(&req, entry, process_redemption_results)
 }
 
-/// Manually eliminate a player (GroupPlay or FinalSelection).
-#[post("/api/tournaments/{id}/players/{player_id}/eliminate")]
-async fn api_eliminate_player(state: AppState, path: Path<TournamentPlayerPath>) -> HttpResponse {
+/// Generate semi-final matches (SemiFinals only, 8 players).
+#[post("/api/tournaments/{id}/finals/matches")]
+async fn api_finals_generate_matches(
+    req: HttpRequest,
+    state: AppState,
+    path: Path<TournamentPath>,
+) -> HttpResponse {
     let mut g = match state.write() {
         Ok(guard) => guard,
         Err(_) => return HttpResponse::InternalServerError().body("lock error"),
@@ -465,19 +2944,15 @@ async fn api_eliminate_player(state: AppState, path: Path<TournamentPlayerPath>)
         }
     };
     entry.last_activity = Instant::now();
-    let t = &mut entry.tournament;
-    match t.eliminate_player(path.player_id) {
-        Ok(()) => HttpResponse::Ok().json(t),
-        Err(e) => HttpResponse::BadRequest().json(serde_json::json!({ "error": e.to_string() })),
-    }
+    apply_mutation(&req, entry, generate_semi_final_matches)
 }
 
-/// Set tournament mode 1v1 or 2v2 (Setup only).
-#[put("/api/tournaments/{id}/mode")]
-async fn api_set_mode(
+/// Generate the next knockout round (knockout-all format).
+#[post("/api/tournaments/{id}/knockout/matches")]
+async fn api_knockout_generate_matches(
+    req: HttpRequest,
     state: AppState,
     path: Path<TournamentPath>,
-    body: Json<SetModeBody>,
 ) -> HttpResponse {
     let mut g = match state.write() {
         Ok(guard) => guard,
@@ -490,16 +2965,17 @@ async fn api_set_mode(
         }
     };
     entry.last_activity = Instant::now();
-    let t = &mut entry.tournament;
-    match t.set_mode(body.mode) {
-        Ok(()) => HttpResponse::Ok().json(t),
-        Err(e) => HttpResponse::BadRequest().json(serde_json::json!({ "error": e.to_string() })),
-    }
+    apply_mutation(&req, entry, generate_knockout_matches)
 }
 
-/// Restart tournament: back to Setup with same player names.
-#[post("/api/tournaments/{id}/restart")]
-async fn api_restart_tournament(state: AppState, path: Path<TournamentPath>) -> HttpResponse {
+/// Set winner for a final-round match (semi, finals, grand finals, or knockout).
+#[put("/api/tournaments/{id}/finals/winner")]
+async fn api_finals_set_winner(
+    req: HttpRequest,
+    state: AppState,
+    path: Path<TournamentPath>,
+    body: Json<SetMatchWinnerBody>,
+) -> HttpResponse {
     let mut g = match state.write() {
         Ok(guard) => guard,
         Err(_) => return HttpResponse::InternalServerError().body("lock error"),
@@ -511,19 +2987,18 @@ async fn api_restart_tournament(state: AppState, path: Path<TournamentPath>) ->
         }
     };
     entry.last_activity = Instant::now();
-    let t = &mut entry.tournament;
-    match t.restart_tournament() {
-        Ok(()) => HttpResponse::Ok().json(t),
-        Err(e) => HttpResponse::BadRequest().json(serde_json::json!({ "error": e.to_string() })),
-    }
+    apply_mutation(&req, entry, |t| {
+        set_finals_match_winner(t, body.target.resolve(t)?, body.team)
+    })
 }
 
-/// Add selected players from last eliminated back to reach 8 (FinalSelection only).
-#[post("/api/tournaments/{id}/final-selection/add-back")]
-async fn api_final_selection_add_back(
+/// Record the winner of the next leg in a semi-final/finals series (`legs_per_final_match` > 1).
+#[put("/api/tournaments/{id}/finals/legs")]
+async fn api_finals_record_leg(
+    req: HttpRequest,
     state: AppState,
     path: Path<TournamentPath>,
-    body: Json<FinalSelectionAddBackBody>,
+    body: Json<SetMatchWinnerBody>,
 ) -> HttpResponse {
     let mut g = match state.write() {
         Ok(guard) => guard,
@@ -536,18 +3011,18 @@ async fn api_final_selection_add_back(
         }
     };
     entry.last_activity = Instant::now();
-    let t = &mut entry.tournament;
-    match add_players_back_from_last_eliminated(t, &body.player_ids) {
-        Ok(()) => HttpResponse::Ok().json(t),
-        Err(e) => HttpResponse::BadRequest().json(serde_json::json!({ "error": e.to_string() })),
-    }
+    apply_mutation(&req, entry, |t| {
+        record_finals_leg(t, body.target.resolve(t)?, body.team)
+    })
 }
 
-/// Transition to semi-finals when 8 players in final selection (no add-back needed).
-#[post("/api/tournaments/{id}/final-selection/start-semi")]
-async fn api_final_selection_start_semi(
+/// Set the number of legs per semi-final/finals series (odd; before the playoffs).
+#[put("/api/tournaments/{id}/legs-per-final-match")]
+async fn api_set_legs_per_final_match(
+    req: HttpRequest,
     state: AppState,
     path: Path<TournamentPath>,
+    body: Json<SetLegsPerFinalMatchBody>,
 ) -> HttpResponse {
     let mut g = match state.write() {
         Ok(guard) => guard,
@@ -560,16 +3035,19 @@ async fn api_final_selection_start_semi(
         }
     };
     entry.last_activity = Instant::now();
-    let t = &mut entry.tournament;
-    match start_semi_finals(t) {
-        Ok(()) => HttpResponse::Ok().json(t),
-        Err(e) => HttpResponse::BadRequest().json(serde_json::json!({ "error": format!("{}", e) })),
-    }
+    apply_mutation(&req, entry, |t| {
+        t.set_legs_per_final_match(body.legs_per_final_match)
+    })
 }
 
-/// Generate semi-final matches (SemiFinals only, 8 players).
-#[post("/api/tournaments/{id}/finals/matches")]
-async fn api_finals_generate_matches(state: AppState, path: Path<TournamentPath>) -> HttpResponse {
+/// Record the current finals match as drawn; submitting resolves it per `final_tie_resolution`.
+#[put("/api/tournaments/{id}/finals/draw")]
+async fn api_finals_record_draw(
+    req: HttpRequest,
+    state: AppState,
+    path: Path<TournamentPath>,
+    body: Json<FinalsDrawBody>,
+) -> HttpResponse {
     let mut g = match state.write() {
         Ok(guard) => guard,
         Err(_) => return HttpResponse::InternalServerError().body("lock error"),
@@ -581,19 +3059,18 @@ async fn api_finals_generate_matches(state: AppState, path: Path<TournamentPath>
         }
     };
     entry.last_activity = Instant::now();
-    let t = &mut entry.tournament;
-    match generate_semi_final_matches(t) {
-        Ok(()) => HttpResponse::Ok().json(t),
-        Err(e) => HttpResponse::BadRequest().json(serde_json::json!({ "error": e.to_string() })),
-    }
+    apply_mutation(&req, entry, |t| {
+        record_drawn_final(t, body.target.resolve(t)?)
+    })
 }
 
-/// Set winner for a final-round match (semi, finals, or grand finals).
-#[put("/api/tournaments/{id}/finals/winner")]
-async fn api_finals_set_winner(
+/// Pick who plays the pending sudden-death decider (one player from each team of the drawn final).
+#[put("/api/tournaments/{id}/finals/sudden-death-players")]
+async fn api_finals_set_sudden_death_players(
+    req: HttpRequest,
     state: AppState,
     path: Path<TournamentPath>,
-    body: Json<SetMatchWinnerBody>,
+    body: Json<SuddenDeathPlayersBody>,
 ) -> HttpResponse {
     let mut g = match state.write() {
         Ok(guard) => guard,
@@ -606,16 +3083,43 @@ async fn api_finals_set_winner(
         }
     };
     entry.last_activity = Instant::now();
-    let t = &mut entry.tournament;
-    match set_finals_match_winner(t, body.match_id, body.team) {
-        Ok(()) => HttpResponse::Ok().json(t),
-        Err(e) => HttpResponse::BadRequest().json(serde_json::json!({ "error": e.to_string() })),
-    }
+    apply_mutation(&req, entry, |t| {
+        set_sudden_death_players(t, body.team_1_player, body.team_2_player)
+    })
+}
+
+/// Set what a drawn final leads to: a replay, a 1v1 decider or a shared title (until completed).
+#[put("/api/tournaments/{id}/final-tie-resolution")]
+async fn api_set_final_tie_resolution(
+    req: HttpRequest,
+    state: AppState,
+    path: Path<TournamentPath>,
+    body: Json<SetFinalTieResolutionBody>,
+) -> HttpResponse {
+    let mut g = match state.write() {
+        Ok(guard) => guard,
+        Err(_) => return HttpResponse::InternalServerError().body("lock error"),
+    };
+    let entry = match g.get_mut(&path.id) {
+        Some(e) => e,
+        None => {
+            return HttpResponse::NotFound().json(serde_json::json!({ "error": "No tournament" }))
+        }
+    };
+    entry.last_activity = Instant::now();
+    apply_mutation(&req, entry, |t| {
+        t.set_final_tie_resolution(body.final_tie_resolution)
+    })
 }
 
-/// Submit current final round (semi → finals, finals → completed).
+/// Submit current final round (semi → finals, finals → completed or grand finals, grand finals →
+/// completed, knockout → next knockout round or finals).
 #[post("/api/tournaments/{id}/finals/submit")]
-async fn api_finals_submit(state: AppState, path: Path<TournamentPath>) -> HttpResponse {
+async fn api_finals_submit(
+    req: HttpRequest,
+    state: AppState,
+    path: Path<TournamentPath>,
+) -> HttpResponse {
     let mut g = match state.write() {
         Ok(guard) => guard,
         Err(_) => return HttpResponse::InternalServerError().body("lock error"),
@@ -627,16 +3131,49 @@ async fn api_finals_submit(state: AppState, path: Path<TournamentPath>) -> HttpR
         }
     };
     entry.last_activity = Instant::now();
-    let t = &mut entry.tournament;
-    let result = match t.state {
+    apply_mutation(&req, entry, |t| match t.state {
         dart_tournament_web::TournamentState::SemiFinals => process_semi_final_results(t),
         dart_tournament_web::TournamentState::Finals => process_finals_results(t),
+        dart_tournament_web::TournamentState::GrandFinals => process_grand_finals_results(t),
+        dart_tournament_web::TournamentState::Knockout => process_knockout_results(t),
         _ => Err(dart_tournament_web::TournamentError::InvalidState),
+    })
+}
+
+/// Perform the natural next step for the current state (see `advance_tournament` for the per-state rules).
+/// Like a group play submit, the response's `recently_eliminated` lists who went out when the step
+/// submitted a group play round (empty otherwise).
+#[post("/api/tournaments/{id}/advance")]
+async fn api_advance_tournament(
+    req: HttpRequest,
+    state: AppState,
+    path: Path<TournamentPath>,
+) -> HttpResponse {
+    let mut g = match state.write() {
+        Ok(guard) => guard,
+        Err(_) => return HttpResponse::InternalServerError().body("lock error"),
     };
-    match result {
-        Ok(()) => HttpResponse::Ok().json(t),
-        Err(e) => HttpResponse::BadRequest().json(serde_json::json!({ "error": e.to_string() })),
-    }
+    let entry = match g.get_mut(&path.id) {
+        Some(e) => e,
+        None => {
+            return HttpResponse::NotFound().json(serde_json::json!({ "error": "No tournament" }))
+        }
+    };
+    entry.last_activity = Instant::now();
+    let rounds_before = entry.tournament.rounds_completed;
+    apply_mutation_with_extras(&req, entry, advance_tournament, |t| {
+        let eliminated = if t.rounds_completed > rounds_before {
+            t.recently_eliminated()
+        } else {
+            Vec::new()
+        };
+        let mut extras = serde_json::Map::new();
+        extras.insert(
+            "recently_eliminated".into(),
+            serde_json::to_value(eliminated).unwrap_or_default(),
+        );
+        extras
+    })
 }
 
 fn default_host() -> String {
@@ -660,13 +3197,55 @@ async fn main() -> std::io::Result<()> {
     log::info!("Starting server at http://{}:{}", bind.0, bind.1);
 
     let state = Data::new(RwLock::new(HashMap::<TournamentId, TournamentEntry>::new()));
+    let shares: ShareState = Data::new(RwLock::new(ShareLinks::default()));
     let site_gate = web::Data::new(SiteGate::new());
+    let audit_log: AuditState =
+        Data::new(
+            std::env::var("AUDIT_LOG")
+                .ok()
+                .and_then(|path| match AuditLog::open(&path) {
+                    Ok(audit_log) => {
+                        log::info!("Appending API mutations to audit log {}", path);
+                        Some(audit_log)
+                    }
+                    Err(e) => {
+                        log::error!("Cannot open audit log {}: {}; auditing disabled", path, e);
+                        None
+                    }
+                }),
+        );
     log::info!("Site gate active (see SITE_GATE_PLAIN in web.rs)");
+    let admin_token = Data::new(AdminToken(
+        std::env::var("ADMIN_TOKEN")
+            .ok()
+            .map(|t| t.trim().to_string())
+            .filter(|t| !t.is_empty()),
+    ));
+    if admin_token.0.is_none() {
+        log::info!("Tournament list disabled (set ADMIN_TOKEN to enable it)");
+    }
+
+    let limits = Data::new(InputLimits {
+        max_players: env_limit("MAX_PLAYERS", DEFAULT_MAX_PLAYERS),
+        max_name_len: env_limit("MAX_NAME_LEN", DEFAULT_MAX_NAME_LEN),
+        max_avatar_url_len: env_limit("MAX_AVATAR_URL_LEN", DEFAULT_MAX_AVATAR_URL_LEN),
+    });
 
-    // Background task: every 30 minutes, remove tournaments inactive for 12+ hours
+    let cleanup_interval =
+        env_duration_secs("CLEANUP_INTERVAL_SECS", DEFAULT_CLEANUP_INTERVAL_SECS);
+    let inactivity_timeout =
+        env_duration_secs("INACTIVITY_TIMEOUT_SECS", DEFAULT_INACTIVITY_TIMEOUT_SECS);
+    log::info!(
+        "Cleanup every {}s, removing tournaments idle for {}s",
+        cleanup_interval.as_secs(),
+        inactivity_timeout.as_secs()
+    );
+
+    // Background task: every cleanup interval, remove tournaments past the inactivity timeout
     let state_cleanup = state.clone();
+    let shares_cleanup = shares.clone();
     actix_web::rt::spawn(async move {
-        let mut interval = actix_web::rt::time::interval(Duration::from_secs(30 * 60));
+        let mut interval = actix_web::rt::time::interval(cleanup_interval);
         loop {
             interval.tick().await;
             let mut g = match state_cleanup.write() {
@@ -674,45 +3253,197 @@ async fn main() -> std::io::Result<()> {
                 Err(_) => continue,
             };
             let before = g.len();
-            g.retain(|_, entry| entry.last_activity.elapsed() < INACTIVITY_TIMEOUT);
+            g.retain(|_, entry| {
+                entry.tournament.paused || entry.last_activity.elapsed() < inactivity_timeout
+            });
             let removed = before - g.len();
+            if let Ok(mut shares) = shares_cleanup.write() {
+                shares.retain_tournaments(|id| g.contains_key(&id));
+            }
             if removed > 0 {
                 log::info!(
-                    "Cleaned up {} inactive tournament(s) (no activity for 12h)",
-                    removed
+                    "Cleaned up {} inactive tournament(s) (no activity for {}s)",
+                    removed,
+                    inactivity_timeout.as_secs()
                 );
             }
         }
     });
 
+    // Background task: submit kiosk rounds whose auto-submit countdown has run out. A failed
+    // submit waits for the next change instead of retrying every tick.
+    let state_auto_submit = state.clone();
+    let audit_auto_submit = audit_log.clone();
+    actix_web::rt::spawn(async move {
+        let mut interval = actix_web::rt::time::interval(AUTO_SUBMIT_TICK);
+        loop {
+            interval.tick().await;
+            let now = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |d| d.as_millis() as u64);
+            // Look under the read lock first so idle ticks never block requests.
+            let due: Vec<TournamentId> = match state_auto_submit.read() {
+                Ok(g) => g
+                    .values()
+                    .filter(|entry| entry.tournament.auto_submit_due(now))
+                    .map(|entry| entry.tournament.id)
+                    .collect(),
+                Err(_) => continue,
+            };
+            if due.is_empty() {
+                continue;
+            }
+            let mut g = match state_auto_submit.write() {
+                Ok(guard) => guard,
+                Err(_) => continue,
+            };
+            for id in due {
+                let Some(entry) = g.get_mut(&id) else {
+                    continue;
+                };
+                if !entry.tournament.auto_submit_due(now) {
+                    continue;
+                }
+                match entry.tournament.apply_versioned(None, advance_tournament) {
+                    Ok(()) => {
+                        log::info!(
+                            "Auto-submitted a round of tournament {}",
+                            entry.tournament.id
+                        );
+                        if let Some(audit_log) = audit_auto_submit.get_ref() {
+                            let _ = audit_log.record(
+                                entry.tournament.id,
+                                "auto-submit",
+                                &format!("version {}", entry.tournament.version),
+                            );
+                        }
+                        if entry.events.receiver_count() > 0 {
+                            let _ = entry
+                                .events
+                                .send(tournament_json(&entry.tournament).to_string());
+                        }
+                    }
+                    Err(e) => {
+                        log::warn!(
+                            "Auto-submit of tournament {} failed: {}",
+                            entry.tournament.id,
+                            e
+                        );
+                        entry.tournament.results_complete_at = None;
+                    }
+                }
+            }
+        }
+    });
+
     HttpServer::new(move || {
         App::new()
             .wrap(from_fn(site_gate_middleware))
             .app_data(state.clone())
+            .app_data(shares.clone())
             .app_data(site_gate.clone())
+            .app_data(admin_token.clone())
+            .app_data(limits.clone())
+            .app_data(audit_log.clone())
             .route("/", web::get().to(serve_index_async))
             .service(api_health)
             .service(favicon)
             .service(api_site_gate_check)
             .service(api_site_gate_login)
+            .service(api_openapi)
             .service(api_create_tournament)
+            .service(api_quickstart_tournament)
+            .service(api_list_tournaments)
             .service(api_get_tournament)
+            .service(api_get_history)
+            .service(api_share_tournament)
+            .service(api_revoke_share)
+            .service(api_get_shared)
+            .service(api_amend_history_result)
+            .service(api_get_bracket)
+            .service(api_get_bracket_svg)
+            .service(api_get_results_html)
+            .service(api_get_current_round)
+            .service(api_get_eliminated)
+            .service(api_get_projection)
+            .service(api_get_result)
+            .service(api_get_projected_finalists)
+            .service(api_get_team_standings)
+            .service(api_validate)
+            .service(api_get_counts)
+            .service(api_get_config)
+            .service(api_set_config)
+            .service(api_events)
             .service(api_add_player)
+            .service(api_import_players_with_stats)
+            .service(api_get_player)
+            .service(api_get_player_matches)
+            .service(api_reorder_players)
             .service(api_remove_player)
             .service(api_set_max_losses)
             .service(api_set_mode)
+            .service(api_set_balance_teams)
+            .service(api_set_shuffle_match_order)
+            .service(api_set_auto_generate_next)
+            .service(api_set_grace_matches)
+            .service(api_set_auto_start_semis)
+            .service(api_set_allow_ghost)
+            .service(api_set_strict_bye_rotation)
+            .service(api_set_deterministic)
+            .service(api_set_single_champion)
+            .service(api_set_champion_name)
+            .service(api_set_draws_allowed)
+            .service(api_pause_tournament)
+            .service(api_resume_tournament)
+            .service(api_set_finals_format)
+            .service(api_set_forfeit_policy)
+            .service(api_set_format)
+            .service(api_set_seeding)
+            .service(api_set_scoring_mode)
+            .service(api_set_num_boards)
+            .service(api_set_max_concurrent_matches)
+            .service(api_set_reshuffle_every)
+            .service(api_set_knockout_after_rounds)
+            .service(api_set_round_cap)
+            .service(api_set_auto_submit_after)
             .service(api_start_tournament)
             .service(api_generate_matches)
+            .service(api_generate_practice_round)
             .service(api_set_match_winner)
+            .service(api_swap_players)
+            .service(api_simulate_match_winner)
+            .service(api_void_match_result)
             .service(api_submit_match_results)
             .service(api_set_player_losses)
+            .service(api_set_player_rating)
+            .service(api_set_player_appearance)
+            .service(api_set_player_handicap_spot)
             .service(api_eliminate_player)
+            .service(api_forfeit_player)
+            .service(api_request_sit_out)
+            .service(api_eliminate_players_bulk)
+            .service(api_import_players_csv)
             .service(api_restart_tournament)
+            .service(api_reset_tournament)
+            .service(api_get_finals_seeding)
+            .service(api_final_selection_candidates)
             .service(api_final_selection_add_back)
+            .service(api_final_selection_add_back_any)
+            .service(api_final_selection_auto_fill)
             .service(api_final_selection_start_semi)
+            .service(api_start_grand_finals)
+            .service(api_redemption_generate_matches)
+            .service(api_redemption_submit)
             .service(api_finals_generate_matches)
+            .service(api_knockout_generate_matches)
             .service(api_finals_set_winner)
+            .service(api_finals_record_leg)
+            .service(api_set_legs_per_final_match)
+            .service(api_finals_record_draw)
+            .service(api_finals_set_sudden_death_players)
+            .service(api_set_final_tie_resolution)
             .service(api_finals_submit)
+            .service(api_advance_tournament)
             .service(Files::new("/static", "static").show_files_listing())
     })
     .bind(bind)?
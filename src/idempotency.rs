@@ -0,0 +1,62 @@
+//! `Idempotency-Key` replay for API mutations: the response to a keyed request is remembered so a
+//! retry gets the same answer without running the mutation again.
+
+use std::collections::VecDeque;
+
+/// How many idempotency keys are remembered per tournament (least recently used are dropped).
+pub const IDEMPOTENCY_CACHE_CAPACITY: usize = 64;
+
+/// A client key scoped to the request it came with: the same key sent to another route (or with
+/// another method) is a different request and is not replayed.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct IdempotencyKey {
+    pub method: String,
+    pub path: String,
+    pub key: String,
+}
+
+impl IdempotencyKey {
+    /// `None` for a missing or blank client key.
+    pub fn new(method: &str, path: &str, key: &str) -> Option<Self> {
+        let key = key.trim();
+        (!key.is_empty()).then(|| Self {
+            method: method.to_string(),
+            path: path.to_string(),
+            key: key.to_string(),
+        })
+    }
+}
+
+/// Bounded LRU of idempotency keys and their responses (most recently used at the back).
+#[derive(Clone, Debug)]
+pub struct IdempotencyCache<R> {
+    entries: VecDeque<(IdempotencyKey, R)>,
+}
+
+impl<R> Default for IdempotencyCache<R> {
+    fn default() -> Self {
+        Self {
+            entries: VecDeque::new(),
+        }
+    }
+}
+
+impl<R: Clone> IdempotencyCache<R> {
+    /// Cached response for `key`, marking it as most recently used.
+    pub fn get(&mut self, key: &IdempotencyKey) -> Option<R> {
+        let idx = self.entries.iter().position(|(k, _)| k == key)?;
+        let entry = self.entries.remove(idx)?;
+        let response = entry.1.clone();
+        self.entries.push_back(entry);
+        Some(response)
+    }
+
+    /// Remember the response for `key`, evicting the least recently used key when full.
+    pub fn insert(&mut self, key: IdempotencyKey, response: R) {
+        self.entries.retain(|(k, _)| *k != key);
+        if self.entries.len() >= IDEMPOTENCY_CACHE_CAPACITY {
+            self.entries.pop_front();
+        }
+        self.entries.push_back((key, response));
+    }
+}
@@ -1,14 +1,32 @@
 //! Dart tournament web app: library with models and business logic.
 
+pub mod audit;
+pub mod escape;
+pub mod idempotency;
 pub mod logic;
 pub mod models;
+pub mod openapi;
+pub mod share;
 
 pub use logic::{
-    add_players_back_from_last_eliminated, generate_group_play_matches,
-    generate_semi_final_matches, process_finals_results, process_group_play_results,
-    process_semi_final_results, set_finals_match_winner, start_semi_finals, start_tournament,
+    add_players_back_from_last_eliminated, advance_tournament, auto_fill_finalists, bracket_svg,
+    generate_group_play_matches, generate_group_play_matches_with_rng, generate_knockout_matches,
+    generate_practice_round, generate_redemption_matches, generate_semi_final_matches,
+    import_roster_csv, knockout_round, parse_roster_csv, process_finals_results,
+    process_grand_finals_results, process_group_play_results, process_group_play_results_summary,
+    process_knockout_results, process_redemption_results, process_semi_final_results,
+    quickstart_tournament, record_drawn_final, record_finals_leg, reproduce_round, results_html,
+    semi_final_bracket_shape, set_finals_match_winner, set_sudden_death_players,
+    start_grand_finals, start_semi_finals, start_tournament, CsvRowError, KnockoutRound,
+    ParsedRoster, QuickstartReport, RosterImport, SemiFinalShape,
 };
 pub use models::{
-    GameMatch, MatchId, Player, PlayerId, PlayerStats, RoundType, Team, Tournament,
-    TournamentError, TournamentId, TournamentMode, TournamentState,
+    AddBackRejection, Counts, CurrentRoundView, EliminatedPlayer, EliminationReason,
+    FeasibilityReport, FeasibilityWarning, FinalResult, FinalSelectionInfo, FinalTieResolution,
+    FinalsFormat, ForfeitPolicy, GameMatch, GameMatchView, InputLimits, MatchId, MatchOutcome,
+    Placement, Player, PlayerId, PlayerLocation, PlayerStats, PlayerSummary, PointsConfig,
+    ProjectedBracket, ProjectedFinal, RoundSummary, RoundType, ScoringMode, SeedingStrategy,
+    SimResult, Team, TeamMember, TeamStats, Tournament, TournamentAction, TournamentConfig,
+    TournamentError, TournamentFormat, TournamentId, TournamentMode, TournamentState,
+    TournamentSummary, DEFAULT_MAX_AVATAR_URL_LEN, DEFAULT_MAX_NAME_LEN, DEFAULT_MAX_PLAYERS,
 };
@@ -0,0 +1,69 @@
+//! One-step dispatcher: perform the natural next action for the tournament's current state.
+
+use crate::logic::{
+    generate_group_play_matches, generate_knockout_matches, generate_redemption_matches,
+    generate_semi_final_matches, process_finals_results, process_grand_finals_results,
+    process_group_play_results, process_knockout_results, process_redemption_results,
+    process_semi_final_results, start_semi_finals, start_tournament,
+};
+use crate::models::{Tournament, TournamentError, TournamentState};
+
+/// Advance the tournament by one logical step, reusing the per-phase functions:
+///
+/// - `Setup`: start the tournament ([`start_tournament`]).
+/// - `GroupPlay`: with no round generated, generate one; otherwise submit the round
+///   ([`process_group_play_results`]) and, if still in group play, generate the next round
+///   (unless `auto_generate_next` already did).
+/// - `Redemption`: with no matches, generate them; otherwise submit the redemption round.
+/// - `FinalSelection`: start the semi-finals when the field allows it ([`start_semi_finals`]);
+///   otherwise `InvalidState`, since choosing who rejoins is up to the operator.
+/// - `SemiFinals`: with no matches, generate them; otherwise submit the semi-finals.
+/// - `Finals`: submit the finals, completing the tournament (or moving on to the grand final).
+/// - `GrandFinals`: submit the grand final, completing the tournament.
+/// - `Knockout`: with no matches, generate the next knockout round; otherwise submit it.
+/// - `Completed`: `InvalidState`.
+///
+/// Submitting with missing results returns `IncompleteResults` and changes nothing.
+pub fn advance_tournament(tournament: &mut Tournament) -> Result<(), TournamentError> {
+    match tournament.state {
+        TournamentState::Setup => start_tournament(tournament),
+        TournamentState::GroupPlay => {
+            if tournament.matches.is_empty() {
+                return generate_group_play_matches(tournament);
+            }
+            // Submit and next round together: a failed generation also undoes the submit.
+            tournament.transaction(|t| {
+                process_group_play_results(t)?;
+                if t.state == TournamentState::GroupPlay && t.matches.is_empty() {
+                    generate_group_play_matches(t)?;
+                }
+                Ok(())
+            })
+        }
+        TournamentState::Redemption => {
+            if tournament.matches.is_empty() {
+                generate_redemption_matches(tournament)
+            } else {
+                process_redemption_results(tournament)
+            }
+        }
+        TournamentState::FinalSelection => start_semi_finals(tournament),
+        TournamentState::SemiFinals => {
+            if tournament.matches.is_empty() {
+                generate_semi_final_matches(tournament)
+            } else {
+                process_semi_final_results(tournament)
+            }
+        }
+        TournamentState::Finals => process_finals_results(tournament),
+        TournamentState::GrandFinals => process_grand_finals_results(tournament),
+        TournamentState::Knockout => {
+            if tournament.matches.is_empty() {
+                generate_knockout_matches(tournament)
+            } else {
+                process_knockout_results(tournament)
+            }
+        }
+        TournamentState::Completed => Err(TournamentError::InvalidState),
+    }
+}
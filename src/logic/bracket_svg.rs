@@ -0,0 +1,172 @@
+//! Printable bracket: semi-finals → finals → winners rendered as a hand-built SVG.
+
+use crate::escape::html as escape;
+use crate::models::{GameMatch, PlayerId, Team, Tournament};
+use std::fmt::Write;
+
+const BOX_WIDTH: u32 = 200;
+const ROW_HEIGHT: u32 = 24;
+const BOX_HEIGHT: u32 = 2 * ROW_HEIGHT;
+const BOX_GAP: u32 = 32;
+const MARGIN: u32 = 20;
+const COLUMN_GAP: u32 = 50;
+const TITLE_HEIGHT: u32 = 30;
+
+/// Render the semi-finals → finals bracket as a standalone SVG document.
+///
+/// Uses the `bracket_*` snapshots (plus the live finals match while in Finals); winners are shown
+/// in bold. Before the semi-finals have been played a small placeholder SVG is returned.
+pub fn bracket_svg(tournament: &Tournament) -> String {
+    let Some(semis) = &tournament.bracket_semi_final_matches else {
+        return placeholder_svg("Bracket is available once the semi-finals are played");
+    };
+    let semi_results = tournament.bracket_semi_final_results.as_ref();
+    let finals = tournament
+        .bracket_finals_match
+        .as_ref()
+        .or(tournament.matches.first());
+
+    // Semi column: played matches, then one box per bye (finalists who played no semi-final).
+    let byes: Vec<PlayerId> = finals
+        .iter()
+        .flat_map(|m| m.team_1.iter().chain(&m.team_2))
+        .filter(|id| {
+            !semis
+                .iter()
+                .any(|s| s.team_1.contains(id) || s.team_2.contains(id))
+        })
+        .copied()
+        .collect();
+    let entries = semis.len() + byes.len();
+    let column_height = entries as u32 * (BOX_HEIGHT + BOX_GAP) - BOX_GAP;
+    let width = 2 * MARGIN + 3 * BOX_WIDTH + 2 * COLUMN_GAP;
+    let height = 2 * MARGIN + TITLE_HEIGHT + column_height;
+    let semi_x = MARGIN;
+    let finals_x = semi_x + BOX_WIDTH + COLUMN_GAP;
+    let winners_x = finals_x + BOX_WIDTH + COLUMN_GAP;
+    let top = MARGIN + TITLE_HEIGHT;
+    let finals_y = top + (column_height - BOX_HEIGHT) / 2;
+
+    let mut svg = svg_header(width, height);
+    let _ = write!(
+        svg,
+        r#"<text x="{semi_x}" y="{}" font-weight="bold">Semi-finals</text><text x="{finals_x}" y="{}" font-weight="bold">Finals</text><text x="{winners_x}" y="{}" font-weight="bold">Winners</text>"#,
+        MARGIN + 14,
+        MARGIN + 14,
+        MARGIN + 14
+    );
+
+    let mut y = top;
+    for m in semis {
+        let winner = semi_results.and_then(|r| r.get(&m.id)).copied();
+        match_box(&mut svg, tournament, m, winner, semi_x, y);
+        connector(
+            &mut svg,
+            semi_x + BOX_WIDTH,
+            y + ROW_HEIGHT,
+            finals_x,
+            finals_y + ROW_HEIGHT,
+        );
+        y += BOX_HEIGHT + BOX_GAP;
+    }
+    for &id in &byes {
+        let label = format!("{} (bye)", tournament.player_name(id));
+        team_row(&mut svg, &label, false, semi_x, y);
+        let _ = write!(
+            svg,
+            r##"<rect x="{semi_x}" y="{}" width="{BOX_WIDTH}" height="{ROW_HEIGHT}" fill="#f4f4f4" stroke="#999" stroke-dasharray="4 2"/>"##,
+            y + ROW_HEIGHT
+        );
+        connector(
+            &mut svg,
+            semi_x + BOX_WIDTH,
+            y + ROW_HEIGHT / 2,
+            finals_x,
+            finals_y + ROW_HEIGHT,
+        );
+        y += BOX_HEIGHT + BOX_GAP;
+    }
+
+    if let Some(m) = finals {
+        let winner = tournament.bracket_finals_result;
+        match_box(&mut svg, tournament, m, winner, finals_x, finals_y);
+        if let Some(team) = winner {
+            connector(
+                &mut svg,
+                finals_x + BOX_WIDTH,
+                finals_y + ROW_HEIGHT,
+                winners_x,
+                finals_y + ROW_HEIGHT,
+            );
+            let ids = match team {
+                Team::One => &m.team_1,
+                Team::Two => &m.team_2,
+            };
+            let names = team_label(tournament, ids);
+            team_row(&mut svg, &names, true, winners_x, finals_y + ROW_HEIGHT / 2);
+        }
+    }
+
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// Minimal SVG with a single line of text (before the bracket exists).
+fn placeholder_svg(message: &str) -> String {
+    let mut svg = svg_header(480, 80);
+    let _ = write!(
+        svg,
+        r##"<text x="{MARGIN}" y="45" fill="#666">{}</text></svg>"##,
+        escape(message)
+    );
+    svg.push('\n');
+    svg
+}
+
+fn svg_header(width: u32, height: u32) -> String {
+    format!(
+        r##"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" viewBox="0 0 {width} {height}" font-family="sans-serif" font-size="13"><rect width="100%" height="100%" fill="#fff"/>"##
+    )
+}
+
+/// Two stacked team rows; the winning team (if known) is bold.
+fn match_box(
+    svg: &mut String,
+    tournament: &Tournament,
+    m: &GameMatch,
+    winner: Option<Team>,
+    x: u32,
+    y: u32,
+) {
+    let team_1 = team_label(tournament, &m.team_1);
+    let team_2 = team_label(tournament, &m.team_2);
+    team_row(svg, &team_1, winner == Some(Team::One), x, y);
+    team_row(svg, &team_2, winner == Some(Team::Two), x, y + ROW_HEIGHT);
+}
+
+fn team_row(svg: &mut String, label: &str, bold: bool, x: u32, y: u32) {
+    let weight = if bold { "bold" } else { "normal" };
+    let _ = write!(
+        svg,
+        r##"<rect x="{x}" y="{y}" width="{BOX_WIDTH}" height="{ROW_HEIGHT}" fill="#f4f4f4" stroke="#999"/><text x="{}" y="{}" font-weight="{weight}">{}</text>"##,
+        x + 6,
+        y + 16,
+        escape(label)
+    );
+}
+
+/// Elbow line from the right edge of one box to the left edge of the next column.
+fn connector(svg: &mut String, x1: u32, y1: u32, x2: u32, y2: u32) {
+    let mid = (x1 + x2) / 2;
+    let _ = write!(
+        svg,
+        r##"<polyline points="{x1},{y1} {mid},{y1} {mid},{y2} {x2},{y2}" fill="none" stroke="#999"/>"##
+    );
+}
+
+pub(crate) fn team_label(tournament: &Tournament, ids: &[PlayerId]) -> String {
+    ids.iter()
+        .map(|&id| tournament.player_name(id))
+        .collect::<Vec<_>>()
+        .join(" & ")
+}
@@ -0,0 +1,113 @@
+//! Printable results sheet: standings, bracket summary and champion as one HTML fragment.
+
+use crate::escape::html as escape;
+use crate::logic::bracket_svg::team_label;
+use crate::models::{GameMatch, RoundType, Team, Tournament, TournamentState};
+use std::fmt::Write;
+
+/// Inline styles so the fragment prints the same wherever it is embedded.
+const STYLE: &str =
+    "<style>.results{font-family:sans-serif}.results table{border-collapse:collapse}\
+.results th,.results td{border:1px solid #999;padding:2px 8px;text-align:left}\
+.results .champion{font-size:1.3em;font-weight:bold}</style>";
+
+/// Self-contained HTML fragment (a `<section>` with its own styles) for printing: a champion
+/// callout once completed, the standings table (see [`Tournament::standings`]) and every played
+/// playoff match. Works in any state; before completion the table shows the current standings.
+pub fn results_html(tournament: &Tournament) -> String {
+    let completed = tournament.state == TournamentState::Completed;
+    let mut html = String::from(r#"<section class="results">"#);
+    html.push_str(STYLE);
+    let title = if completed {
+        "Results"
+    } else {
+        "Standings (in progress)"
+    };
+    let _ = write!(html, "<h2>{title}</h2>");
+
+    if let Some(result) = tournament.final_result() {
+        let names: Vec<&str> = result.winners.iter().map(|p| p.name.as_str()).collect();
+        let team = tournament
+            .champion_team_name
+            .as_deref()
+            .map(|name| format!(" ({})", escape(name)))
+            .unwrap_or_default();
+        let _ = write!(
+            html,
+            r#"<p class="champion">Champion: {}{team}</p>"#,
+            escape(&names.join(" & "))
+        );
+    }
+
+    html.push_str(
+        "<table><thead><tr><th>#</th><th>Player</th><th>W</th><th>D</th><th>L</th>\
+         <th>Pts</th><th>Status</th></tr></thead><tbody>",
+    );
+    // Semi-final losers are only kept in the semi-final snapshot, so list them after the rest.
+    let mut rows = tournament.standings();
+    for p in tournament.bracket_semi_final_players.iter().flatten() {
+        if !rows.iter().any(|r| r.id == p.id) {
+            rows.push(p);
+        }
+    }
+    for (i, p) in rows.into_iter().enumerate() {
+        let active = tournament.players.iter().any(|a| a.id == p.id);
+        let status = match p.eliminated_at_round {
+            _ if active => "In".to_string(),
+            Some(round) => format!("Out (round {round})"),
+            None => "Out".to_string(),
+        };
+        let _ = write!(
+            html,
+            "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{status}</td></tr>",
+            i + 1,
+            escape(&p.name),
+            p.wins,
+            p.draws,
+            p.losses,
+            tournament.points(p)
+        );
+    }
+    html.push_str("</tbody></table>");
+
+    let playoffs: Vec<&GameMatch> = tournament
+        .match_history
+        .iter()
+        .filter(|m| m.round != RoundType::GroupPlay)
+        .collect();
+    if !playoffs.is_empty() {
+        html.push_str("<h3>Bracket</h3><ul>");
+        for m in playoffs {
+            let _ = write!(
+                html,
+                "<li>{}: {} vs {}",
+                round_label(m.round),
+                escape(&team_label(tournament, &m.team_1)),
+                escape(&team_label(tournament, &m.team_2))
+            );
+            if let Some(winner) = m.winner {
+                let ids = match winner {
+                    Team::One => &m.team_1,
+                    Team::Two => &m.team_2,
+                };
+                let _ = write!(html, " (won by {})", escape(&team_label(tournament, ids)));
+            }
+            html.push_str("</li>");
+        }
+        html.push_str("</ul>");
+    }
+
+    html.push_str("</section>\n");
+    html
+}
+
+fn round_label(round: RoundType) -> &'static str {
+    match round {
+        RoundType::GroupPlay => "Group play",
+        RoundType::Redemption => "Redemption",
+        RoundType::SemiFinals => "Semi-final",
+        RoundType::Finals => "Final",
+        RoundType::GrandFinals => "Grand final",
+        RoundType::Knockout => "Knockout",
+    }
+}
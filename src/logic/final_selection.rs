@@ -1,6 +1,6 @@
 //! Final selection: add players back from last eliminated to reach 4 (1v1) or 8 (2v2) for semi-finals.
 
-use crate::models::{PlayerId, Tournament, TournamentError, TournamentState};
+use crate::models::{record_rank, Player, PlayerId, Tournament, TournamentError, TournamentState};
 
 /// Add selected players from last_eliminated_players back to the tournament.
 /// Must select exactly (required - players.len()) players, all from last_eliminated_players.
@@ -43,7 +43,7 @@ pub fn add_players_back_from_last_eliminated(
         .filter(|p| ids_to_add.contains(&p.id))
         .collect();
     for p in &mut to_add {
-        p.eliminated = false;
+        p.reinstate();
     }
     tournament.players.append(&mut to_add);
 
@@ -58,13 +58,36 @@ pub fn add_players_back_from_last_eliminated(
     Ok(())
 }
 
+/// FinalSelection without a manual pick: the `needed` players from `last_eliminated_players` with
+/// the best records rejoin (most wins, then fewest losses, then fewest sit-outs, then earliest
+/// joined) through [`add_players_back_from_last_eliminated`], so the state becomes SemiFinals.
+pub fn auto_fill_finalists(tournament: &mut Tournament) -> Result<(), TournamentError> {
+    if tournament.state != TournamentState::FinalSelection {
+        return Err(TournamentError::InvalidState);
+    }
+    let needed = tournament
+        .players_required_for_semi()
+        .saturating_sub(tournament.players.len());
+    if needed == 0 {
+        return Err(TournamentError::InvalidState);
+    }
+    let available = tournament.last_eliminated_players.len();
+    if available < needed {
+        return Err(TournamentError::NotEnoughEliminatedPlayers { needed, available });
+    }
+    let mut ranked: Vec<&Player> = tournament.last_eliminated_players.iter().collect();
+    ranked.sort_by_key(|p| record_rank(p));
+    let ids: Vec<PlayerId> = ranked.iter().take(needed).map(|p| p.id).collect();
+    add_players_back_from_last_eliminated(tournament, &ids)
+}
+
 /// Transition from FinalSelection to SemiFinals when exactly 4 (1v1) or 8 (2v2) players (no add-back needed).
+/// With `FinalsFormat::SmallField`, smaller fields down to finals size + 1 may also start.
 pub fn start_semi_finals(tournament: &mut Tournament) -> Result<(), TournamentError> {
     if tournament.state != TournamentState::FinalSelection {
         return Err(TournamentError::InvalidState);
     }
-    let required = tournament.players_required_for_semi();
-    if tournament.players.len() != required {
+    if !tournament.can_start_semi_finals() {
         return Err(TournamentError::InvalidState);
     }
     tournament.state = TournamentState::SemiFinals;
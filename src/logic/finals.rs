@@ -1,64 +1,280 @@
 //! Final rounds: semi-finals and finals (single-elimination bracket). Tournament ends after finals with two winners.
 
+use crate::logic::group_play::round_seed;
 use crate::models::{
-    GameMatch, MatchId, PlayerId, RoundType, Team, Tournament, TournamentError, TournamentMode,
-    TournamentState,
+    FinalTieResolution, GameMatch, MatchId, MatchOutcome, Player, PlayerId, RoundType, ScoringMode,
+    SeedingStrategy, Team, Tournament, TournamentError, TournamentMode, TournamentState,
 };
+use rand::rngs::StdRng;
 use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use std::cmp::Reverse;
+use std::collections::HashMap;
 
-/// Generate semi-final matches: 4 players (1v1) → 2 matches of 1v1; 8 players (2v2) → 2 matches of 2v2. Seeds randomly.
+/// Shape of the semi-final round for a given field size.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct SemiFinalShape {
+    /// Matches played in the tournament's mode (2v2 in 2v2, 1v1 in 1v1).
+    pub full_matches: usize,
+    /// Extra 1v1 matches in 2v2 small fields (each sends a single player to the finals).
+    pub single_matches: usize,
+    /// Players who skip the semi-finals and go straight to the finals.
+    pub byes: usize,
+}
+
+/// Semi-final bracket for `n` players, or `None` if `n` is not above the finals size and at most the semi size.
+///
+/// - 2v2: 8 → two 2v2; 7 → one 2v2 + one 1v1 + 1 bye; 6 → one 2v2 + 2 byes; 5 → one 1v1 + 3 byes.
+/// - 1v1: 4 → two 1v1; 3 → one 1v1 + 1 bye.
+///
+/// Every shape sends exactly the finals size (4 or 2) players to the finals.
+pub fn semi_final_bracket_shape(mode: TournamentMode, n: usize) -> Option<SemiFinalShape> {
+    let team_size = match mode {
+        TournamentMode::OneVOne => 1,
+        TournamentMode::TwoVTwo => 2,
+    };
+    let finals_size = 2 * team_size;
+    if n <= finals_size || n > 2 * finals_size {
+        return None;
+    }
+    // Each full match removes `team_size` players from the field, each 1v1 removes one.
+    let extra = n - finals_size;
+    let full_matches = extra / team_size;
+    let single_matches = extra % team_size;
+    Some(SemiFinalShape {
+        full_matches,
+        single_matches,
+        byes: n - full_matches * finals_size - single_matches * 2,
+    })
+}
+
+/// Generate semi-final matches: 4 players (1v1) → 2 matches of 1v1; 8 players (2v2) → 2 matches of 2v2.
+/// Seeded per `tournament.seeding` (random by default, see [`seeded_order`] for the snake strategies).
+/// Smaller fields (`FinalsFormat::SmallField`) follow [`semi_final_bracket_shape`]; bye players are
+/// stored in `semi_final_byes`. With `deterministic`, random seeding keeps the player list order.
+/// The shuffle's seed is stored in `last_round_seed` (see [`crate::reproduce_round`]), the
+/// resulting draw order in `finals_seeding`.
 pub fn generate_semi_final_matches(tournament: &mut Tournament) -> Result<(), TournamentError> {
+    let seed = round_seed();
+    generate_semi_finals_with_rng(tournament, &mut StdRng::seed_from_u64(seed))?;
+    tournament.last_round_seed = Some(seed);
+    Ok(())
+}
+
+pub(crate) fn generate_semi_finals_with_rng<R: Rng>(
+    tournament: &mut Tournament,
+    rng: &mut R,
+) -> Result<(), TournamentError> {
     if tournament.state != TournamentState::SemiFinals {
         return Err(TournamentError::InvalidState);
     }
-    let required = tournament.players_required_for_semi();
-    if tournament.players.len() != required {
+    if !tournament.can_start_semi_finals() {
         return Err(TournamentError::InvalidState);
     }
-    let mut players = std::mem::take(&mut tournament.players);
-    players.shuffle(&mut rand::thread_rng());
-    tournament.players = players;
-
-    let p = &tournament.players;
-    let matches: Vec<GameMatch> = match tournament.mode {
-        TournamentMode::OneVOne => vec![
-            GameMatch::new(vec![p[0].id], vec![p[1].id], RoundType::SemiFinals),
-            GameMatch::new(vec![p[2].id], vec![p[3].id], RoundType::SemiFinals),
-        ],
-        TournamentMode::TwoVTwo => vec![
-            GameMatch::new(
-                vec![p[0].id, p[1].id],
-                vec![p[2].id, p[3].id],
-                RoundType::SemiFinals,
+    let shape = semi_final_bracket_shape(tournament.mode, tournament.players.len())
+        .ok_or(TournamentError::InvalidState)?;
+    if !tournament.deterministic {
+        let mut players = std::mem::take(&mut tournament.players);
+        players.shuffle(rng);
+        tournament.players = players;
+    }
+
+    let order = match tournament.seeding {
+        SeedingStrategy::Random => tournament.players.iter().map(|p| p.id).collect(),
+        SeedingStrategy::SnakeByWins | SeedingStrategy::SnakeByRating => {
+            let mut ranked: Vec<&Player> = tournament.players.iter().collect();
+            if tournament.seeding == SeedingStrategy::SnakeByRating {
+                // Option ordering puts unrated (None) below every rating; wins break ties.
+                ranked.sort_by_key(|p| (Reverse(p.rating), Reverse(p.wins), p.losses));
+            } else {
+                ranked.sort_by_key(|p| (Reverse(p.wins), p.losses));
+            }
+            let ranked: Vec<PlayerId> = ranked.iter().map(|p| p.id).collect();
+            seeded_order(&ranked, tournament.mode, shape)
+        }
+    };
+    tournament.finals_seeding = Some(order.clone());
+    let mut ids = order.into_iter();
+    let mut matches: Vec<GameMatch> = Vec::new();
+    for _ in 0..shape.full_matches {
+        let (team_1, team_2) = match tournament.mode {
+            TournamentMode::OneVOne => (
+                ids.by_ref().take(1).collect(),
+                ids.by_ref().take(1).collect(),
             ),
-            GameMatch::new(
-                vec![p[4].id, p[5].id],
-                vec![p[6].id, p[7].id],
-                RoundType::SemiFinals,
+            TournamentMode::TwoVTwo => (
+                ids.by_ref().take(2).collect(),
+                ids.by_ref().take(2).collect(),
             ),
-        ],
-    };
+        };
+        matches.push(GameMatch::new(team_1, team_2, RoundType::SemiFinals));
+    }
+    for _ in 0..shape.single_matches {
+        let team_1 = ids.by_ref().take(1).collect();
+        let team_2 = ids.by_ref().take(1).collect();
+        matches.push(GameMatch::new(team_1, team_2, RoundType::SemiFinals));
+    }
+    tournament.semi_final_byes = ids.collect();
     tournament.matches = matches;
-    tournament.final_match_results.clear();
+    tournament.compute_match_handicaps();
+    tournament.number_new_matches();
+    tournament.prune_stale_results();
     Ok(())
 }
 
-/// Set winner for a final-round match (semi or finals).
+/// Arrange players ranked best-first into the order [`generate_semi_final_matches`] consumes
+/// (full matches, then single matches, then byes).
+///
+/// The top seeds take the byes. The rest are snaked: in 1v1 the best remaining plays the worst;
+/// in 2v2 team `i` gets seeds `i` and `last - i`, and team `i` plays team `teams - 1 - i`.
+fn seeded_order(ranked: &[PlayerId], mode: TournamentMode, shape: SemiFinalShape) -> Vec<PlayerId> {
+    let (byes, rest) = ranked.split_at(shape.byes);
+    let full_len = shape.full_matches * 2 * mode.team_size();
+    let (full, singles) = rest.split_at(full_len);
+
+    let mut order = Vec::with_capacity(ranked.len());
+    match mode {
+        TournamentMode::OneVOne => {
+            for i in 0..shape.full_matches {
+                order.extend([full[i], full[full.len() - 1 - i]]);
+            }
+        }
+        TournamentMode::TwoVTwo => {
+            let teams: Vec<[PlayerId; 2]> = (0..full.len() / 2)
+                .map(|i| [full[i], full[full.len() - 1 - i]])
+                .collect();
+            for i in 0..shape.full_matches {
+                order.extend(teams[i]);
+                order.extend(teams[teams.len() - 1 - i]);
+            }
+        }
+    }
+    for i in 0..shape.single_matches {
+        order.extend([singles[i], singles[singles.len() - 1 - i]]);
+    }
+    order.extend_from_slice(byes);
+    order
+}
+
+/// Set winner for a final-round match (semi or finals). Matches whose round is scored in legs
+/// (see [`Tournament::scoring_mode`]) are series: use [`record_finals_leg`] instead.
 pub fn set_finals_match_winner(
     tournament: &mut Tournament,
     match_id: MatchId,
     team: Team,
 ) -> Result<(), TournamentError> {
-    if !tournament.matches.iter().any(|m| m.id == match_id) {
+    let Some(m) = tournament.matches.iter().find(|m| m.id == match_id) else {
         return Err(TournamentError::InvalidState);
+    };
+    if is_series(tournament, m) {
+        return Err(TournamentError::SeriesMatch(match_id));
     }
     tournament.final_match_results.insert(match_id, team);
+    if tournament.finals_draw == Some(match_id) {
+        tournament.finals_draw = None;
+    }
+    Ok(())
+}
+
+/// Record the current finals match as drawn, replacing any winner set for it (setting a winner
+/// again replaces the draw). On submit the draw is resolved per
+/// [`Tournament::final_tie_resolution`]. Finals only; leg series cannot be drawn.
+pub fn record_drawn_final(
+    tournament: &mut Tournament,
+    match_id: MatchId,
+) -> Result<(), TournamentError> {
+    if tournament.state != TournamentState::Finals {
+        return Err(TournamentError::InvalidState);
+    }
+    let m = tournament
+        .matches
+        .iter()
+        .find(|m| m.id == match_id)
+        .ok_or(TournamentError::MatchNotFound(match_id))?;
+    if is_series(tournament, m) {
+        return Err(TournamentError::SeriesMatch(match_id));
+    }
+    tournament.final_match_results.remove(&match_id);
+    tournament.finals_draw = Some(match_id);
+    Ok(())
+}
+
+/// Pick who plays the pending sudden-death decider: `team_1_player` from the drawn final's first
+/// team, `team_2_player` from its second (by default the first listed player of each). Only while
+/// the decider has no result.
+pub fn set_sudden_death_players(
+    tournament: &mut Tournament,
+    team_1_player: PlayerId,
+    team_2_player: PlayerId,
+) -> Result<(), TournamentError> {
+    let Some(drawn) = &tournament.finals_decider_for else {
+        return Err(TournamentError::InvalidState);
+    };
+    if tournament.state != TournamentState::Finals
+        || tournament.matches.len() != 1
+        || !tournament.final_match_results.is_empty()
+        || tournament.finals_draw.is_some()
+    {
+        return Err(TournamentError::InvalidState);
+    }
+    if !drawn.team_1.contains(&team_1_player) {
+        return Err(TournamentError::PlayerNotFound(team_1_player));
+    }
+    if !drawn.team_2.contains(&team_2_player) {
+        return Err(TournamentError::PlayerNotFound(team_2_player));
+    }
+    let decider = &mut tournament.matches[0];
+    decider.team_1 = vec![team_1_player];
+    decider.team_2 = vec![team_2_player];
+    tournament.compute_match_handicaps();
+    Ok(())
+}
+
+/// Whether a match is a leg series (its round is scored in legs).
+fn is_series(tournament: &Tournament, m: &GameMatch) -> bool {
+    tournament.scoring_mode(m.round) == ScoringMode::Legs
+}
+
+/// Record the winner of the next leg of a semi-final/finals series. Once a team has won the
+/// majority (`legs_to_win`), the series result is set and further legs are rejected.
+pub fn record_finals_leg(
+    tournament: &mut Tournament,
+    match_id: MatchId,
+    team: Team,
+) -> Result<(), TournamentError> {
+    if !matches!(
+        tournament.state,
+        TournamentState::SemiFinals | TournamentState::Finals
+    ) {
+        return Err(TournamentError::InvalidState);
+    }
+    if tournament.final_match_results.contains_key(&match_id) {
+        return Err(TournamentError::InvalidState);
+    }
+    let legs_to_win = tournament.legs_to_win() as usize;
+    let m = tournament
+        .matches
+        .iter_mut()
+        .find(|m| m.id == match_id)
+        .ok_or(TournamentError::MatchNotFound(match_id))?;
+    m.legs.push(team);
+    if m.legs.iter().filter(|&&t| t == team).count() >= legs_to_win {
+        tournament.final_match_results.insert(match_id, team);
+    }
     Ok(())
 }
 
+/// Playoff results as outcomes (for the match history; playoff matches always have a winner).
+pub(crate) fn playoff_outcomes(results: &HashMap<MatchId, Team>) -> HashMap<MatchId, MatchOutcome> {
+    results
+        .iter()
+        .map(|(&id, &team)| (id, team.into()))
+        .collect()
+}
+
 /// Apply win/loss for a single playoff match to player stats.
 /// Takes team ids and winner so we don't hold a reference into tournament while mutating it.
-fn apply_playoff_match_result(
+pub(crate) fn apply_playoff_match_result(
     tournament: &mut Tournament,
     team_1: &[PlayerId],
     team_2: &[PlayerId],
@@ -69,28 +285,31 @@ fn apply_playoff_match_result(
         Team::Two => (team_2, team_1),
     };
     for &pid in loser_ids {
-        tournament
+        let p = tournament
             .get_player_mut_any(pid)
-            .ok_or(TournamentError::PlayerNotFound(pid))?
-            .add_loss();
+            .ok_or(TournamentError::PlayerNotFound(pid))?;
+        p.add_loss();
+        p.record_match_played();
     }
     for &pid in winner_ids {
-        tournament
+        let p = tournament
             .get_player_mut_any(pid)
-            .ok_or(TournamentError::PlayerNotFound(pid))?
-            .add_win();
+            .ok_or(TournamentError::PlayerNotFound(pid))?;
+        p.add_win();
+        p.record_match_played();
     }
     Ok(())
 }
 
-/// Process semi-final results: advance 4 winners to Finals, generate finals match.
+/// Process semi-final results: advance 4 winners (plus any byes) to Finals, generate finals match.
 pub fn process_semi_final_results(tournament: &mut Tournament) -> Result<(), TournamentError> {
     if tournament.state != TournamentState::SemiFinals {
         return Err(TournamentError::InvalidState);
     }
-    if tournament.matches.len() != 2 {
+    if tournament.matches.is_empty() {
         return Err(TournamentError::InvalidState);
     }
+    tournament.validate_match_composition()?;
     for m in &tournament.matches {
         if !tournament.final_match_results.contains_key(&m.id) {
             return Err(TournamentError::IncompleteResults);
@@ -113,9 +332,13 @@ pub fn process_semi_final_results(tournament: &mut Tournament) -> Result<(), Tou
         apply_playoff_match_result(tournament, &team_1, &team_2, w)?;
     }
 
+    let results = playoff_outcomes(&tournament.final_match_results);
+    tournament.record_history(&results);
     tournament.bracket_semi_final_players = Some(tournament.players.clone());
+    let (team_1, team_2) =
+        tournament.finals_teams(&tournament.matches, &tournament.final_match_results);
 
-    let mut winner_ids: Vec<PlayerId> = Vec::new();
+    let mut winner_ids: Vec<PlayerId> = tournament.semi_final_byes.clone();
     for m in &tournament.matches {
         let w = tournament.final_match_results[&m.id];
         let ids = match w {
@@ -124,6 +347,8 @@ pub fn process_semi_final_results(tournament: &mut Tournament) -> Result<(), Tou
         };
         winner_ids.extend(ids.iter().copied());
     }
+    // The byes are in the finals now; the bracket shows them from the finals match.
+    tournament.semi_final_byes.clear();
 
     tournament.bracket_semi_final_matches = Some(tournament.matches.clone());
     tournament.bracket_semi_final_results = Some(tournament.final_match_results.clone());
@@ -137,22 +362,17 @@ pub fn process_semi_final_results(tournament: &mut Tournament) -> Result<(), Tou
         .cloned()
         .collect();
     tournament.players = advancing;
-
-    let p = &tournament.players;
-    let finals_match = match tournament.mode {
-        TournamentMode::OneVOne => GameMatch::new(vec![p[0].id], vec![p[1].id], RoundType::Finals),
-        TournamentMode::TwoVTwo => GameMatch::new(
-            vec![p[0].id, p[1].id],
-            vec![p[2].id, p[3].id],
-            RoundType::Finals,
-        ),
-    };
-    tournament.matches = vec![finals_match];
+    tournament.matches = vec![GameMatch::new(team_1, team_2, RoundType::Finals)];
+    tournament.compute_match_handicaps();
+    tournament.number_new_matches();
+    tournament.prune_stale_results();
     tournament.state = TournamentState::Finals;
     Ok(())
 }
 
-/// Process finals result: tournament completed (two winners from the winning team).
+/// Process finals result: tournament completed (two winners from the winning team). With
+/// `single_champion` in 2v2, the winning team's two players go on to a 1v1 grand final instead.
+/// A drawn final (see [`record_drawn_final`]) is resolved per `final_tie_resolution` instead.
 pub fn process_finals_results(tournament: &mut Tournament) -> Result<(), TournamentError> {
     if tournament.state != TournamentState::Finals {
         return Err(TournamentError::InvalidState);
@@ -160,20 +380,158 @@ pub fn process_finals_results(tournament: &mut Tournament) -> Result<(), Tournam
     if tournament.matches.len() != 1 {
         return Err(TournamentError::InvalidState);
     }
-    let team_1 = tournament.matches[0].team_1.clone();
-    let team_2 = tournament.matches[0].team_2.clone();
+    tournament.validate_match_composition()?;
+    let m = tournament.matches[0].clone();
+    let Some(w) = tournament.final_match_results.get(&m.id).copied() else {
+        if tournament.finals_draw == Some(m.id) {
+            return resolve_drawn_final(tournament, m);
+        }
+        return Err(TournamentError::IncompleteResults);
+    };
+
+    apply_playoff_match_result(tournament, &m.team_1, &m.team_2, w)?;
+    let results = playoff_outcomes(&tournament.final_match_results);
+    tournament.record_history(&results);
+
+    // A sudden-death decider settles the drawn final: its winner's whole team takes the title.
+    let (finals_match, w) = match tournament.finals_decider_for.take() {
+        Some(drawn) => {
+            let decider_winner = match w {
+                Team::One => m.team_1[0],
+                Team::Two => m.team_2[0],
+            };
+            let side = drawn.team_of(decider_winner).unwrap_or(w);
+            (drawn, side)
+        }
+        None => (m, w),
+    };
+    let winners = match w {
+        Team::One => finals_match.team_1.clone(),
+        Team::Two => finals_match.team_2.clone(),
+    };
+    tournament.bracket_finals_match = Some(finals_match);
+    tournament.bracket_finals_result = Some(w);
+    tournament.matches.clear();
+    tournament.final_match_results.clear();
+    if tournament.single_champion && winners.len() == 2 {
+        set_up_grand_final(tournament, &winners);
+        return Ok(());
+    }
+    tournament.complete();
+    Ok(())
+}
+
+/// Resolve the drawn finals match `m`: the draw counts for everyone who played it, then per
+/// `final_tie_resolution` the final is replayed, decided by a 1v1 decider (first listed player of
+/// each team, see [`set_sudden_death_players`]), or shared (completing the tournament with both
+/// teams as champions).
+fn resolve_drawn_final(tournament: &mut Tournament, m: GameMatch) -> Result<(), TournamentError> {
+    for &pid in m.team_1.iter().chain(&m.team_2) {
+        let p = tournament
+            .get_player_mut_any(pid)
+            .ok_or(TournamentError::PlayerNotFound(pid))?;
+        p.add_draw();
+        p.record_match_played();
+    }
+    tournament.record_history(&HashMap::from([(m.id, MatchOutcome::Draw)]));
+    tournament.matches.clear();
+    tournament.final_match_results.clear();
+    tournament.finals_draw = None;
+    // A drawn decider still resolves the original final.
+    let drawn = tournament.finals_decider_for.take().unwrap_or(m);
+    match tournament.final_tie_resolution {
+        FinalTieResolution::Replay => {
+            let replay = GameMatch::new(drawn.team_1, drawn.team_2, RoundType::Finals);
+            set_up_finals_match(tournament, replay);
+        }
+        FinalTieResolution::SuddenDeath => {
+            let decider = GameMatch::new(
+                vec![drawn.team_1[0]],
+                vec![drawn.team_2[0]],
+                RoundType::Finals,
+            );
+            tournament.finals_decider_for = Some(drawn);
+            set_up_finals_match(tournament, decider);
+        }
+        FinalTieResolution::SharedTitle => {
+            tournament.bracket_finals_match = Some(drawn);
+            tournament.bracket_finals_result = None;
+            tournament.shared_title = true;
+            tournament.complete();
+        }
+    }
+    Ok(())
+}
+
+/// Make `m` the current finals match (a replay or sudden-death decider); stays in `Finals`.
+fn set_up_finals_match(tournament: &mut Tournament, m: GameMatch) {
+    tournament.matches = vec![m];
+    tournament.compute_match_handicaps();
+    tournament.number_new_matches();
+    tournament.prune_stale_results();
+}
+
+/// Play a 1v1 grand final between the two players of the winning 2v2 finals team after all, to
+/// crown a single champion. Valid only on a tournament that just completed its finals that way:
+/// `Completed`, a two-player winning finals team, and no grand final played yet. Turns on
+/// `single_champion` and reopens the tournament in `GrandFinals`.
+pub fn start_grand_finals(tournament: &mut Tournament) -> Result<(), TournamentError> {
+    if tournament.state != TournamentState::Completed
+        || tournament.bracket_grand_finals_match.is_some()
+    {
+        return Err(TournamentError::InvalidState);
+    }
+    let winners = match (
+        &tournament.bracket_finals_match,
+        tournament.bracket_finals_result,
+    ) {
+        (Some(m), Some(Team::One)) => m.team_1.clone(),
+        (Some(m), Some(Team::Two)) => m.team_2.clone(),
+        _ => return Err(TournamentError::InvalidState),
+    };
+    if winners.len() != 2 {
+        return Err(TournamentError::InvalidState);
+    }
+    tournament.single_champion = true;
+    tournament.completed_at = None;
+    set_up_grand_final(tournament, &winners);
+    Ok(())
+}
+
+/// The grand final match between the two finals winners; moves to `GrandFinals`.
+fn set_up_grand_final(tournament: &mut Tournament, winners: &[PlayerId]) {
+    tournament.matches = vec![GameMatch::new(
+        vec![winners[0]],
+        vec![winners[1]],
+        RoundType::GrandFinals,
+    )];
+    tournament.compute_match_handicaps();
+    tournament.number_new_matches();
+    tournament.prune_stale_results();
+    tournament.state = TournamentState::GrandFinals;
+}
+
+/// Process the single-champion grand final: the winner is the champion, the tournament completes.
+pub fn process_grand_finals_results(tournament: &mut Tournament) -> Result<(), TournamentError> {
+    if tournament.state != TournamentState::GrandFinals || tournament.matches.len() != 1 {
+        return Err(TournamentError::InvalidState);
+    }
+    tournament.validate_match_composition()?;
+    let m = tournament.matches[0].clone();
     let w = tournament
         .final_match_results
-        .get(&tournament.matches[0].id)
+        .get(&m.id)
         .copied()
         .ok_or(TournamentError::IncompleteResults)?;
 
-    apply_playoff_match_result(tournament, &team_1, &team_2, w)?;
+    apply_playoff_match_result(tournament, &m.team_1, &m.team_2, w)?;
+    let results = playoff_outcomes(&tournament.final_match_results);
+    tournament.record_history(&results);
 
-    tournament.bracket_finals_match = Some(tournament.matches[0].clone());
-    tournament.bracket_finals_result = Some(w);
+    tournament.bracket_grand_finals_match = Some(m);
+    tournament.bracket_grand_finals_result = Some(w);
     tournament.matches.clear();
     tournament.final_match_results.clear();
-    tournament.state = TournamentState::Completed;
+    tournament.complete();
     Ok(())
 }
@@ -1,23 +1,100 @@
 //! Group stage: match generation and result processing.
 
+use crate::logic::finals::generate_semi_finals_with_rng;
+use crate::logic::knockout::start_knockout;
 use crate::models::{
-    GameMatch, Player, PlayerId, RoundType, Tournament, TournamentError, TournamentMode,
-    TournamentState,
+    record_rank, EliminationReason, GameMatch, MatchOutcome, Player, PlayerId, RoundSummary,
+    RoundType, Tournament, TournamentError, TournamentFormat, TournamentMode, TournamentState,
 };
 use crate::Team;
+use rand::rngs::StdRng;
 use rand::seq::SliceRandom;
-use rand::Rng;
+use rand::{Rng, SeedableRng};
 
 /// Generate matches for the current group play round (1v1: 2 per match, 2v2: 4 per match).
 ///
 /// 1. Filter to non-eliminated players.
-/// 2. Sort by `internal_times_sat_out` (ascending).
-/// 3. Take excess = len % players_per_round; first `excess` sit out.
+/// 2. Sort by `internal_times_sat_out` (ascending), then prefer players who `played_last_round`
+///    (so they get the rest); the sit-out count always wins, keeping the ≤1 fairness invariant.
+///    Sit-out volunteers (`request_sit_out`) come first, so they take the byes. With
+///    `strict_bye_rotation`, `times_sat_out` comes before everything else, so nobody sits out
+///    twice before every active player has sat out once.
+/// 3. Take excess = len % players_per_round; first `excess` sit out. With
+///    `max_concurrent_matches`, everyone beyond the players those matches need sits out too.
+///    2v2 with `allow_ghost` and three to sit out: they play a match of their own instead, with
+///    the playing player who has sat out least as their fourth (`ghost_player`, in two matches).
 /// 4. Shuffle and form matches: 1v1 chunks of 2, 2v2 chunks of 4.
+/// 5. 2v2 with `balance_teams`: within each chunk, strongest + weakest (by wins) form one team.
+///    With `reshuffle_every` > 1, only every that many rounds forms new teams; in between, last
+///    round's teams whose players both play again stay together and the others are paired up.
+///    The teams are then drawn against each other.
+/// 6. With `shuffle_match_order` (default), shuffle the order of the resulting matches.
+/// 7. With `num_boards`, assign the matches to boards round-robin in that order.
+///
+/// With `deterministic`, the random tiebreak in step 2 and both shuffles are skipped: players are
+/// paired in list order.
+///
+/// The RNG is seeded from a fresh random seed, stored in `last_round_seed` so the round can be
+/// checked later with [`reproduce_round`].
 pub fn generate_group_play_matches(tournament: &mut Tournament) -> Result<(), TournamentError> {
+    let seed = round_seed();
+    generate_round(tournament, &mut StdRng::seed_from_u64(seed), false)?;
+    tournament.last_round_seed = Some(seed);
+    Ok(())
+}
+
+/// A fresh round seed, kept below 2^53 so JavaScript clients read it exactly.
+pub(crate) fn round_seed() -> u64 {
+    rand::thread_rng().gen_range(0..1u64 << 53)
+}
+
+/// Regenerate the round that `seed` produced from `tournament` as it was just before that round
+/// (e.g. an exported snapshot), without modifying it. Works in GroupPlay and SemiFinals; returns
+/// the matches (match ids are new, the pairings are the same).
+pub fn reproduce_round(
+    tournament: &Tournament,
+    seed: u64,
+) -> Result<Vec<GameMatch>, TournamentError> {
+    let mut copy = tournament.clone();
+    let mut rng = StdRng::seed_from_u64(seed);
+    match copy.state {
+        TournamentState::GroupPlay => generate_round(&mut copy, &mut rng, false)?,
+        TournamentState::SemiFinals => generate_semi_finals_with_rng(&mut copy, &mut rng)?,
+        _ => return Err(TournamentError::InvalidState),
+    }
+    Ok(copy.matches)
+}
+
+/// Same as [`generate_group_play_matches`] but with a caller-supplied RNG (e.g. seeded for tests).
+/// No seed is recorded.
+pub fn generate_group_play_matches_with_rng<R: Rng>(
+    tournament: &mut Tournament,
+    rng: &mut R,
+) -> Result<(), TournamentError> {
+    generate_round(tournament, rng, false)?;
+    tournament.last_round_seed = None;
+    Ok(())
+}
+
+/// Generate a practice (warm-up) round: same pairing as a group play round, but matches are
+/// flagged `practice`, sit-outs are not counted, and submitting it only records history
+/// (no wins/losses, eliminations, or state change).
+pub fn generate_practice_round(tournament: &mut Tournament) -> Result<(), TournamentError> {
+    generate_round(tournament, &mut rand::thread_rng(), true)?;
+    tournament.last_round_seed = None;
+    Ok(())
+}
+
+fn generate_round<R: Rng>(
+    tournament: &mut Tournament,
+    rng: &mut R,
+    practice: bool,
+) -> Result<(), TournamentError> {
     if tournament.state != TournamentState::GroupPlay {
         return Err(TournamentError::InvalidState);
     }
+    // Regenerating an unplayed round: its byes must not count twice.
+    tournament.undo_last_bye_assignment();
 
     let (min_players, chunk_size, excess_mod) = match tournament.mode {
         TournamentMode::OneVOne => (2, 2, 2),
@@ -35,35 +112,89 @@ pub fn generate_group_play_matches(tournament: &mut Tournament) -> Result<(), To
         return Err(TournamentError::NotEnoughPlayers);
     }
 
-    let mut rng = rand::thread_rng();
-    let mut with_tiebreak: Vec<(Player, u32)> =
-        available.drain(..).map(|p| (p, rng.gen::<u32>())).collect();
-    with_tiebreak.sort_by_key(|(p, t)| (p.internal_times_sat_out, *t));
+    let deterministic = tournament.deterministic;
+    let mut with_tiebreak: Vec<(Player, u32)> = available
+        .drain(..)
+        .map(|p| (p, if deterministic { 0 } else { rng.gen::<u32>() }))
+        .collect();
+    let volunteers = &tournament.sit_out_requests;
+    let strict = tournament.strict_bye_rotation;
+    with_tiebreak.sort_by_key(|(p, t)| {
+        (
+            if strict { p.times_sat_out } else { 0 },
+            !volunteers.contains(&p.id),
+            p.internal_times_sat_out,
+            !p.played_last_round,
+            *t,
+        )
+    });
     available = with_tiebreak.into_iter().map(|(p, _)| p).collect();
 
     let n = available.len();
-    let excess = n % excess_mod;
+    let mut excess = n % excess_mod;
+    if let Some(cap) = tournament.max_concurrent_matches {
+        excess = excess.max(n.saturating_sub(cap * chunk_size));
+    }
+
+    let ghost_round = tournament.allow_ghost
+        && tournament.mode == TournamentMode::TwoVTwo
+        && !practice
+        && excess == 3
+        && tournament
+            .max_concurrent_matches
+            .is_none_or(|cap| cap * chunk_size > n);
+    let mut ghost_fill: Vec<Player> = Vec::new();
+    if ghost_round {
+        ghost_fill = available.drain(0..excess).collect();
+        excess = 0;
+        if let Some(ghost) = available.iter().min_by_key(|p| p.times_sat_out) {
+            ghost_fill.push(ghost.clone());
+        }
+    }
 
     let mut unused: Vec<Player> = available.drain(0..excess).collect();
-    for p in &mut unused {
-        p.record_sat_out();
+    if !practice {
+        for p in &mut unused {
+            p.record_sat_out();
+        }
     }
 
-    available.shuffle(&mut rng);
+    if !deterministic {
+        available.shuffle(rng);
+    }
 
-    let matches: Vec<GameMatch> = available
-        .chunks_exact(chunk_size)
-        .map(|chunk| {
-            let (team_1, team_2) = match tournament.mode {
-                TournamentMode::OneVOne => (vec![chunk[0].id], vec![chunk[1].id]),
-                TournamentMode::TwoVTwo => (
-                    vec![chunk[0].id, chunk[1].id],
-                    vec![chunk[2].id, chunk[3].id],
-                ),
-            };
-            GameMatch::new(team_1, team_2, RoundType::GroupPlay)
-        })
-        .collect();
+    let keep_teams = tournament.mode == TournamentMode::TwoVTwo
+        && !practice
+        && !tournament
+            .rounds_completed
+            .is_multiple_of(tournament.reshuffle_every.max(1));
+    let chunk_match = |chunk: &[Player]| {
+        let (team_1, team_2) = match tournament.mode {
+            TournamentMode::OneVOne => (vec![chunk[0].id], vec![chunk[1].id]),
+            TournamentMode::TwoVTwo if tournament.balance_teams => balanced_teams(chunk),
+            TournamentMode::TwoVTwo => (
+                vec![chunk[0].id, chunk[1].id],
+                vec![chunk[2].id, chunk[3].id],
+            ),
+        };
+        let mut m = GameMatch::new(team_1, team_2, RoundType::GroupPlay);
+        m.practice = practice;
+        m
+    };
+    let mut matches: Vec<GameMatch> = if keep_teams {
+        kept_team_matches(&tournament.last_round_teams, &available, deterministic, rng)
+    } else {
+        available
+            .chunks_exact(chunk_size)
+            .map(chunk_match)
+            .collect()
+    };
+    if ghost_fill.len() == chunk_size {
+        matches.push(chunk_match(&ghost_fill));
+    }
+    if tournament.shuffle_match_order && !deterministic {
+        matches.shuffle(rng);
+    }
 
     for p in &unused {
         if let Some(t) = tournament.players.iter_mut().find(|t| t.id == p.id) {
@@ -72,44 +203,167 @@ pub fn generate_group_play_matches(tournament: &mut Tournament) -> Result<(), To
         }
     }
 
+    if !practice {
+        tournament.round_sit_out_requests = std::mem::take(&mut tournament.sit_out_requests);
+    }
+    if tournament.mode == TournamentMode::TwoVTwo && !practice {
+        tournament.last_round_teams = matches
+            .iter()
+            .flat_map(|m| [m.team_1.clone(), m.team_2.clone()])
+            .collect();
+    }
     tournament.matches = matches;
+    tournament.ghost_player = ghost_fill.get(3).map(|p| p.id);
+    tournament.compute_match_handicaps();
+    tournament.number_new_matches();
+    tournament.assign_boards();
     tournament.unused_players = unused;
-    tournament.match_results.clear();
+    tournament.prune_stale_results();
 
     Ok(())
 }
 
+/// 2v2 matches that keep `previous` teams whose players are all in `playing`; the remaining
+/// players are paired in order, and the teams are drawn against each other.
+fn kept_team_matches<R: Rng>(
+    previous: &[Vec<PlayerId>],
+    playing: &[Player],
+    deterministic: bool,
+    rng: &mut R,
+) -> Vec<GameMatch> {
+    let ids: Vec<PlayerId> = playing.iter().map(|p| p.id).collect();
+    let mut teams: Vec<Vec<PlayerId>> = Vec::new();
+    for team in previous {
+        // A ghost was in two teams last round; only the first of them is kept.
+        if team.iter().all(|id| ids.contains(id))
+            && !teams.iter().flatten().any(|id| team.contains(id))
+        {
+            teams.push(team.clone());
+        }
+    }
+    let free: Vec<PlayerId> = ids
+        .into_iter()
+        .filter(|id| !teams.iter().any(|team| team.contains(id)))
+        .collect();
+    teams.extend(free.chunks_exact(2).map(<[PlayerId]>::to_vec));
+    if !deterministic {
+        teams.shuffle(rng);
+    }
+    teams
+        .chunks_exact(2)
+        .map(|pair| GameMatch::new(pair[0].clone(), pair[1].clone(), RoundType::GroupPlay))
+        .collect()
+}
+
+/// Split 4 players into two teams with the closest win totals: strongest + weakest vs the middle two.
+fn balanced_teams(chunk: &[Player]) -> (Vec<PlayerId>, Vec<PlayerId>) {
+    let mut by_wins: Vec<&Player> = chunk.iter().collect();
+    by_wins.sort_by_key(|p| std::cmp::Reverse(p.wins));
+    (
+        vec![by_wins[0].id, by_wins[3].id],
+        vec![by_wins[1].id, by_wins[2].id],
+    )
+}
+
 /// Process the current round's match results: apply wins/losses, eliminate if at max losses, update state.
 ///
-/// Uses `tournament.match_results`; all match ids in `tournament.matches` must have a result.
+/// Uses `tournament.match_results`; all match ids in `tournament.matches` must have a result
+/// (walkovers are awarded first, see `Tournament::detect_walkovers`).
 /// After processing: clears `match_results` and `matches`/`unused_players`, and sets state to
-/// `FinalSelection` if ≤8 players remain.
+/// `FinalSelection` if ≤8 players remain (`Redemption` instead when enabled and slots are open). A practice round is only recorded in history.
+/// Once `round_cap` rounds are done, the best records fill the semi-final field and the rest are
+/// eliminated, so the tournament moves on however many players were left.
+/// With `auto_generate_next`, a tournament still in GroupPlay gets its next round right away.
 pub fn process_group_play_results(tournament: &mut Tournament) -> Result<(), TournamentError> {
+    process_group_play_results_summary(tournament).map(|_| ())
+}
+
+/// Same as [`process_group_play_results`], returning what happened in the round. The summary is
+/// also stored in `tournament.last_round_summary` for clients. A round that drops the field below
+/// the semi-final size keeps all of its eliminated players as add-back candidates and says how
+/// many must come back (`needs_add_back`, `add_back_needed`).
+pub fn process_group_play_results_summary(
+    tournament: &mut Tournament,
+) -> Result<RoundSummary, TournamentError> {
     if tournament.state != TournamentState::GroupPlay {
         return Err(TournamentError::InvalidState);
     }
 
-    for m in &tournament.matches {
-        if !tournament.match_results.contains_key(&m.id) {
+    tournament.validate_match_composition()?;
+    // Walkovers fill in missing results: check on a copy so a rejected submit changes nothing.
+    let mut submitted = tournament.clone();
+    submitted.detect_walkovers();
+    for m in &submitted.matches {
+        if !submitted.match_results.contains_key(&m.id) {
             return Err(TournamentError::IncompleteResults);
         }
     }
+    *tournament = submitted;
+
+    if tournament.matches.iter().any(|m| m.practice) {
+        let results = std::mem::take(&mut tournament.match_results);
+        tournament.record_history(&results);
+        let summary = RoundSummary {
+            matches_played: tournament.matches.len(),
+            ..RoundSummary::default()
+        };
+        tournament.matches.clear();
+        tournament.unused_players.clear();
+        tournament.last_round_summary = Some(summary.clone());
+        return Ok(summary);
+    }
 
     tournament.last_eliminated_players.clear();
 
-    let max_losses = tournament.max_losses;
-    let match_data: Vec<(Vec<PlayerId>, Vec<PlayerId>, Team)> = tournament
+    for p in &mut tournament.players {
+        p.played_last_round = false;
+    }
+    for id in tournament
+        .matches
+        .iter()
+        .flat_map(|m| m.team_1.iter().chain(&m.team_2))
+    {
+        if let Some(p) = tournament.players.iter_mut().find(|p| p.id == *id) {
+            p.played_last_round = true;
+        }
+    }
+
+    // Walkovers credit the winners only; the absent team takes no loss.
+    let match_data: Vec<(Vec<PlayerId>, Vec<PlayerId>, MatchOutcome)> = tournament
         .matches
         .iter()
         .map(|m| {
-            let w = tournament.match_results[&m.id];
-            (m.team_1.clone(), m.team_2.clone(), w)
+            let outcome = tournament.match_results[&m.id];
+            match (m.walkover, outcome) {
+                (true, MatchOutcome::Win(Team::One)) => (m.team_1.clone(), Vec::new(), outcome),
+                (true, MatchOutcome::Win(Team::Two)) => (Vec::new(), m.team_2.clone(), outcome),
+                _ => (m.team_1.clone(), m.team_2.clone(), outcome),
+            }
         })
         .collect();
 
-    for (team_1, team_2, winner) in match_data {
-        let eliminated = apply_match_result(tournament, &team_1, &team_2, winner, max_losses)?;
-        tournament.last_eliminated_players.extend(eliminated);
+    for (team_1, team_2, outcome) in &match_data {
+        match *outcome {
+            MatchOutcome::Win(winner) => apply_match_result(tournament, team_1, team_2, winner)?,
+            MatchOutcome::Draw => apply_draw(tournament, team_1, team_2)?,
+        }
+    }
+    // Check everyone who played once the whole round is in: a ghost's second match counts, and a
+    // win or draw that ends someone's grace with `max_losses` already reached puts them out too.
+    let round = tournament.current_round_number();
+    for (team_1, team_2, _) in &match_data {
+        for id in team_1.iter().chain(team_2) {
+            let Some(i) = tournament.players.iter().position(|p| p.id == *id) else {
+                continue;
+            };
+            let p = &tournament.players[i];
+            if !p.eliminated && tournament.reached_max_losses(p) {
+                let p = &mut tournament.players[i];
+                p.eliminate_in_round(EliminationReason::MaxLosses, round);
+                let out = p.clone();
+                tournament.last_eliminated_players.push(out);
+            }
+        }
     }
 
     // Move eliminated into eliminated_players and remove from players
@@ -118,68 +372,138 @@ pub fn process_group_play_results(tournament: &mut Tournament) -> Result<(), Tou
         .extend(tournament.last_eliminated_players.iter().cloned());
     tournament.players.retain(|p| !p.eliminated);
 
+    let results = std::mem::take(&mut tournament.match_results);
+    tournament.record_history(&results);
+
+    let matches_played = tournament.matches.len();
+    tournament.rounds_completed += 1;
     // Clear current round state
     tournament.matches.clear();
+    tournament.ghost_player = None;
     tournament.unused_players.clear();
+    tournament.round_sit_out_requests.clear();
     tournament.match_results.clear();
 
     let threshold = tournament.players_required_for_semi();
-    if tournament.players.len() <= threshold {
-        tournament.state = TournamentState::FinalSelection;
+    let cap_reached = tournament
+        .round_cap
+        .is_some_and(|cap| tournament.rounds_completed >= cap);
+    if tournament.format == TournamentFormat::WithFinals && cap_reached {
+        cut_to_finals_field(tournament, threshold);
+    }
+    let advanced_to_final_selection =
+        tournament.format == TournamentFormat::WithFinals && tournament.players.len() <= threshold;
+    let to_knockout = tournament.format == TournamentFormat::KnockoutAll
+        && (tournament.rounds_completed >= tournament.knockout_after_rounds
+            || tournament.players.len() <= 2);
+    if tournament.group_only_finished() {
+        tournament.complete();
+    }
+    if to_knockout {
+        start_knockout(tournament);
+    }
+    if advanced_to_final_selection {
+        let open_slots = tournament.players.len() < threshold;
+        tournament.state = if tournament.redemption
+            && open_slots
+            && !tournament.last_eliminated_players.is_empty()
+        {
+            TournamentState::Redemption
+        } else {
+            TournamentState::FinalSelection
+        };
+        if tournament.auto_start_semis
+            && tournament.state == TournamentState::FinalSelection
+            && tournament.players.len() == threshold
+        {
+            tournament.state = TournamentState::SemiFinals;
+        }
     }
 
+    let add_back_needed = if tournament.state == TournamentState::FinalSelection {
+        threshold.saturating_sub(tournament.players.len())
+    } else {
+        0
+    };
+    let summary = RoundSummary {
+        matches_played,
+        eliminated: tournament.last_eliminated_players.clone(),
+        advanced_to_final_selection: advanced_to_final_selection || to_knockout,
+        needs_add_back: add_back_needed > 0,
+        add_back_needed,
+    };
+    tournament.last_round_summary = Some(summary.clone());
+    if tournament.auto_generate_next && tournament.state == TournamentState::GroupPlay {
+        generate_group_play_matches(tournament)?;
+    }
+    Ok(summary)
+}
+
+/// Round cap reached: keep the `field` best records (same order as
+/// [`Tournament::projected_finalists`]) and eliminate the rest, who join this round's eliminated
+/// players.
+fn cut_to_finals_field(tournament: &mut Tournament, field: usize) {
+    if tournament.players.len() <= field {
+        return;
+    }
+    let round = tournament.rounds_completed;
+    let mut ranked: Vec<&Player> = tournament.players.iter().collect();
+    ranked.sort_by_key(|p| record_rank(p));
+    let ranked: Vec<PlayerId> = ranked.into_iter().map(|p| p.id).collect();
+    for id in &ranked[field..] {
+        if let Some(p) = tournament.get_player_mut(*id) {
+            p.eliminate_in_round(EliminationReason::RoundCap, round);
+            let cut = p.clone();
+            tournament.last_eliminated_players.push(cut.clone());
+            tournament.eliminated_players.push(cut);
+        }
+    }
+    tournament.players.retain(|p| !p.eliminated);
+}
+
+/// Apply a drawn match: every player gets a draw and a match played, nobody takes a loss. Players
+/// eliminated since the round was generated (e.g. a 2v2 forfeit) are skipped.
+fn apply_draw(
+    tournament: &mut Tournament,
+    team_1: &[PlayerId],
+    team_2: &[PlayerId],
+) -> Result<(), TournamentError> {
+    for &pid in team_1.iter().chain(team_2) {
+        let Some(p) = tournament.get_player_mut(pid) else {
+            continue;
+        };
+        p.add_draw();
+        p.record_match_played();
+    }
     Ok(())
 }
 
-/// Apply a single match result: add wins/losses, mark eliminated if at max losses.
-/// Returns clones of players that were eliminated this match.
+/// Apply a single match result: the winners get a win, the losers a loss, everyone a match
+/// played. Players eliminated since the round was generated are skipped. Eliminations are checked
+/// once the whole round is applied.
 fn apply_match_result(
     tournament: &mut Tournament,
     team_1: &[PlayerId],
     team_2: &[PlayerId],
     winner: Team,
-    max_losses: u32,
-) -> Result<Vec<Player>, TournamentError> {
-    let mut eliminated = Vec::new();
-
-    match winner {
-        Team::One => {
-            for &pid in team_2 {
-                let p = tournament
-                    .get_player_mut(pid)
-                    .ok_or(TournamentError::PlayerNotFound(pid))?;
-                p.add_loss();
-                if p.losses >= max_losses {
-                    p.eliminate();
-                    eliminated.push(p.clone());
-                }
-            }
-            for &pid in team_1 {
-                tournament
-                    .get_player_mut(pid)
-                    .ok_or(TournamentError::PlayerNotFound(pid))?
-                    .add_win();
-            }
-        }
-        Team::Two => {
-            for &pid in team_1 {
-                let p = tournament
-                    .get_player_mut(pid)
-                    .ok_or(TournamentError::PlayerNotFound(pid))?;
-                p.add_loss();
-                if p.losses >= max_losses {
-                    p.eliminate();
-                    eliminated.push(p.clone());
-                }
-            }
-            for &pid in team_2 {
-                tournament
-                    .get_player_mut(pid)
-                    .ok_or(TournamentError::PlayerNotFound(pid))?
-                    .add_win();
-            }
-        }
+) -> Result<(), TournamentError> {
+    let (winners, losers) = match winner {
+        Team::One => (team_1, team_2),
+        Team::Two => (team_2, team_1),
+    };
+    for &pid in losers {
+        let Some(p) = tournament.get_player_mut(pid) else {
+            continue;
+        };
+        p.add_loss();
+        p.record_match_played();
     }
-
-    Ok(eliminated)
+    for &pid in winners {
+        let Some(p) = tournament.get_player_mut(pid) else {
+            continue;
+        };
+        p.add_win();
+        p.record_match_played();
+    }
+    Ok(())
 }
@@ -0,0 +1,152 @@
+//! Knockout-all format: after group play every survivor goes into a single-elimination bracket.
+
+use crate::logic::finals::{apply_playoff_match_result, playoff_outcomes};
+use crate::models::{
+    record_rank, EliminationReason, GameMatch, Player, PlayerId, RoundType, Team, Tournament,
+    TournamentError, TournamentState,
+};
+
+/// One round of a single-elimination bracket (see [`knockout_round`]).
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct KnockoutRound {
+    /// Pairings, each with the better seed first.
+    pub matches: Vec<(PlayerId, PlayerId)>,
+    /// Players who go through to the next round without playing.
+    pub byes: Vec<PlayerId>,
+}
+
+/// Pair a seeded field (best seed first) for one knockout round. The bracket is the next power of
+/// two: the top seeds fill the missing slots with byes and the rest play highest against lowest
+/// (5 players: seeds 1-3 have byes, 4 plays 5; 8 players: 1v8, 2v7, 3v6, 4v5). After the first
+/// round the field is a power of two, so later rounds have no byes. A field of one is a bye.
+pub fn knockout_round(seeds: &[PlayerId]) -> KnockoutRound {
+    if seeds.len() < 2 {
+        return KnockoutRound {
+            matches: Vec::new(),
+            byes: seeds.to_vec(),
+        };
+    }
+    let byes = seeds.len().next_power_of_two() - seeds.len();
+    let (byes, playing) = seeds.split_at(byes);
+    let matches = (0..playing.len() / 2)
+        .map(|i| (playing[i], playing[playing.len() - 1 - i]))
+        .collect();
+    KnockoutRound {
+        matches,
+        byes: byes.to_vec(),
+    }
+}
+
+/// End group play of a knockout-all tournament: seed the survivors by record (wins, then fewest
+/// losses) and move to the knockout.
+pub(crate) fn start_knockout(tournament: &mut Tournament) {
+    let mut ranked: Vec<&Player> = tournament.players.iter().collect();
+    ranked.sort_by_key(|p| record_rank(p));
+    tournament.knockout_seeds = ranked.iter().map(|p| p.id).collect();
+    tournament.knockout_eliminated.clear();
+    next_knockout_step(tournament);
+}
+
+/// After group play or a knockout round: two players left play the finals, one left completes the
+/// tournament, otherwise the knockout continues (the next round is generated separately).
+fn next_knockout_step(tournament: &mut Tournament) {
+    match tournament.players.len() {
+        0 | 1 => tournament.complete(),
+        2 => {
+            let ids = seeded_field(tournament);
+            tournament.matches = vec![GameMatch::new(
+                vec![ids[0]],
+                vec![ids[1]],
+                RoundType::Finals,
+            )];
+            tournament.compute_match_handicaps();
+            tournament.number_new_matches();
+            tournament.prune_stale_results();
+            tournament.state = TournamentState::Finals;
+        }
+        _ => tournament.state = TournamentState::Knockout,
+    }
+}
+
+/// Active players in seed order, re-seeded every round from the seeding at the end of group play.
+fn seeded_field(tournament: &Tournament) -> Vec<PlayerId> {
+    tournament
+        .knockout_seeds
+        .iter()
+        .copied()
+        .filter(|id| tournament.players.iter().any(|p| p.id == *id))
+        .collect()
+}
+
+/// Generate the next knockout round (see [`knockout_round`]); players with a bye have no match.
+pub fn generate_knockout_matches(tournament: &mut Tournament) -> Result<(), TournamentError> {
+    if tournament.state != TournamentState::Knockout || !tournament.matches.is_empty() {
+        return Err(TournamentError::InvalidState);
+    }
+    let round = knockout_round(&seeded_field(tournament));
+    tournament.matches = round
+        .matches
+        .into_iter()
+        .map(|(a, b)| GameMatch::new(vec![a], vec![b], RoundType::Knockout))
+        .collect();
+    tournament.compute_match_handicaps();
+    tournament.number_new_matches();
+    tournament.prune_stale_results();
+    Ok(())
+}
+
+/// Submit a knockout round (winners set with `set_finals_match_winner`): losers are eliminated and
+/// the winners and byes go through, into the finals once two remain.
+pub fn process_knockout_results(tournament: &mut Tournament) -> Result<(), TournamentError> {
+    if tournament.state != TournamentState::Knockout || tournament.matches.is_empty() {
+        return Err(TournamentError::InvalidState);
+    }
+    tournament.validate_match_composition()?;
+    // Walkovers fill in missing results: check on a copy so a rejected submit changes nothing.
+    let mut submitted = tournament.clone();
+    submitted.detect_walkovers();
+    for m in &submitted.matches {
+        if !submitted.final_match_results.contains_key(&m.id) {
+            return Err(TournamentError::IncompleteResults);
+        }
+    }
+    *tournament = submitted;
+
+    let match_data: Vec<_> = tournament
+        .matches
+        .iter()
+        .map(|m| {
+            (
+                m.team_1.clone(),
+                m.team_2.clone(),
+                tournament.final_match_results[&m.id],
+            )
+        })
+        .collect();
+    let mut losers = Vec::new();
+    for (team_1, team_2, w) in match_data {
+        apply_playoff_match_result(tournament, &team_1, &team_2, w)?;
+        losers.extend(match w {
+            Team::One => team_2,
+            Team::Two => team_1,
+        });
+    }
+
+    let results = playoff_outcomes(&tournament.final_match_results);
+    tournament.record_history(&results);
+    tournament.matches.clear();
+    tournament.final_match_results.clear();
+
+    let round = tournament.current_round_number();
+    let (mut out, stay): (Vec<Player>, Vec<Player>) = std::mem::take(&mut tournament.players)
+        .into_iter()
+        .partition(|p| losers.contains(&p.id));
+    tournament.players = stay;
+    for p in &mut out {
+        p.eliminate_in_round(EliminationReason::Knockout, round);
+    }
+    tournament.eliminated_players.extend(out);
+    tournament.knockout_eliminated.push(losers);
+    next_knockout_step(tournament);
+    Ok(())
+}
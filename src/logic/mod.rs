@@ -1,14 +1,37 @@
 //! Tournament business logic: setup, group play, finals, etc.
 
+mod advance;
+mod bracket_svg;
+mod export;
 mod final_selection;
 mod finals;
 mod group_play;
+mod knockout;
+mod redemption;
+mod roster_csv;
 mod setup;
 
-pub use final_selection::{add_players_back_from_last_eliminated, start_semi_finals};
+pub use advance::advance_tournament;
+pub use bracket_svg::bracket_svg;
+pub use export::results_html;
+pub use final_selection::{
+    add_players_back_from_last_eliminated, auto_fill_finalists, start_semi_finals,
+};
 pub use finals::{
-    generate_semi_final_matches, process_finals_results, process_semi_final_results,
-    set_finals_match_winner,
+    generate_semi_final_matches, process_finals_results, process_grand_finals_results,
+    process_semi_final_results, record_drawn_final, record_finals_leg, semi_final_bracket_shape,
+    set_finals_match_winner, set_sudden_death_players, start_grand_finals, SemiFinalShape,
+};
+pub use group_play::{
+    generate_group_play_matches, generate_group_play_matches_with_rng, generate_practice_round,
+    process_group_play_results, process_group_play_results_summary, reproduce_round,
+};
+pub(crate) use knockout::start_knockout;
+pub use knockout::{
+    generate_knockout_matches, knockout_round, process_knockout_results, KnockoutRound,
+};
+pub use redemption::{generate_redemption_matches, process_redemption_results};
+pub use roster_csv::{
+    import_roster_csv, parse_roster_csv, CsvRowError, ParsedRoster, RosterImport,
 };
-pub use group_play::{generate_group_play_matches, process_group_play_results};
-pub use setup::start_tournament;
+pub use setup::{quickstart_tournament, start_tournament, QuickstartReport};
@@ -0,0 +1,146 @@
+//! Redemption round (opt-in): instead of the add-back selection, the last eliminated players play
+//! 1v1 last-chance matches and the winners fill the open semi-final slots.
+
+use crate::models::{
+    GameMatch, MatchOutcome, Player, PlayerId, RoundType, Team, Tournament, TournamentError,
+    TournamentState,
+};
+use rand::seq::SliceRandom;
+use rand::Rng;
+use std::cmp::Reverse;
+
+/// Generate the redemption matches for the `needed` open slots from `last_eliminated_players`:
+///
+/// - At most `needed` candidates: all of them rejoin directly, no matches.
+/// - Up to `2 * needed`: one 1v1 per surplus candidate; the rest (most wins first) get a bye and rejoin now.
+/// - More than that: `needed` 1v1 matches among the candidates with the most wins; the others stay out.
+///
+/// When no matches are needed the round is finished immediately (see [`process_redemption_results`]).
+pub fn generate_redemption_matches(tournament: &mut Tournament) -> Result<(), TournamentError> {
+    if tournament.state != TournamentState::Redemption || !tournament.matches.is_empty() {
+        return Err(TournamentError::InvalidState);
+    }
+    let needed = tournament
+        .players_required_for_semi()
+        .saturating_sub(tournament.players.len());
+
+    let mut rng = rand::thread_rng();
+    let mut candidates: Vec<(PlayerId, u32, u32)> = tournament
+        .last_eliminated_players
+        .iter()
+        .map(|p| {
+            let tiebreak = if tournament.deterministic {
+                0
+            } else {
+                rng.gen::<u32>()
+            };
+            (p.id, p.wins, tiebreak)
+        })
+        .collect();
+    candidates.sort_by_key(|&(_, wins, tiebreak)| (Reverse(wins), tiebreak));
+    let ids: Vec<PlayerId> = candidates.into_iter().map(|(id, _, _)| id).collect();
+
+    let match_count = ids.len().saturating_sub(needed).min(needed);
+    let byes = needed.min(ids.len()) - match_count;
+    let (bye_ids, rest) = ids.split_at(byes);
+    for &id in bye_ids {
+        reinstate(tournament, id);
+    }
+
+    let mut contenders = rest[..2 * match_count].to_vec();
+    if !tournament.deterministic {
+        contenders.shuffle(&mut rng);
+    }
+    tournament.matches = contenders
+        .chunks_exact(2)
+        .map(|pair| GameMatch::new(vec![pair[0]], vec![pair[1]], RoundType::Redemption))
+        .collect();
+    tournament.compute_match_handicaps();
+    tournament.number_new_matches();
+    tournament.prune_stale_results();
+
+    if tournament.matches.is_empty() {
+        finish_redemption(tournament);
+    }
+    Ok(())
+}
+
+/// Process redemption results (winners set via `set_finals_match_winner`): winners rejoin the field,
+/// losers stay eliminated. Then SemiFinals if the field is full, otherwise FinalSelection.
+pub fn process_redemption_results(tournament: &mut Tournament) -> Result<(), TournamentError> {
+    if tournament.state != TournamentState::Redemption || tournament.matches.is_empty() {
+        return Err(TournamentError::InvalidState);
+    }
+    tournament.validate_match_composition()?;
+    for m in &tournament.matches {
+        if !tournament.final_match_results.contains_key(&m.id) {
+            return Err(TournamentError::IncompleteResults);
+        }
+    }
+
+    let outcomes: Vec<(PlayerId, PlayerId)> = tournament
+        .matches
+        .iter()
+        .map(|m| match tournament.final_match_results[&m.id] {
+            Team::One => (m.team_1[0], m.team_2[0]),
+            Team::Two => (m.team_2[0], m.team_1[0]),
+        })
+        .collect();
+    for (winner, loser) in outcomes {
+        update_eliminated(tournament, loser, |p| {
+            p.add_loss();
+            p.record_match_played();
+        });
+        update_eliminated(tournament, winner, |p| {
+            p.add_win();
+            p.record_match_played();
+        });
+        reinstate(tournament, winner);
+    }
+
+    let results = tournament
+        .final_match_results
+        .iter()
+        .map(|(&id, &team)| (id, MatchOutcome::from(team)))
+        .collect();
+    tournament.record_history(&results);
+    tournament.matches.clear();
+    tournament.final_match_results.clear();
+    finish_redemption(tournament);
+    Ok(())
+}
+
+/// Apply `f` to both copies of an eliminated player (`eliminated_players` and `last_eliminated_players`).
+fn update_eliminated(tournament: &mut Tournament, id: PlayerId, f: impl Fn(&mut Player)) {
+    for p in tournament
+        .eliminated_players
+        .iter_mut()
+        .chain(tournament.last_eliminated_players.iter_mut())
+        .filter(|p| p.id == id)
+    {
+        f(p);
+    }
+}
+
+/// Move an eliminated player back into the active field.
+fn reinstate(tournament: &mut Tournament, id: PlayerId) {
+    let Some(idx) = tournament
+        .eliminated_players
+        .iter()
+        .position(|p| p.id == id)
+    else {
+        return;
+    };
+    let mut p = tournament.eliminated_players.remove(idx);
+    p.reinstate();
+    tournament.last_eliminated_players.retain(|x| x.id != id);
+    tournament.players.push(p);
+}
+
+fn finish_redemption(tournament: &mut Tournament) {
+    tournament.state = if tournament.players.len() == tournament.players_required_for_semi() {
+        TournamentState::SemiFinals
+    } else {
+        TournamentState::FinalSelection
+    };
+}
@@ -0,0 +1,123 @@
+//! Roster import: `name[,rating]` rows from a spreadsheet export.
+
+use crate::models::{Tournament, TournamentError, TournamentState};
+
+/// A row of the CSV that could not be read as `name[,rating]`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CsvRowError {
+    /// 1-based line number in the uploaded text.
+    pub line: usize,
+    pub message: String,
+}
+
+/// Parsed roster: the good rows in file order, plus one error per malformed row.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct ParsedRoster {
+    pub rows: Vec<(String, Option<i32>)>,
+    pub errors: Vec<CsvRowError>,
+}
+
+/// Result of [`import_roster_csv`].
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct RosterImport {
+    /// Players added.
+    pub added: usize,
+    /// Rows that could not be parsed.
+    pub malformed: Vec<CsvRowError>,
+    /// Parsed names the tournament rejected (duplicates, too long, roster full, ...).
+    pub rejected: Vec<(String, TournamentError)>,
+}
+
+/// Parse `name[,rating]` rows. Blank lines and a leading `name,...` header row are skipped; fields
+/// may be double-quoted (with `""` for a literal quote) so names can contain commas or line
+/// breaks. A row with an empty name, more than two fields, a non-integer rating or an unterminated
+/// quote is reported as malformed, by the line it starts on, without affecting the other rows.
+pub fn parse_roster_csv(csv: &str) -> ParsedRoster {
+    let mut roster = ParsedRoster::default();
+    let mut reader = csv::ReaderBuilder::new()
+        .has_headers(false)
+        .flexible(true)
+        .from_reader(csv.as_bytes());
+    let mut record = csv::StringRecord::new();
+    loop {
+        let (line, start) = {
+            let pos = reader.position();
+            (pos.line() as usize, pos.byte() as usize)
+        };
+        match reader.read_record(&mut record) {
+            Ok(false) => break,
+            Ok(true) => {}
+            Err(e) => {
+                let line = e.position().map_or(line, |pos| pos.line() as usize);
+                roster.errors.push(CsvRowError {
+                    line,
+                    message: e.to_string(),
+                });
+                continue;
+            }
+        }
+        // Lines before the record that were blank are skipped by the reader.
+        let line = record.position().map_or(line, |pos| pos.line() as usize);
+        let start = record.position().map_or(start, |pos| pos.byte() as usize);
+        let error = |message: &str| CsvRowError {
+            line,
+            message: message.to_string(),
+        };
+        // An unclosed quote runs to the end of the input, so only the last record can have one.
+        let end = reader.position().byte() as usize;
+        let raw = csv.get(start..end).unwrap_or_default();
+        if end >= csv.len() && raw.matches('"').count() % 2 == 1 {
+            roster.errors.push(error("Unterminated quoted field"));
+            continue;
+        }
+        if record.iter().all(|field| field.trim().is_empty()) {
+            continue;
+        }
+        if roster.rows.is_empty()
+            && roster.errors.is_empty()
+            && record[0].trim().eq_ignore_ascii_case("name")
+        {
+            continue;
+        }
+        if record.len() > 2 {
+            roster.errors.push(error("Expected name[,rating]"));
+            continue;
+        }
+        let name = record[0].trim();
+        if name.is_empty() {
+            roster.errors.push(error("Missing name"));
+            continue;
+        }
+        let rating = match record.get(1).map(str::trim) {
+            None | Some("") => None,
+            Some(r) => match r.parse::<i32>() {
+                Ok(rating) => Some(rating),
+                Err(_) => {
+                    roster.errors.push(error("Rating must be a whole number"));
+                    continue;
+                }
+            },
+        };
+        roster.rows.push((name.to_string(), rating));
+    }
+    roster
+}
+
+/// Parse `csv` and add every well-formed row with its rating (Setup only). Malformed rows and
+/// names the tournament rejects are reported; the rest are still added.
+pub fn import_roster_csv(
+    tournament: &mut Tournament,
+    csv: &str,
+) -> Result<RosterImport, TournamentError> {
+    if tournament.state != TournamentState::Setup {
+        return Err(TournamentError::InvalidState);
+    }
+    let parsed = parse_roster_csv(csv);
+    let before = tournament.players.len();
+    let rejected = tournament.add_players_with_ratings(parsed.rows);
+    Ok(RosterImport {
+        added: tournament.players.len() - before,
+        malformed: parsed.errors,
+        rejected,
+    })
+}
@@ -1,20 +1,58 @@
 //! Setup phase: start tournament (transition from Setup to GroupPlay or FinalSelection).
 
-use crate::models::{Tournament, TournamentError, TournamentState};
+use crate::models::{
+    epoch_millis, Tournament, TournamentError, TournamentFormat, TournamentMode, TournamentState,
+};
+
+/// Outcome of [`quickstart_tournament`]: names that were not added, and whether the start worked.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct QuickstartReport {
+    /// Names `add_player` rejected (duplicates, empty, too long, ...) with the reason.
+    pub rejected: Vec<(String, TournamentError)>,
+    /// Result of [`start_tournament`] once all names have been tried.
+    pub started: Result<(), TournamentError>,
+}
+
+/// Add all `names` to a tournament in Setup and start it in one go. Rejected names do not stop the
+/// others being added; if the start fails the tournament is left in Setup with the players added.
+pub fn quickstart_tournament<S: Into<String>>(
+    tournament: &mut Tournament,
+    names: impl IntoIterator<Item = S>,
+) -> QuickstartReport {
+    let rejected = if tournament.state == TournamentState::Setup {
+        tournament.add_players(names)
+    } else {
+        Vec::new()
+    };
+    QuickstartReport {
+        rejected,
+        started: start_tournament(tournament),
+    }
+}
 
 /// Start the tournament: require 4 players (1v1) or 8 (2v2); set state to GroupPlay if above threshold else FinalSelection.
+/// The knockout-all format needs 1v1 (`InvalidConfig` otherwise).
 pub fn start_tournament(tournament: &mut Tournament) -> Result<(), TournamentError> {
     if tournament.state != TournamentState::Setup {
         return Err(TournamentError::InvalidState);
     }
+    if tournament.format == TournamentFormat::KnockoutAll
+        && tournament.mode != TournamentMode::OneVOne
+    {
+        return Err(TournamentError::InvalidConfig("knockout_all needs 1v1"));
+    }
     let required = tournament.players_required_to_start();
     if tournament.players.len() < required {
         return Err(TournamentError::NotEnoughPlayersToStart { required });
     }
-    tournament.state = if tournament.players.len() > required {
+    // Group-only and knockout-all tournaments have no final selection to skip ahead to.
+    tournament.state = if tournament.players.len() > required
+        || tournament.format != TournamentFormat::WithFinals
+    {
         TournamentState::GroupPlay
     } else {
         TournamentState::FinalSelection
     };
+    tournament.started_at = Some(epoch_millis());
     Ok(())
 }
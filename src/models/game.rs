@@ -16,36 +16,197 @@ pub enum Team {
     Two,
 }
 
+/// Group play result for a match: a winning team or (when the tournament allows it) a draw.
+/// Serialized as `"one"`, `"two"` or `"draw"`, so it reads like [`Team`] for decided matches.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
+#[serde(from = "OutcomeRepr", into = "OutcomeRepr")]
+pub enum MatchOutcome {
+    Win(Team),
+    Draw,
+}
+
+impl MatchOutcome {
+    /// Winning team, or `None` for a draw.
+    pub fn winner(self) -> Option<Team> {
+        match self {
+            MatchOutcome::Win(team) => Some(team),
+            MatchOutcome::Draw => None,
+        }
+    }
+}
+
+impl From<Team> for MatchOutcome {
+    fn from(team: Team) -> Self {
+        MatchOutcome::Win(team)
+    }
+}
+
+/// Wire format of [`MatchOutcome`].
+#[derive(Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum OutcomeRepr {
+    One,
+    Two,
+    Draw,
+}
+
+impl From<OutcomeRepr> for MatchOutcome {
+    fn from(repr: OutcomeRepr) -> Self {
+        match repr {
+            OutcomeRepr::One => MatchOutcome::Win(Team::One),
+            OutcomeRepr::Two => MatchOutcome::Win(Team::Two),
+            OutcomeRepr::Draw => MatchOutcome::Draw,
+        }
+    }
+}
+
+impl From<MatchOutcome> for OutcomeRepr {
+    fn from(outcome: MatchOutcome) -> Self {
+        match outcome {
+            MatchOutcome::Win(Team::One) => OutcomeRepr::One,
+            MatchOutcome::Win(Team::Two) => OutcomeRepr::Two,
+            MatchOutcome::Draw => OutcomeRepr::Draw,
+        }
+    }
+}
+
 /// Phase of the tournament this match belongs to.
-#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum RoundType {
     GroupPlay,
+    /// Last-chance 1v1 matches among the last eliminated players (see `Tournament::redemption`).
+    Redemption,
     SemiFinals,
     Finals,
+    /// Single-champion 1v1 between the two members of the winning 2v2 finals team.
+    GrandFinals,
+    /// Single-elimination 1v1 round of the knockout-all format.
+    Knockout,
 }
 
 /// A single match: two teams (2v2 in group/semi/finals).
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 pub struct GameMatch {
     pub id: MatchId,
+    /// Short number for referring to the match out loud: 1, 2, 3, ... in generation order,
+    /// unique within the tournament. 0 until the match is numbered.
+    #[serde(default)]
+    pub match_number: u32,
     /// Team 1 player IDs (2 for 2v2).
     pub team_1: Vec<PlayerId>,
     /// Team 2 player IDs.
     pub team_2: Vec<PlayerId>,
-    /// None if not yet played.
+    /// None if not yet played (or drawn, see `draw`).
     pub winner: Option<Team>,
+    /// Group play match that ended in a draw (only recorded in history).
+    #[serde(default)]
+    pub draw: bool,
     pub round: RoundType,
+    /// Practice match: result is recorded in history but never counts toward stats or elimination.
+    #[serde(default)]
+    pub practice: bool,
+    /// Awarded without play because the other team had no eligible players left.
+    #[serde(default)]
+    pub walkover: bool,
+    /// Physical board (1-based) the match is played on, when the tournament has `num_boards`.
+    #[serde(default)]
+    pub board: Option<u32>,
+    /// Semi-final/finals series: winner of each leg played so far (see `legs_per_final_match`).
+    #[serde(default)]
+    pub legs: Vec<Team>,
+    /// Combined `handicap_spot` of team 1 (display only), computed when the match is generated.
+    #[serde(default)]
+    pub team_1_handicap: i32,
+    /// Combined `handicap_spot` of team 2.
+    #[serde(default)]
+    pub team_2_handicap: i32,
 }
 
 impl GameMatch {
     pub fn new(team_1: Vec<PlayerId>, team_2: Vec<PlayerId>, round: RoundType) -> Self {
         Self {
             id: Uuid::new_v4(),
+            match_number: 0,
             team_1,
             team_2,
             winner: None,
+            draw: false,
             round,
+            practice: false,
+            walkover: false,
+            board: None,
+            legs: Vec::new(),
+            team_1_handicap: 0,
+            team_2_handicap: 0,
+        }
+    }
+
+    /// The team `player` is on, if they are in this match.
+    pub fn team_of(&self, player: PlayerId) -> Option<Team> {
+        if self.team_1.contains(&player) {
+            Some(Team::One)
+        } else if self.team_2.contains(&player) {
+            Some(Team::Two)
+        } else {
+            None
+        }
+    }
+
+    /// Whether `player` won this match; `None` if they are not in it or it has no winner yet
+    /// (unplayed or drawn).
+    pub fn won_by(&self, player: PlayerId) -> Option<bool> {
+        Some(self.team_of(player)? == self.winner?)
+    }
+}
+
+/// A player in a [`GameMatchView`] team.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct TeamMember {
+    pub id: PlayerId,
+    pub name: String,
+}
+
+/// A match for display, with each team's names inline; the stored [`GameMatch`] keeps ids only.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct GameMatchView {
+    pub id: MatchId,
+    pub match_number: u32,
+    pub team_1: Vec<TeamMember>,
+    pub team_2: Vec<TeamMember>,
+    pub winner: Option<Team>,
+    pub draw: bool,
+    pub round: RoundType,
+    pub practice: bool,
+    pub walkover: bool,
+    pub board: Option<u32>,
+    pub legs: Vec<Team>,
+    pub team_1_handicap: i32,
+    pub team_2_handicap: i32,
+}
+
+impl GameMatchView {
+    /// View of `m`, naming each player with `name`.
+    pub fn new(m: &GameMatch, name: impl Fn(PlayerId) -> String) -> Self {
+        let team = |ids: &[PlayerId]| {
+            ids.iter()
+                .map(|&id| TeamMember { id, name: name(id) })
+                .collect()
+        };
+        Self {
+            id: m.id,
+            match_number: m.match_number,
+            team_1: team(&m.team_1),
+            team_2: team(&m.team_2),
+            winner: m.winner,
+            draw: m.draw,
+            round: m.round,
+            practice: m.practice,
+            walkover: m.walkover,
+            board: m.board,
+            legs: m.legs.clone(),
+            team_1_handicap: m.team_1_handicap,
+            team_2_handicap: m.team_2_handicap,
         }
     }
 }
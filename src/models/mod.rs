@@ -4,6 +4,15 @@ mod game;
 mod player;
 mod tournament;
 
-pub use game::{GameMatch, MatchId, RoundType, Team};
-pub use player::{Player, PlayerId, PlayerStats};
-pub use tournament::{Tournament, TournamentError, TournamentId, TournamentMode, TournamentState};
+pub use game::{GameMatch, GameMatchView, MatchId, MatchOutcome, RoundType, Team, TeamMember};
+pub use player::{EliminationReason, Player, PlayerId, PlayerStats, PlayerSummary};
+pub(crate) use tournament::{epoch_millis, record_rank};
+pub use tournament::{
+    AddBackRejection, Counts, CurrentRoundView, EliminatedPlayer, FeasibilityReport,
+    FeasibilityWarning, FinalResult, FinalSelectionInfo, FinalTieResolution, FinalsFormat,
+    ForfeitPolicy, InputLimits, Placement, PlayerLocation, PointsConfig, ProjectedBracket,
+    ProjectedFinal, RoundSummary, ScoringMode, SeedingStrategy, SimResult, TeamStats, Tournament,
+    TournamentAction, TournamentConfig, TournamentError, TournamentFormat, TournamentId,
+    TournamentMode, TournamentState, TournamentSummary, DEFAULT_MAX_AVATAR_URL_LEN,
+    DEFAULT_MAX_NAME_LEN, DEFAULT_MAX_PLAYERS,
+};
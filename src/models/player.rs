@@ -6,22 +6,80 @@ use uuid::Uuid;
 /// Unique identifier for a player (used in matches and lookups).
 pub type PlayerId = Uuid;
 
+/// Why a player was eliminated.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EliminationReason {
+    /// Reached the tournament's max losses.
+    MaxLosses,
+    /// Eliminated by the operator.
+    Manual,
+    /// Forfeited (did not play).
+    Forfeit,
+    /// Lost a knockout match (knockout-all format).
+    Knockout,
+    /// Ranked outside the finals field when group play hit its `round_cap`.
+    RoundCap,
+}
+
 /// Statistics view of a player (for API / display).
 #[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
 pub struct PlayerStats {
     pub losses: u32,
     pub wins: u32,
+    pub draws: u32,
+    /// Completed matches this player took part in (byes/sit-outs excluded).
+    pub matches_played: u32,
     pub times_sat_out: u32,
     pub eliminated_status: bool,
+    /// Losses left before elimination (`max_losses - losses`); 0 once eliminated.
+    #[serde(default)]
+    pub lives_remaining: u32,
+    /// See [`Player::current_streak`].
+    #[serde(default)]
+    pub current_streak: i32,
+    /// See [`Player::longest_streak`].
+    #[serde(default)]
+    pub longest_streak: u32,
 }
 
 impl PlayerStats {
-    pub fn from_player(p: &Player) -> Self {
+    /// Stats of `p` in a tournament where `max_losses` eliminates them.
+    pub fn from_player(p: &Player, max_losses: u32) -> Self {
         Self {
             losses: p.losses,
             wins: p.wins,
+            draws: p.draws,
+            matches_played: p.matches_played,
             times_sat_out: p.times_sat_out,
             eliminated_status: p.eliminated,
+            lives_remaining: if p.eliminated {
+                0
+            } else {
+                max_losses.saturating_sub(p.losses)
+            },
+            current_streak: p.current_streak,
+            longest_streak: p.longest_streak,
+        }
+    }
+}
+
+/// A player's id and name together with their stats (for lists of players in API responses).
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct PlayerSummary {
+    pub id: PlayerId,
+    pub name: String,
+    #[serde(flatten)]
+    pub stats: PlayerStats,
+}
+
+impl PlayerSummary {
+    /// See [`PlayerStats::from_player`] for `max_losses`.
+    pub fn from_player(p: &Player, max_losses: u32) -> Self {
+        Self {
+            id: p.id,
+            name: p.name.clone(),
+            stats: p.stats(max_losses),
         }
     }
 }
@@ -33,12 +91,50 @@ pub struct Player {
     pub name: String,
     pub losses: u32,
     pub wins: u32,
+    /// Drawn matches: group play when the tournament allows draws, or a drawn final.
+    #[serde(default)]
+    pub draws: u32,
+    /// Completed matches played; not derived from wins + losses so draws/byes can differ.
+    #[serde(default)]
+    pub matches_played: u32,
     pub times_sat_out: u32,
     /// Internal counter for sit-out fairness (can go negative when we "owe" a sit-out).
     pub internal_times_sat_out: i32,
     /// Random seed for matchmaking (shuffle).
     pub seed: u32,
     pub eliminated: bool,
+    /// Position in which the player joined the tournament; used for stable display order only.
+    #[serde(default)]
+    pub join_order: u32,
+    /// Set when the player is eliminated; `None` while active.
+    #[serde(default)]
+    pub elimination_reason: Option<EliminationReason>,
+    /// External (club) rating, e.g. ELO; used by `SeedingStrategy::SnakeByRating`.
+    #[serde(default)]
+    pub rating: Option<i32>,
+    /// Starting-points spot per leg (display only, e.g. +40 for a weaker player).
+    #[serde(default)]
+    pub handicap_spot: i32,
+    /// Whether the player was in a match in the last processed group play round (rest preference).
+    #[serde(default)]
+    pub played_last_round: bool,
+    /// Group play round (1-based) in which the player was eliminated; `None` while active.
+    #[serde(default)]
+    pub eliminated_at_round: Option<u32>,
+    /// Consecutive results up to now: +n after n wins in a row, -n after n losses in a row, 0
+    /// after a draw. Byes and sit-outs are not matches and leave the streak as it is; an amended
+    /// result counts as the latest one.
+    #[serde(default)]
+    pub current_streak: i32,
+    /// Most consecutive wins so far.
+    #[serde(default)]
+    pub longest_streak: u32,
+    /// Scoreboard color as `#rgb` or `#rrggbb` (display only).
+    #[serde(default)]
+    pub color: Option<String>,
+    /// Picture shown next to the player's name (display only).
+    #[serde(default)]
+    pub avatar_url: Option<String>,
 }
 
 impl Player {
@@ -50,31 +146,72 @@ impl Player {
             name,
             losses: 0,
             wins: 0,
+            draws: 0,
+            matches_played: 0,
             times_sat_out: 0,
             internal_times_sat_out: 0,
             seed: 0,
             eliminated: false,
+            join_order: 0,
+            elimination_reason: None,
+            played_last_round: false,
+            eliminated_at_round: None,
+            rating: None,
+            handicap_spot: 0,
+            current_streak: 0,
+            longest_streak: 0,
+            color: None,
+            avatar_url: None,
         }
     }
 
-    /// Current stats as a separate struct (for API responses).
-    pub fn stats(&self) -> PlayerStats {
-        PlayerStats::from_player(self)
+    /// Current stats as a separate struct (for API responses), given the tournament's
+    /// `max_losses`.
+    pub fn stats(&self, max_losses: u32) -> PlayerStats {
+        PlayerStats::from_player(self, max_losses)
     }
 
-    /// Record a win for this player.
+    /// Record a win for this player (extends a winning streak, ends a losing one).
     pub fn add_win(&mut self) {
         self.wins += 1;
+        self.current_streak = self.current_streak.max(0) + 1;
+        self.longest_streak = self.longest_streak.max(self.current_streak.unsigned_abs());
     }
 
-    /// Record a loss for this player.
+    /// Record a loss for this player (extends a losing streak, ends a winning one).
     pub fn add_loss(&mut self) {
         self.losses += 1;
+        self.current_streak = self.current_streak.min(0) - 1;
+    }
+
+    /// Record a draw for this player (ends any streak).
+    pub fn add_draw(&mut self) {
+        self.draws += 1;
+        self.current_streak = 0;
     }
 
-    /// Mark the player as eliminated.
-    pub fn eliminate(&mut self) {
+    /// Record that this player took part in a completed match.
+    pub fn record_match_played(&mut self) {
+        self.matches_played += 1;
+    }
+
+    /// Mark the player as eliminated, recording why.
+    pub fn eliminate(&mut self, reason: EliminationReason) {
         self.eliminated = true;
+        self.elimination_reason = Some(reason);
+    }
+
+    /// [`Player::eliminate`], also recording the group play round it happened in.
+    pub fn eliminate_in_round(&mut self, reason: EliminationReason, round: u32) {
+        self.eliminate(reason);
+        self.eliminated_at_round = Some(round);
+    }
+
+    /// Bring an eliminated player back into the tournament (clears the elimination reason and round).
+    pub fn reinstate(&mut self) {
+        self.eliminated = false;
+        self.elimination_reason = None;
+        self.eliminated_at_round = None;
     }
 
     /// Record that this player sat out one round.
@@ -82,4 +219,10 @@ impl Player {
         self.times_sat_out += 1;
         self.internal_times_sat_out += 1;
     }
+
+    /// Take back one [`Player::record_sat_out`] (the round was never played).
+    pub fn undo_sat_out(&mut self) {
+        self.times_sat_out = self.times_sat_out.saturating_sub(1);
+        self.internal_times_sat_out -= 1;
+    }
 }
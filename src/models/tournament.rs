@@ -1,9 +1,10 @@
 //! Tournament and TournamentState.
 
-use crate::models::game::{GameMatch, MatchId, Team};
-use crate::models::player::{Player, PlayerId};
+use crate::models::game::{GameMatch, GameMatchView, MatchId, MatchOutcome, RoundType, Team};
+use crate::models::player::{EliminationReason, Player, PlayerId, PlayerSummary};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 use uuid::Uuid;
 
 /// Errors that can occur during tournament operations.
@@ -27,6 +28,46 @@ pub enum TournamentError {
     WrongNumberOfPlayers { needed: usize, selected: usize },
     /// A selected player is not in the last eliminated list.
     PlayerNotInLastEliminated(PlayerId),
+    /// A selected player is not in the eliminated list.
+    PlayerNotEliminated(PlayerId),
+    /// Even all eliminated players cannot bring the field up to the semi-final size.
+    NotEnoughEliminatedPlayers { needed: usize, available: usize },
+    /// No match with this id in the current round.
+    MatchNotFound(MatchId),
+    /// No match with this number (see `GameMatch::match_number`).
+    MatchNumberNotFound(u32),
+    /// The match has no recorded result to void.
+    NoResultRecorded(MatchId),
+    /// A match's team sizes do not fit the tournament mode (e.g. a 1-player team in 2v2).
+    InvalidMatchComposition(MatchId),
+    /// A draw was entered but this tournament does not allow draws.
+    DrawsNotAllowed,
+    /// The tournament is paused; resume it before making changes.
+    Paused,
+    /// The roster already has `InputLimits::max_players` players.
+    TooManyPlayers { max: usize },
+    /// The player name is longer than `InputLimits::max_name_len` characters.
+    NameTooLong { max: usize },
+    /// Legs per final match must be odd (so a series cannot end in a tie).
+    InvalidLegCount(u32),
+    /// This match is played as a series of legs; record legs instead of a winner.
+    SeriesMatch(MatchId),
+    /// The caller's expected version is stale (someone else changed the tournament first).
+    VersionConflict { expected: u64, current: u64 },
+    /// A player color is not a `#rgb` or `#rrggbb` hex color.
+    InvalidColor,
+    /// A player avatar URL is not an `http://` or `https://` URL.
+    InvalidAvatarUrl,
+    /// The avatar URL is longer than `InputLimits::max_avatar_url_len` characters.
+    AvatarUrlTooLong { max: usize },
+    /// `max_losses` must be at least 1 (0 would eliminate on a player's first match).
+    InvalidMaxLosses,
+    /// A new roster order must list every current player exactly once.
+    InvalidPlayerOrder,
+    /// A bulk configuration update has an option that does not fit the rest (see the reason).
+    InvalidConfig(&'static str),
+    /// Imported player stats do not fit a player record (see the reason).
+    InvalidImportedStats(&'static str),
 }
 
 impl std::fmt::Display for TournamentError {
@@ -55,6 +96,66 @@ impl std::fmt::Display for TournamentError {
             TournamentError::PlayerNotInLastEliminated(_) => {
                 write!(f, "Selected player is not in the last eliminated list")
             }
+            TournamentError::PlayerNotEliminated(_) => {
+                write!(f, "Selected player is not in the eliminated list")
+            }
+            TournamentError::NotEnoughEliminatedPlayers { needed, available } => {
+                write!(
+                    f,
+                    "Need {} players to rejoin but only {} have been eliminated",
+                    needed, available
+                )
+            }
+            TournamentError::MatchNotFound(_) => write!(f, "Match not found"),
+            TournamentError::MatchNumberNotFound(number) => {
+                write!(f, "No match with number {}", number)
+            }
+            TournamentError::NoResultRecorded(_) => {
+                write!(f, "No result has been recorded for this match")
+            }
+            TournamentError::InvalidMatchComposition(_) => {
+                write!(f, "Match teams do not match the tournament mode")
+            }
+            TournamentError::DrawsNotAllowed => {
+                write!(f, "Draws are not allowed in this tournament")
+            }
+            TournamentError::Paused => write!(f, "Tournament is paused"),
+            TournamentError::TooManyPlayers { max } => {
+                write!(f, "A tournament can have at most {} players", max)
+            }
+            TournamentError::NameTooLong { max } => {
+                write!(f, "Player name can be at most {} characters", max)
+            }
+            TournamentError::InvalidLegCount(legs) => {
+                write!(f, "Legs per final match must be odd (got {})", legs)
+            }
+            TournamentError::SeriesMatch(_) => {
+                write!(f, "This match is a series; record each leg instead")
+            }
+            TournamentError::VersionConflict { expected, current } => {
+                write!(
+                    f,
+                    "Tournament has changed (expected version {}, current version {})",
+                    expected, current
+                )
+            }
+            TournamentError::InvalidColor => {
+                write!(f, "Color must be a hex color like #1e90ff")
+            }
+            TournamentError::InvalidAvatarUrl => {
+                write!(f, "Avatar URL must start with http:// or https://")
+            }
+            TournamentError::AvatarUrlTooLong { max } => {
+                write!(f, "Avatar URL can be at most {} characters", max)
+            }
+            TournamentError::InvalidMaxLosses => write!(f, "Max losses must be at least 1"),
+            TournamentError::InvalidPlayerOrder => {
+                write!(f, "The new order must list every player exactly once")
+            }
+            TournamentError::InvalidConfig(reason) => write!(f, "Invalid configuration: {reason}"),
+            TournamentError::InvalidImportedStats(reason) => {
+                write!(f, "Invalid imported stats: {reason}")
+            }
         }
     }
 }
@@ -73,6 +174,217 @@ pub enum TournamentMode {
     TwoVTwo,
 }
 
+impl TournamentMode {
+    /// Players required to start in this mode (4 for 1v1, 8 for 2v2).
+    pub fn players_required_to_start(self) -> usize {
+        match self {
+            TournamentMode::OneVOne => 4,
+            TournamentMode::TwoVTwo => 8,
+        }
+    }
+
+    /// Players per team (1 for 1v1, 2 for 2v2).
+    pub fn team_size(self) -> usize {
+        match self {
+            TournamentMode::OneVOne => 1,
+            TournamentMode::TwoVTwo => 2,
+        }
+    }
+}
+
+/// How the semi-finals are formed when final selection ends below the semi-final size.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FinalsFormat {
+    /// Semi-finals need exactly 4 (1v1) or 8 (2v2) players; add players back to reach that.
+    #[default]
+    Standard,
+    /// Fields between the finals size and the semi size play a reduced semi round with byes
+    /// (see `semi_final_bracket_shape`).
+    SmallField,
+}
+
+/// Whether group play leads into semi-finals and finals.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TournamentFormat {
+    /// Group play down to the semi-final size, then semi-finals and finals.
+    #[default]
+    WithFinals,
+    /// Group play only: rounds continue until too few players remain for a match, then the
+    /// tournament completes with everyone ranked by how long they lasted.
+    GroupOnly,
+    /// 1v1 only: group play for `knockout_after_rounds` rounds, then every survivor goes into a
+    /// single-elimination knockout (padded to a power of two with byes for the top seeds).
+    KnockoutAll,
+}
+
+/// What a forfeit does to the player who forfeits.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ForfeitPolicy {
+    /// The player is eliminated, whatever their loss count.
+    #[default]
+    ImmediateElimination,
+    /// The forfeit is a loss; the player is only eliminated if it reaches `max_losses`.
+    CountsAsLoss,
+}
+
+/// How players are placed into the semi-final bracket.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SeedingStrategy {
+    /// Shuffle the field.
+    #[default]
+    Random,
+    /// Rank by in-tournament wins (fewest losses breaks ties); best plays worst, top seeds get byes.
+    SnakeByWins,
+    /// Like `SnakeByWins` but ranked by `Player::rating`; unrated players rank below rated ones,
+    /// ordered by wins.
+    SnakeByRating,
+}
+
+/// How the result of a match is entered.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ScoringMode {
+    /// A bare winner (or draw) decides the match.
+    #[default]
+    Winner,
+    /// The match is a best-of-`legs_per_final_match` series entered leg by leg.
+    Legs,
+}
+
+/// What a drawn final (see [`crate::record_drawn_final`]) leads to.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FinalTieResolution {
+    /// The same final is played again.
+    #[default]
+    Replay,
+    /// A 1v1 decider between one player of each finals team; its winner's team takes the title.
+    /// The first listed player of each team plays unless the organizer picks others (see
+    /// [`crate::set_sudden_death_players`]).
+    SuddenDeath,
+    /// The tournament completes with both finals teams as co-champions.
+    SharedTitle,
+}
+
+/// Points awarded per group play result, used to order the standings (league style: 3/1/0).
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct PointsConfig {
+    pub win: i32,
+    pub draw: i32,
+    pub loss: i32,
+}
+
+impl Default for PointsConfig {
+    /// One point per win: standings ordered by win count.
+    fn default() -> Self {
+        Self {
+            win: 1,
+            draw: 0,
+            loss: 0,
+        }
+    }
+}
+
+impl PointsConfig {
+    /// Points for a record of wins, draws and losses, saturating at the `i32` bounds.
+    pub fn points_for(&self, wins: u32, draws: u32, losses: u32) -> i32 {
+        let term =
+            |per: i32, count: u32| per.saturating_mul(i32::try_from(count).unwrap_or(i32::MAX));
+        term(self.win, wins)
+            .saturating_add(term(self.draw, draws))
+            .saturating_add(term(self.loss, losses))
+    }
+}
+
+/// Outcome of one processed group play round, for a "X and Y were eliminated" notice.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct RoundSummary {
+    /// Matches that were submitted in the round.
+    pub matches_played: usize,
+    /// Players eliminated by this round's results.
+    pub eliminated: Vec<Player>,
+    /// Whether the round ended group play (to FinalSelection, Redemption when enabled, or the
+    /// knockout).
+    pub advanced_to_final_selection: bool,
+    /// The round left fewer active players than the semi-final size and the tournament waits in
+    /// FinalSelection: `add_back_needed` of this round's eliminated players must be added back.
+    #[serde(default)]
+    pub needs_add_back: bool,
+    /// Players to add back before the semi-finals (0 unless `needs_add_back`).
+    #[serde(default)]
+    pub add_back_needed: usize,
+}
+
+/// Default cap on roster size (active + eliminated players).
+pub const DEFAULT_MAX_PLAYERS: usize = 256;
+/// Default cap on player name length, in characters (after trimming).
+pub const DEFAULT_MAX_NAME_LEN: usize = 64;
+/// Default cap on avatar URL length, in characters (after trimming).
+pub const DEFAULT_MAX_AVATAR_URL_LEN: usize = 2048;
+
+/// Every setting of a tournament in one object (see [`Tournament::config`]), for a single settings
+/// screen. Server-side input limits are not part of it.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct TournamentConfig {
+    pub max_losses: u32,
+    pub mode: TournamentMode,
+    pub format: TournamentFormat,
+    pub balance_teams: bool,
+    pub reshuffle_every: u32,
+    pub draws_allowed: bool,
+    pub points_config: PointsConfig,
+    pub redemption: bool,
+    pub single_champion: bool,
+    pub num_boards: Option<u32>,
+    pub max_concurrent_matches: Option<usize>,
+    pub legs_per_final_match: u32,
+    pub final_tie_resolution: FinalTieResolution,
+    pub deterministic: bool,
+    pub shuffle_match_order: bool,
+    pub auto_generate_next: bool,
+    pub strict_bye_rotation: bool,
+    pub knockout_after_rounds: u32,
+    pub grace_matches: u32,
+    pub round_cap: Option<u32>,
+    pub auto_start_semis: bool,
+    pub allow_ghost: bool,
+    pub finals_format: FinalsFormat,
+    pub forfeit_policy: ForfeitPolicy,
+    pub seeding: SeedingStrategy,
+    /// Per-round overrides of how results are entered (see [`Tournament::scoring_mode`]).
+    #[serde(default)]
+    pub scoring_modes: HashMap<RoundType, ScoringMode>,
+    #[serde(default)]
+    pub auto_submit_after_secs: Option<u64>,
+}
+
+/// Caps on client input so a single tournament cannot exhaust the server.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct InputLimits {
+    pub max_players: usize,
+    pub max_name_len: usize,
+    #[serde(default = "default_max_avatar_url_len")]
+    pub max_avatar_url_len: usize,
+}
+
+impl Default for InputLimits {
+    fn default() -> Self {
+        Self {
+            max_players: DEFAULT_MAX_PLAYERS,
+            max_name_len: DEFAULT_MAX_NAME_LEN,
+            max_avatar_url_len: DEFAULT_MAX_AVATAR_URL_LEN,
+        }
+    }
+}
+
+fn default_max_avatar_url_len() -> usize {
+    DEFAULT_MAX_AVATAR_URL_LEN
+}
+
 /// Current phase of the tournament.
 #[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -82,21 +394,374 @@ pub enum TournamentState {
     Setup,
     /// Main phase: >8 players, group play rounds.
     GroupPlay,
+    /// Redemption round (opt-in): the last eliminated players play 1v1 for the open semi-final slots.
+    Redemption,
     /// 8 or fewer players; may need to select from last eliminated to reach 8.
     FinalSelection,
     /// 8 players; semi-finals (2 matches, 2v2).
     SemiFinals,
     /// 4 players; finals (1 match, 2v2). Submitting completes the tournament (two winners).
     Finals,
+    /// Single champion (opt-in, 2v2): the winning finals team's two players play 1v1 for the title.
+    GrandFinals,
+    /// Knockout-all format: single-elimination rounds of all group play survivors, down to the
+    /// last two, who play the finals.
+    Knockout,
     /// Tournament finished; show winners and stats.
     Completed,
 }
 
+/// An operation the tournament currently accepts (see [`Tournament::available_actions`]).
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TournamentAction {
+    /// Add a player (Setup, GroupPlay, FinalSelection).
+    AddPlayer,
+    /// Remove a player (Setup).
+    RemovePlayer,
+    /// Switch between 1v1 and 2v2 (Setup).
+    SetMode,
+    /// Start the tournament: enough players for the mode.
+    Start,
+    /// Generate the next round of the current phase (group play, redemption, semi-finals, knockout).
+    GenerateMatches,
+    /// Record a result for a match of the current round.
+    RecordResult,
+    /// Submit the current round; every match has a result (or will be a walkover).
+    Submit,
+    /// Final selection: add players back from the last eliminated (enough of them to fill the
+    /// field).
+    AddPlayersBack,
+    /// Final selection: the last eliminated cannot fill the field; add players back from everyone
+    /// eliminated.
+    AddBackFromAllEliminated,
+    /// Final selection: the best records among the last eliminated fill the open slots.
+    AutoFillFinalists,
+    /// Final selection: the field has the semi-final size (see `can_start_semi_finals`).
+    StartSemiFinals,
+    /// Group play: a player forfeits (see `forfeit_policy`).
+    Forfeit,
+    /// Group play: swap two players between the current matches before any result is in.
+    SwapPlayers,
+    /// Completed 2v2 without a grand final: the winning pair plays 1v1 for a single champion.
+    StartGrandFinals,
+    /// Back to Setup with the same roster (GroupPlay, FinalSelection).
+    Restart,
+    /// Pause mutations (until completed).
+    Pause,
+    /// Resume a paused tournament.
+    Resume,
+}
+
+/// Which list a player is currently in.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PlayerLocation {
+    /// In `players` and playing (or available for) the current round.
+    Active,
+    /// In `unused_players`: sitting out the current group play round.
+    SittingOut,
+    /// In `eliminated_players`.
+    Eliminated,
+}
+
+/// What the operator must do in FinalSelection: how many players to add back and who is eligible.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct FinalSelectionInfo {
+    /// Players to select to reach the semi-final size (0 when already there).
+    pub needed: usize,
+    /// Players eliminated in the most recent round, eligible to rejoin.
+    pub candidates: Vec<PlayerSummary>,
+    /// Candidates tied on the cutoff line (see [`Tournament::tied_at_cutoff`]); the organizer
+    /// decides between them.
+    #[serde(default)]
+    pub tied_at_cutoff: Vec<PlayerId>,
+}
+
+/// Why a final-selection add-back was rejected, with the ids that may be picked instead.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "reason", rename_all = "snake_case")]
+pub enum AddBackRejection {
+    /// Too few or too many players were selected; exactly `needed` are required.
+    WrongCount {
+        needed: usize,
+        selected: usize,
+        eligible_ids: Vec<PlayerId>,
+    },
+    /// `player_id` was not eliminated in the most recent round.
+    IneligiblePlayer {
+        player_id: PlayerId,
+        eligible_ids: Vec<PlayerId>,
+    },
+}
+
+/// Lightweight poll payload: just the current round's matches, their entered results and names.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct CurrentRoundView {
+    pub state: TournamentState,
+    /// Current matches (empty when no round has been generated).
+    pub matches: Vec<GameMatch>,
+    /// Results entered so far, group play and final rounds alike.
+    pub results: HashMap<MatchId, MatchOutcome>,
+    /// Names of the players in `matches`.
+    pub player_names: HashMap<PlayerId, String>,
+    /// RNG seed the round was generated with (see `Tournament::last_round_seed`).
+    pub seed: Option<u64>,
+    /// `matches` with names inline, when asked for (see [`Tournament::current_round_view_with_names`]).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub match_views: Option<Vec<GameMatchView>>,
+}
+
+/// A final standing: everyone in `players` shares `place` (1 = champion).
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct Placement {
+    pub place: u32,
+    pub players: Vec<PlayerId>,
+}
+
+/// Who won a completed tournament: the first place (both players of a 2v2 winning team unless a
+/// grand final crowned one champion) and the second place.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct FinalResult {
+    pub completed: bool,
+    pub winners: Vec<PlayerSummary>,
+    pub runners_up: Vec<PlayerSummary>,
+}
+
+/// What entering a group play result would do (see [`Tournament::simulate_winner`]).
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct SimResult {
+    /// Players of the match that this result would eliminate.
+    pub eliminated: Vec<PlayerSummary>,
+    /// Whether this is the round's last missing result.
+    pub completes_round: bool,
+    /// State once the round is submitted if it would be complete; otherwise the current state.
+    pub state: TournamentState,
+}
+
+/// Player list sizes for a UI header (see [`Tournament::counts`]).
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct Counts {
+    /// Players still in (sitting-out players included).
+    pub active: usize,
+    /// Players sitting out the current round.
+    pub unused: usize,
+    pub eliminated: usize,
+    /// Eliminated in the last processed round.
+    pub last_eliminated: usize,
+    pub max_losses: u32,
+    pub state: TournamentState,
+}
+
+/// One row of the tournament list (see [`Tournament::summary`]).
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct TournamentSummary {
+    pub id: TournamentId,
+    pub state: TournamentState,
+    pub mode: TournamentMode,
+    pub format: TournamentFormat,
+    /// Players still in (sitting-out players included).
+    pub active: usize,
+    pub version: u64,
+    pub started_at: Option<u64>,
+    /// Whole seconds since a request last touched the tournament.
+    pub last_activity_seconds_ago: u64,
+}
+
+impl TournamentSummary {
+    /// Whether the row passes the list filters (`None` matches anything).
+    pub fn matches_filter(
+        &self,
+        state: Option<TournamentState>,
+        mode: Option<TournamentMode>,
+    ) -> bool {
+        state.is_none_or(|s| s == self.state) && mode.is_none_or(|m| m == self.mode)
+    }
+}
+
+/// A 2v2 pairing's record over the submitted matches it played together.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct TeamStats {
+    /// The two players, sorted by id so a pairing is the same whichever side it played on.
+    pub players: Vec<PlayerId>,
+    /// Names in the same order as `players`.
+    pub names: Vec<String>,
+    pub wins: u32,
+    pub losses: u32,
+    pub draws: u32,
+    pub matches_played: u32,
+}
+
+/// One possible finals match, for the given semi-final winners.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct ProjectedFinal {
+    /// Winning team of every semi-final: recorded results plus the assumed ones.
+    pub semi_winners: HashMap<MatchId, Team>,
+    pub team_1: Vec<PlayerId>,
+    pub team_2: Vec<PlayerId>,
+}
+
+/// "Who plays whom next": the finals pairings still possible given the recorded semi results.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct ProjectedBracket {
+    pub state: TournamentState,
+    /// Semi-finals without a result yet.
+    pub undecided: Vec<MatchId>,
+    /// One entry per combination of undecided semi-final winners.
+    pub finals: Vec<ProjectedFinal>,
+    /// Names of the players in `finals`.
+    pub player_names: HashMap<PlayerId, String>,
+}
+
+/// An eliminated player and when they went out (for a "knocked out in round 4" display).
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct EliminatedPlayer {
+    #[serde(flatten)]
+    pub player: PlayerSummary,
+    /// Group play round of the elimination; `None` for players eliminated before rounds were tracked.
+    pub round_number: Option<u32>,
+    pub reason: Option<EliminationReason>,
+}
+
+/// A configuration that can start but is likely to surprise the operator.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum FeasibilityWarning {
+    /// The roster is not a multiple of the match size, so this many players sit out every round.
+    SittingOutEveryRound { per_round: usize },
+    /// One round of single-loss elimination leaves fewer than the semi-final size; the bracket
+    /// has to be filled back up from the eliminated players.
+    FirstRoundBelowSemiFinalSize {
+        remaining: usize,
+        semi_final_size: usize,
+    },
+    /// The roster is exactly the semi-final size, so there is no group play at all.
+    NoGroupPlay,
+}
+
+impl std::fmt::Display for FeasibilityWarning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FeasibilityWarning::SittingOutEveryRound { per_round } => {
+                write!(f, "{} player(s) will sit out every round", per_round)
+            }
+            FeasibilityWarning::FirstRoundBelowSemiFinalSize {
+                remaining,
+                semi_final_size,
+            } => write!(
+                f,
+                "Only {} players survive the first round but the semi-finals need {}; \
+                 eliminated players will have to be added back",
+                remaining, semi_final_size
+            ),
+            FeasibilityWarning::NoGroupPlay => {
+                write!(
+                    f,
+                    "The roster is exactly the semi-final size; group play is skipped"
+                )
+            }
+        }
+    }
+}
+
+/// Non-blocking warnings about the current roster and settings (see [`Tournament::start_feasibility`]).
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct FeasibilityReport {
+    pub warnings: Vec<FeasibilityWarning>,
+}
+
+impl FeasibilityReport {
+    /// True when there is nothing to warn about.
+    pub fn is_clean(&self) -> bool {
+        self.warnings.is_empty()
+    }
+
+    /// Human-readable warning texts, for the UI.
+    pub fn messages(&self) -> Vec<String> {
+        self.warnings.iter().map(|w| w.to_string()).collect()
+    }
+}
+
+/// Trimmed color and avatar URL (blank means `None`); the color must be `#rgb` or `#rrggbb`, the
+/// avatar an `http(s)://` URL of at most `limits.max_avatar_url_len` characters.
+fn appearance(
+    color: Option<&str>,
+    avatar_url: Option<&str>,
+    limits: &InputLimits,
+) -> Result<(Option<String>, Option<String>), TournamentError> {
+    let color = color.map(str::trim).filter(|c| !c.is_empty());
+    if let Some(c) = color {
+        let hex = c.strip_prefix('#').ok_or(TournamentError::InvalidColor)?;
+        if !matches!(hex.len(), 3 | 6) || !hex.chars().all(|ch| ch.is_ascii_hexdigit()) {
+            return Err(TournamentError::InvalidColor);
+        }
+    }
+    let avatar_url = avatar_url.map(str::trim).filter(|u| !u.is_empty());
+    if let Some(url) = avatar_url {
+        if url.chars().count() > limits.max_avatar_url_len {
+            return Err(TournamentError::AvatarUrlTooLong {
+                max: limits.max_avatar_url_len,
+            });
+        }
+        let scheme = url.split_once("://").map(|(scheme, _)| scheme);
+        if !scheme
+            .is_some_and(|s| s.eq_ignore_ascii_case("http") || s.eq_ignore_ascii_case("https"))
+        {
+            return Err(TournamentError::InvalidAvatarUrl);
+        }
+    }
+    Ok((color.map(str::to_string), avatar_url.map(str::to_string)))
+}
+
+/// Sort key for "best record first": most wins, then fewest losses, then fewest sit-outs, then
+/// earliest joined.
+pub(crate) fn record_rank(p: &Player) -> (std::cmp::Reverse<u32>, u32, u32, u32) {
+    (
+        std::cmp::Reverse(p.wins),
+        p.losses,
+        p.times_sat_out,
+        p.join_order,
+    )
+}
+
+/// Current wall-clock time as milliseconds since the Unix epoch.
+pub(crate) fn epoch_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_millis() as u64)
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_legs_per_final_match() -> u32 {
+    1
+}
+
+fn default_reshuffle_every() -> u32 {
+    1
+}
+
+fn default_knockout_after_rounds() -> u32 {
+    3
+}
+
 /// Full tournament state: players, matches, results, and phase.
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Tournament {
     pub id: TournamentId,
-    /// Active (non-eliminated) players.
+    /// Bumped after every successful mutation; clients send it back to detect lost updates.
+    #[serde(default)]
+    pub version: u64,
+    /// When the tournament was started (epoch millis).
+    #[serde(default)]
+    pub started_at: Option<u64>,
+    /// When the tournament was completed (epoch millis).
+    #[serde(default)]
+    pub completed_at: Option<u64>,
+    /// Active (non-eliminated) players. This internal order is reshuffled by matchmaking and is not
+    /// meant for display; see [`Tournament::public_view`] for the stable display order.
     pub players: Vec<Player>,
     /// Players eliminated so far.
     pub eliminated_players: Vec<Player>,
@@ -110,9 +775,112 @@ pub struct Tournament {
     pub max_losses: u32,
     /// 1v1 or 2v2; determines players needed to start (4 vs 8) and group/playoff format.
     pub mode: TournamentMode,
+    /// 2v2 group play: pair strongest with weakest (by wins) in each match instead of random teams.
+    #[serde(default)]
+    pub balance_teams: bool,
+    /// 2v2 group play forms new teams every this many rounds (1 = every round) and otherwise
+    /// keeps last round's teams together.
+    #[serde(default = "default_reshuffle_every")]
+    pub reshuffle_every: u32,
+    /// Group play: allow drawn matches (no win or loss for either team).
+    #[serde(default)]
+    pub draws_allowed: bool,
+    /// Points per win/draw/loss for `standings()`.
+    #[serde(default)]
+    pub points_config: PointsConfig,
+    /// Fill open semi-final slots with a redemption round among the last eliminated players instead
+    /// of the add-back selection.
+    #[serde(default)]
+    pub redemption: bool,
+    /// 2v2: crown one champion by a 1v1 grand final between the winning finals team's players
+    /// (otherwise both are winners).
+    #[serde(default)]
+    pub single_champion: bool,
+    /// Celebratory name the organizer gives the winning finals team (cosmetic only).
+    #[serde(default)]
+    pub champion_team_name: Option<String>,
+    /// Boards at the venue; group play matches are assigned round-robin to boards 1..=n.
+    #[serde(default)]
+    pub num_boards: Option<u32>,
+    /// At most this many group play matches per round; the extra players sit out (fairly rotated).
+    #[serde(default)]
+    pub max_concurrent_matches: Option<usize>,
+    /// Semi-finals and finals are best-of-N leg series (odd, 1 = a single match).
+    #[serde(default = "default_legs_per_final_match")]
+    pub legs_per_final_match: u32,
+    /// Per-round-type override of how results are entered (see [`Tournament::scoring_mode`]).
+    #[serde(default)]
+    pub scoring_modes: HashMap<RoundType, ScoringMode>,
+    /// What a drawn final leads to (a replay by default).
+    #[serde(default)]
+    pub final_tie_resolution: FinalTieResolution,
+    /// Roster size, name length and avatar URL length caps enforced when adding players.
+    #[serde(default)]
+    pub limits: InputLimits,
+    /// Paused (e.g. for a break): changes are rejected until resumed. Does not change `state`.
+    #[serde(default)]
+    pub paused: bool,
+    /// Kiosk mode: once every current match has a result, the server advances the tournament this
+    /// many seconds after the last change (see [`Tournament::auto_submit_due`]). Off by default.
+    #[serde(default)]
+    pub auto_submit_after_secs: Option<u64>,
+    /// When (epoch ms) the last change left every current match with a result; `None` while
+    /// results are missing. Kept up to date by [`Tournament::apply_versioned`].
+    #[serde(default)]
+    pub results_complete_at: Option<u64>,
+    /// Demo mode: no randomness at all in match generation. Players are paired in list order and
+    /// every random tiebreak is skipped, so sit-out fairness falls back to `internal_times_sat_out`
+    /// (then `played_last_round`) with list order deciding ties. Distinct from seeding an RNG.
+    #[serde(default)]
+    pub deterministic: bool,
+    /// Group play: shuffle the order of a round's matches so display order does not reveal seeding.
+    #[serde(default = "default_true")]
+    pub shuffle_match_order: bool,
+    /// Submitting a group play round generates the next one when the tournament stays in
+    /// GroupPlay (off by default: submit and generate are separate steps).
+    #[serde(default)]
+    pub auto_generate_next: bool,
+    /// Strict bye rotation: nobody sits out again until every active player has sat out as
+    /// often (byes go by `times_sat_out` first, ahead of volunteers and the fairness counter).
+    #[serde(default)]
+    pub strict_bye_rotation: bool,
+    /// Knockout-all format: group play rounds played before the knockout starts.
+    #[serde(default = "default_knockout_after_rounds")]
+    pub knockout_after_rounds: u32,
+    /// Group play: a player is not eliminated in their first this many matches, whatever their
+    /// losses (0 = no grace). Protects late joiners from going out in their very first match.
+    #[serde(default)]
+    pub grace_matches: u32,
+    /// With finals: group play ends after this many rounds even if more than the semi-final size
+    /// remain; the best records (as in `projected_finalists()`) fill the field and the rest go out.
+    #[serde(default)]
+    pub round_cap: Option<u32>,
+    /// A group play round that leaves exactly the semi-final size goes straight to SemiFinals
+    /// instead of waiting in FinalSelection (off by default, since adding players back is common).
+    #[serde(default)]
+    pub auto_start_semis: bool,
+    /// 2v2 group play: when three players would sit out, one player plays a second match with
+    /// them instead (the "ghost", see `ghost_player`). Off by default.
+    #[serde(default)]
+    pub allow_ghost: bool,
+    /// The player playing twice in the current group play round, if any (see `allow_ghost`).
+    #[serde(default)]
+    pub ghost_player: Option<PlayerId>,
+    /// Semi-final format when fewer than the semi-final size remain.
+    #[serde(default)]
+    pub finals_format: FinalsFormat,
+    /// With finals (default) or group play only.
+    #[serde(default)]
+    pub format: TournamentFormat,
+    /// What `forfeit_player` does.
+    #[serde(default)]
+    pub forfeit_policy: ForfeitPolicy,
+    /// How the semi-final bracket is seeded.
+    #[serde(default)]
+    pub seeding: SeedingStrategy,
     pub state: TournamentState,
-    /// Current round: which team won each match (before submit).
-    pub match_results: HashMap<MatchId, Team>,
+    /// Current round: outcome of each match (before submit).
+    pub match_results: HashMap<MatchId, MatchOutcome>,
     /// Final rounds (semi/finals/grand): results per match.
     pub final_match_results: HashMap<MatchId, Team>,
     /// Bracket display: semi-final matches (when in Finals or later).
@@ -123,15 +891,84 @@ pub struct Tournament {
     pub bracket_finals_match: Option<GameMatch>,
     /// Bracket display: finals result.
     pub bracket_finals_result: Option<Team>,
+    /// The current finals match was drawn (see [`crate::record_drawn_final`]); resolved on submit
+    /// per `final_tie_resolution`.
+    #[serde(default)]
+    pub finals_draw: Option<MatchId>,
+    /// Sudden death: the drawn finals match the current 1v1 decider settles. The decider's winner
+    /// takes the title for their whole finals team.
+    #[serde(default)]
+    pub finals_decider_for: Option<GameMatch>,
+    /// The finals were drawn and resolved as a shared title: both teams of `bracket_finals_match`
+    /// are champions (`bracket_finals_result` stays `None`).
+    #[serde(default)]
+    pub shared_title: bool,
+    /// Bracket display: grand finals match (single champion only).
+    #[serde(default)]
+    pub bracket_grand_finals_match: Option<GameMatch>,
+    /// Bracket display: grand finals result.
+    #[serde(default)]
+    pub bracket_grand_finals_result: Option<Team>,
     /// Bracket display: 8 players at semi-finals (for name lookup).
     pub bracket_semi_final_players: Option<Vec<Player>>,
+    /// `join_order` given to the next added player.
+    #[serde(default)]
+    pub next_join_order: u32,
+    /// Every submitted match in chronological order, with `winner` set.
+    #[serde(default)]
+    pub match_history: Vec<GameMatch>,
+    /// Group play rounds processed so far (practice rounds excluded).
+    #[serde(default)]
+    pub rounds_completed: u32,
+    /// Summary of the last processed group play round (`None` before the first one).
+    #[serde(default)]
+    pub last_round_summary: Option<RoundSummary>,
+    /// Small-field semi-finals: players who skip the semi-finals and go straight to the finals.
+    /// Cleared once the finals are set up.
+    #[serde(default)]
+    pub semi_final_byes: Vec<PlayerId>,
+    /// Order the semi-final field was drawn in (after shuffling and seeding): matches take players
+    /// from the front, byes come last. `None` until the semi-finals are generated.
+    #[serde(default)]
+    pub finals_seeding: Option<Vec<PlayerId>>,
+    /// RNG seed of the current group play or semi-final round, for audits (see `reproduce_round`).
+    #[serde(default)]
+    pub last_round_seed: Option<u64>,
+    /// 2v2 teams of the last generated group play round (for `reshuffle_every`).
+    #[serde(default)]
+    pub last_round_teams: Vec<Vec<PlayerId>>,
+    /// Players who asked to sit out the next group play round (see `request_sit_out`).
+    #[serde(default)]
+    pub sit_out_requests: Vec<PlayerId>,
+    /// Sit-out requests the current group play round was generated with; they are put back if the
+    /// round is generated again and dropped once it is submitted.
+    #[serde(default)]
+    pub round_sit_out_requests: Vec<PlayerId>,
+    /// Last `GameMatch::match_number` handed out (0 before the first match).
+    #[serde(default)]
+    pub last_match_number: u32,
+    /// Knockout-all format: the knockout field, best seed first, fixed when group play ends.
+    #[serde(default)]
+    pub knockout_seeds: Vec<PlayerId>,
+    /// Knockout-all format: the players knocked out in each knockout round, in order.
+    #[serde(default)]
+    pub knockout_eliminated: Vec<Vec<PlayerId>>,
 }
 
 impl Tournament {
     /// Create a new tournament in Setup state with no players.
+    ///
+    /// # Panics
+    ///
+    /// If `max_losses` is 0 (a player must be able to lose at least once; see
+    /// [`Tournament::set_max_losses`]).
     pub fn new(max_losses: u32, mode: TournamentMode) -> Self {
+        assert!(max_losses >= 1, "max_losses must be at least 1");
         Self {
             id: Uuid::new_v4(),
+            version: 0,
+            started_at: None,
+            completed_at: None,
             players: Vec::new(),
             eliminated_players: Vec::new(),
             last_eliminated_players: Vec::new(),
@@ -139,6 +976,36 @@ impl Tournament {
             unused_players: Vec::new(),
             max_losses,
             mode,
+            balance_teams: false,
+            reshuffle_every: 1,
+            draws_allowed: false,
+            points_config: PointsConfig::default(),
+            redemption: false,
+            single_champion: false,
+            champion_team_name: None,
+            num_boards: None,
+            max_concurrent_matches: None,
+            legs_per_final_match: 1,
+            scoring_modes: HashMap::new(),
+            final_tie_resolution: FinalTieResolution::Replay,
+            limits: InputLimits::default(),
+            paused: false,
+            auto_submit_after_secs: None,
+            results_complete_at: None,
+            deterministic: false,
+            shuffle_match_order: true,
+            auto_generate_next: false,
+            strict_bye_rotation: false,
+            knockout_after_rounds: 3,
+            grace_matches: 0,
+            round_cap: None,
+            auto_start_semis: false,
+            allow_ghost: false,
+            ghost_player: None,
+            finals_format: FinalsFormat::Standard,
+            format: TournamentFormat::WithFinals,
+            forfeit_policy: ForfeitPolicy::ImmediateElimination,
+            seeding: SeedingStrategy::Random,
             state: TournamentState::Setup,
             match_results: HashMap::new(),
             final_match_results: HashMap::new(),
@@ -146,16 +1013,36 @@ impl Tournament {
             bracket_semi_final_results: None,
             bracket_finals_match: None,
             bracket_finals_result: None,
+            finals_draw: None,
+            finals_decider_for: None,
+            shared_title: false,
+            bracket_grand_finals_match: None,
+            bracket_grand_finals_result: None,
             bracket_semi_final_players: None,
+            semi_final_byes: Vec::new(),
+            finals_seeding: None,
+            last_round_seed: None,
+            last_round_teams: Vec::new(),
+            sit_out_requests: Vec::new(),
+            round_sit_out_requests: Vec::new(),
+            last_match_number: 0,
+            knockout_seeds: Vec::new(),
+            knockout_eliminated: Vec::new(),
+            match_history: Vec::new(),
+            last_round_summary: None,
+            rounds_completed: 0,
+            next_join_order: 0,
         }
     }
 
     /// Players required to start (4 for 1v1, 8 for 2v2).
     pub fn players_required_to_start(&self) -> usize {
-        match self.mode {
-            TournamentMode::OneVOne => 4,
-            TournamentMode::TwoVTwo => 8,
-        }
+        self.mode.players_required_to_start()
+    }
+
+    /// Whether the current roster is large enough to start in `mode` (e.g. before switching mode).
+    pub fn can_start_in_mode(&self, mode: TournamentMode) -> bool {
+        self.players.len() >= mode.players_required_to_start()
     }
 
     /// Players required for semi-finals (4 for 1v1, 8 for 2v2).
@@ -166,19 +1053,237 @@ impl Tournament {
         }
     }
 
+    /// Players in the finals match (2 for 1v1, 4 for 2v2).
+    pub fn players_in_finals(&self) -> usize {
+        match self.mode {
+            TournamentMode::OneVOne => 2,
+            TournamentMode::TwoVTwo => 4,
+        }
+    }
+
+    /// Whether the current active players can go to the semi-finals as they are: exactly the
+    /// semi-final size, or (with `FinalsFormat::SmallField`) between the finals and semi size.
+    pub fn can_start_semi_finals(&self) -> bool {
+        let n = self.players.len();
+        let required = self.players_required_for_semi();
+        match self.finals_format {
+            FinalsFormat::Standard => n == required,
+            FinalsFormat::SmallField => n > self.players_in_finals() && n <= required,
+        }
+    }
+
+    /// Check that every current match has two teams of the mode's team size. Small-field semi-finals
+    /// may also contain 1v1 matches in 2v2 (see `FinalsFormat::SmallField`); redemption matches are always 1v1.
+    pub fn validate_match_composition(&self) -> Result<(), TournamentError> {
+        let team_size = self.mode.team_size();
+        for m in &self.matches {
+            let sizes_ok = |size: usize| m.team_1.len() == size && m.team_2.len() == size;
+            let small_field_single = m.round == RoundType::SemiFinals
+                && self.finals_format == FinalsFormat::SmallField
+                && sizes_ok(1);
+            let one_v_one_round =
+                matches!(m.round, RoundType::Redemption | RoundType::GrandFinals) && sizes_ok(1);
+            let sudden_death =
+                m.round == RoundType::Finals && self.finals_decider_for.is_some() && sizes_ok(1);
+            if !sizes_ok(team_size) && !small_field_single && !one_v_one_round && !sudden_death {
+                return Err(TournamentError::InvalidMatchComposition(m.id));
+            }
+        }
+        Ok(())
+    }
+
+    /// Worst-case number of group play rounds for the current roster and `max_losses`: losses are
+    /// spread as evenly as possible, so players go out one at a time only once nobody can absorb
+    /// another loss. 0 if the roster is already at or below the semi-final size. Planning info only.
+    pub fn max_possible_rounds(&self) -> u32 {
+        let threshold = self.players_required_for_semi();
+        let chunk = 2 * self.mode.team_size();
+        let max_losses = self.max_losses as usize;
+        let mut active = self.players.len();
+        let mut absorbed = 0usize;
+        let mut rounds = 0u32;
+        while active > threshold {
+            let losses = (active - active % chunk) / 2;
+            if losses == 0 {
+                break;
+            }
+            rounds += 1;
+            absorbed += losses;
+            while active > threshold && absorbed > (max_losses - 1) * active {
+                absorbed -= max_losses;
+                active -= 1;
+            }
+        }
+        rounds
+    }
+
+    /// 1-based number of the group play round in progress (or about to be generated).
+    pub fn current_round_number(&self) -> u32 {
+        self.rounds_completed + 1
+    }
+
+    /// Players eliminated by the last processed group play round (empty when nobody went out), for
+    /// animating eliminations. Unlike `last_eliminated_players` this is not consumed by add-back.
+    pub fn recently_eliminated(&self) -> Vec<PlayerSummary> {
+        self.last_round_summary
+            .iter()
+            .flat_map(|s| &s.eliminated)
+            .map(|p| self.player_summary(p))
+            .collect()
+    }
+
+    /// Eliminated players in elimination order, each with the round they went out in.
+    pub fn eliminated_view(&self) -> Vec<EliminatedPlayer> {
+        self.eliminated_players
+            .iter()
+            .map(|p| EliminatedPlayer {
+                player: self.player_summary(p),
+                round_number: p.eliminated_at_round,
+                reason: p.elimination_reason,
+            })
+            .collect()
+    }
+
+    /// Check the roster and settings for configs that start fine but play out awkwardly (permanent
+    /// sit-outs, a field that falls below the semi-final size in one round). Never blocks starting;
+    /// an empty report when the roster is too small to start at all.
+    pub fn start_feasibility(&self) -> FeasibilityReport {
+        let mut warnings = Vec::new();
+        let n = self.players.len();
+        let semi_final_size = self.players_required_for_semi();
+        if n < self.players_required_to_start() {
+            return FeasibilityReport { warnings };
+        }
+        if n == semi_final_size {
+            warnings.push(FeasibilityWarning::NoGroupPlay);
+            return FeasibilityReport { warnings };
+        }
+        let chunk = 2 * self.mode.team_size();
+        let per_round = n % chunk;
+        if per_round != 0 {
+            warnings.push(FeasibilityWarning::SittingOutEveryRound { per_round });
+        }
+        let remaining = n - (n - per_round) / 2;
+        if self.max_losses <= 1 && remaining < semi_final_size {
+            warnings.push(FeasibilityWarning::FirstRoundBelowSemiFinalSize {
+                remaining,
+                semi_final_size,
+            });
+        }
+        FeasibilityReport { warnings }
+    }
+
     /// Create a tournament with initial players (e.g. from setup). Still in Setup until started.
-    pub fn with_players(players: Vec<Player>, max_losses: u32, mode: TournamentMode) -> Self {
+    /// Panics if `max_losses` is 0, like [`Tournament::new`].
+    pub fn with_players(mut players: Vec<Player>, max_losses: u32, mode: TournamentMode) -> Self {
+        for (i, p) in players.iter_mut().enumerate() {
+            p.join_order = i as u32;
+        }
         Self {
+            next_join_order: players.len() as u32,
             players,
             ..Self::new(max_losses, mode)
         }
     }
 
+    /// Copy of the tournament for API responses: `players`, `unused_players` and
+    /// `eliminated_players` sorted by `join_order` so clients see stable lists (each eliminated
+    /// player's `eliminated_at_round` still tells when they went out). The internal order (used for
+    /// sit-out fairness and seeding) is left untouched.
+    pub fn public_view(&self) -> Tournament {
+        let mut view = self.clone();
+        view.players.sort_by_key(|p| p.join_order);
+        view.unused_players.sort_by_key(|p| p.join_order);
+        view.eliminated_players.sort_by_key(|p| p.join_order);
+        view
+    }
+
     /// Mutable reference to an active player by id (searches `players` only).
     pub fn get_player_mut(&mut self, id: PlayerId) -> Option<&mut Player> {
         self.players.iter_mut().find(|p| p.id == id)
     }
 
+    /// Consistency diagnostics: one message per broken invariant (unknown player ids in matches,
+    /// players listed twice, results for matches not in the current round, ...). Empty for a
+    /// healthy tournament.
+    pub fn check_invariants(&self) -> Vec<String> {
+        let mut problems = Vec::new();
+        let mut seen = HashSet::new();
+        for p in &self.players {
+            if !seen.insert(p.id) {
+                problems.push(format!("Player {} is listed twice in players", p.id));
+            }
+        }
+        for p in &self.eliminated_players {
+            if self.players.iter().any(|a| a.id == p.id) {
+                problems.push(format!(
+                    "Player {} is in both players and eliminated_players",
+                    p.id
+                ));
+            } else if !seen.insert(p.id) {
+                problems.push(format!(
+                    "Player {} is listed twice in eliminated_players",
+                    p.id
+                ));
+            }
+        }
+        for p in &self.unused_players {
+            if !self.players.iter().any(|a| a.id == p.id) {
+                problems.push(format!("Sitting-out player {} is not in players", p.id));
+            }
+        }
+        for m in &self.matches {
+            for id in m.team_1.iter().chain(&m.team_2) {
+                if self.find_player_anywhere(*id).is_none() {
+                    problems.push(format!("Match {} has unknown player {}", m.id, id));
+                }
+            }
+        }
+        let current: HashSet<MatchId> = self.matches.iter().map(|m| m.id).collect();
+        for id in self
+            .match_results
+            .keys()
+            .chain(self.final_match_results.keys())
+        {
+            if !current.contains(id) {
+                problems.push(format!(
+                    "Result for match {id} which is not in the current round"
+                ));
+            }
+        }
+        for id in &self.semi_final_byes {
+            if !self.players.iter().any(|p| p.id == *id) {
+                problems.push(format!("Semi-final bye {id} is not in players"));
+            }
+        }
+        problems
+    }
+
+    /// `p` with stats, including lives left under this tournament's `max_losses`.
+    pub fn player_summary(&self, p: &Player) -> PlayerSummary {
+        PlayerSummary::from_player(p, self.max_losses)
+    }
+
+    /// Find a player in any list, with where they are. Sitting-out players are also kept in
+    /// `players`; that entry (with up-to-date stats) is returned.
+    pub fn find_player_anywhere(&self, id: PlayerId) -> Option<(&Player, PlayerLocation)> {
+        if let Some(p) = self.players.iter().find(|p| p.id == id) {
+            let location = if self.unused_players.iter().any(|u| u.id == id) {
+                PlayerLocation::SittingOut
+            } else {
+                PlayerLocation::Active
+            };
+            return Some((p, location));
+        }
+        if let Some(p) = self.unused_players.iter().find(|p| p.id == id) {
+            return Some((p, PlayerLocation::SittingOut));
+        }
+        self.eliminated_players
+            .iter()
+            .find(|p| p.id == id)
+            .map(|p| (p, PlayerLocation::Eliminated))
+    }
+
     /// Look up a player in either `players` or `unused_players` (for group play).
     pub fn get_player_mut_any(&mut self, id: PlayerId) -> Option<&mut Player> {
         self.players
@@ -187,8 +1292,18 @@ impl Tournament {
             .or_else(|| self.unused_players.iter_mut().find(|p| p.id == id))
     }
 
-    /// Add a player (valid in Setup, GroupPlay, or FinalSelection). Names must be unique (case-insensitive).
+    /// Add a player (valid in Setup, GroupPlay, or FinalSelection). Names must be unique (case-insensitive);
+    /// name length and roster size are capped by `limits`.
     pub fn add_player(&mut self, name: impl Into<String>) -> Result<(), TournamentError> {
+        self.add_player_with_rating(name, None)
+    }
+
+    /// Same as [`Tournament::add_player`], also setting the player's external rating.
+    pub fn add_player_with_rating(
+        &mut self,
+        name: impl Into<String>,
+        rating: Option<i32>,
+    ) -> Result<(), TournamentError> {
         use TournamentState::*;
         if !matches!(self.state, Setup | GroupPlay | FinalSelection) {
             return Err(TournamentError::InvalidState);
@@ -198,118 +1313,2141 @@ impl Tournament {
         if name_trimmed.is_empty() {
             return Err(TournamentError::EmptyPlayerName);
         }
+        if name_trimmed.chars().count() > self.limits.max_name_len {
+            return Err(TournamentError::NameTooLong {
+                max: self.limits.max_name_len,
+            });
+        }
+        if self.players.len() + self.eliminated_players.len() >= self.limits.max_players {
+            return Err(TournamentError::TooManyPlayers {
+                max: self.limits.max_players,
+            });
+        }
+        // Eliminated and sitting-out players keep their names too.
         let is_duplicate = self
             .players
             .iter()
+            .chain(&self.unused_players)
+            .chain(&self.eliminated_players)
             .any(|p| p.name.eq_ignore_ascii_case(name_trimmed));
         if is_duplicate {
             return Err(TournamentError::DuplicatePlayerName);
         }
-        self.players.push(Player::new(name_trimmed));
+        let mut player = Player::new(name_trimmed);
+        player.rating = rating;
+        player.join_order = self.next_join_order;
+        self.next_join_order += 1;
+        self.players.push(player);
         Ok(())
     }
 
-    /// Remove a player by id (only valid in Setup).
-    pub fn remove_player(&mut self, player_id: PlayerId) -> Result<(), TournamentError> {
-        if self.state != TournamentState::Setup {
-            return Err(TournamentError::InvalidState);
+    /// Same as [`Tournament::add_player_with_rating`], also setting the player's color and
+    /// avatar (validated before the player is added).
+    pub fn add_player_with_appearance(
+        &mut self,
+        name: impl Into<String>,
+        rating: Option<i32>,
+        color: Option<&str>,
+        avatar_url: Option<&str>,
+    ) -> Result<(), TournamentError> {
+        let (color, avatar_url) = appearance(color, avatar_url, &self.limits)?;
+        self.add_player_with_rating(name, rating)?;
+        if let Some(player) = self.players.last_mut() {
+            player.color = color;
+            player.avatar_url = avatar_url;
         }
-        let idx = self
-            .players
-            .iter()
-            .position(|p| p.id == player_id)
-            .ok_or(TournamentError::PlayerNotFound(player_id))?;
-        self.players.remove(idx);
         Ok(())
     }
 
-    /// Set max losses before elimination (only valid in Setup).
-    pub fn set_max_losses(&mut self, max_losses: u32) -> Result<(), TournamentError> {
-        if self.state != TournamentState::Setup {
+    /// Add a player who already has a record, e.g. to continue a tournament tracked on paper
+    /// (Setup only). Their matches played are `wins + losses` and `sat_out` counts as rounds sat
+    /// out. A player with `max_losses` or more losses is added as already eliminated. Records with
+    /// more than `i32::MAX` matches played or rounds sat out are rejected.
+    pub fn add_player_with_stats(
+        &mut self,
+        name: impl Into<String>,
+        wins: u32,
+        losses: u32,
+        sat_out: u32,
+    ) -> Result<(), TournamentError> {
+        if self.state != TournamentState::Setup {
+            return Err(TournamentError::InvalidState);
+        }
+        let matches_played = wins
+            .checked_add(losses)
+            .filter(|&n| n <= i32::MAX as u32)
+            .ok_or(TournamentError::InvalidImportedStats(
+                "wins + losses is too large",
+            ))?;
+        let internal_sat_out = i32::try_from(sat_out)
+            .map_err(|_| TournamentError::InvalidImportedStats("sat_out is too large"))?;
+        self.add_player(name)?;
+        let mut player = self.players.pop().expect("just added");
+        player.wins = wins;
+        player.losses = losses;
+        player.matches_played = matches_played;
+        player.times_sat_out = sat_out;
+        player.internal_times_sat_out = internal_sat_out;
+        if losses >= self.max_losses {
+            player.eliminate(EliminationReason::MaxLosses);
+            self.eliminated_players.push(player);
+        } else {
+            self.players.push(player);
+        }
+        Ok(())
+    }
+
+    /// Add several players by name, skipping (and returning with the reason) any that `add_player`
+    /// rejects, e.g. duplicates. The others are added in order.
+    pub fn add_players<S: Into<String>>(
+        &mut self,
+        names: impl IntoIterator<Item = S>,
+    ) -> Vec<(String, TournamentError)> {
+        self.add_players_with_ratings(names.into_iter().map(|name| (name, None)))
+    }
+
+    /// [`Tournament::add_players`] with an optional rating per player.
+    pub fn add_players_with_ratings<S: Into<String>>(
+        &mut self,
+        players: impl IntoIterator<Item = (S, Option<i32>)>,
+    ) -> Vec<(String, TournamentError)> {
+        let mut rejected = Vec::new();
+        for (name, rating) in players {
+            let name = name.into();
+            if let Err(e) = self.add_player_with_rating(name.clone(), rating) {
+                rejected.push((name, e));
+            }
+        }
+        rejected
+    }
+
+    /// Remove a player by id (only valid in Setup).
+    pub fn remove_player(&mut self, player_id: PlayerId) -> Result<(), TournamentError> {
+        if self.state != TournamentState::Setup {
+            return Err(TournamentError::InvalidState);
+        }
+        let idx = self
+            .players
+            .iter()
+            .position(|p| p.id == player_id)
+            .ok_or(TournamentError::PlayerNotFound(player_id))?;
+        self.players.remove(idx);
+        Ok(())
+    }
+
+    /// Put the roster in the given order (Setup only). `ordered_ids` must contain every current
+    /// player exactly once; the join order is renumbered so the display follows it.
+    pub fn reorder_players(&mut self, ordered_ids: &[PlayerId]) -> Result<(), TournamentError> {
+        if self.state != TournamentState::Setup {
+            return Err(TournamentError::InvalidState);
+        }
+        let unique: HashSet<PlayerId> = ordered_ids.iter().copied().collect();
+        if ordered_ids.len() != self.players.len()
+            || unique.len() != ordered_ids.len()
+            || self.players.iter().any(|p| !unique.contains(&p.id))
+        {
+            return Err(TournamentError::InvalidPlayerOrder);
+        }
+        let position: HashMap<PlayerId, usize> = ordered_ids
+            .iter()
+            .enumerate()
+            .map(|(i, &id)| (id, i))
+            .collect();
+        self.players.sort_by_key(|p| position[&p.id]);
+        for (i, p) in self.players.iter_mut().enumerate() {
+            p.join_order = i as u32;
+        }
+        self.next_join_order = self.players.len() as u32;
+        Ok(())
+    }
+
+    /// Set max losses before elimination (only valid in Setup; at least 1).
+    pub fn set_max_losses(&mut self, max_losses: u32) -> Result<(), TournamentError> {
+        if self.state != TournamentState::Setup {
+            return Err(TournamentError::InvalidState);
+        }
+        if max_losses == 0 {
+            return Err(TournamentError::InvalidMaxLosses);
+        }
+        self.max_losses = max_losses;
+        Ok(())
+    }
+
+    /// Set mode 1v1 or 2v2 (only valid in Setup). A roster too small for the new mode is allowed
+    /// here (players can still be added); check [`Tournament::can_start_in_mode`] to warn the user.
+    pub fn set_mode(&mut self, mode: TournamentMode) -> Result<(), TournamentError> {
+        if self.state != TournamentState::Setup {
+            return Err(TournamentError::InvalidState);
+        }
+        self.mode = mode;
+        Ok(())
+    }
+
+    /// Enable or disable balanced 2v2 team assignment in group play (only valid in Setup).
+    pub fn set_balance_teams(&mut self, balance_teams: bool) -> Result<(), TournamentError> {
+        if self.state != TournamentState::Setup {
+            return Err(TournamentError::InvalidState);
+        }
+        self.balance_teams = balance_teams;
+        Ok(())
+    }
+
+    /// Set how many rounds 2v2 teams stay together (at least 1; 1 = new teams every round). Takes
+    /// effect from the next generated round.
+    pub fn set_reshuffle_every(&mut self, rounds: u32) -> Result<(), TournamentError> {
+        if self.state == TournamentState::Completed {
+            return Err(TournamentError::InvalidState);
+        }
+        if rounds == 0 {
+            return Err(TournamentError::InvalidConfig(
+                "reshuffle_every must be at least 1",
+            ));
+        }
+        self.reshuffle_every = rounds;
+        Ok(())
+    }
+
+    /// Set how many group play rounds a knockout-all tournament plays before the knockout (at
+    /// least 1; not once the knockout has started).
+    pub fn set_knockout_after_rounds(&mut self, rounds: u32) -> Result<(), TournamentError> {
+        if !matches!(
+            self.state,
+            TournamentState::Setup | TournamentState::GroupPlay
+        ) {
+            return Err(TournamentError::InvalidState);
+        }
+        if rounds == 0 {
+            return Err(TournamentError::InvalidConfig(
+                "knockout_after_rounds must be at least 1",
+            ));
+        }
+        self.knockout_after_rounds = rounds;
+        Ok(())
+    }
+
+    /// Set (or clear with `None`) the number of group play rounds after which the finals field is
+    /// filled by record (at least 1; not once group play is over).
+    pub fn set_round_cap(&mut self, cap: Option<u32>) -> Result<(), TournamentError> {
+        if !matches!(
+            self.state,
+            TournamentState::Setup | TournamentState::GroupPlay
+        ) {
+            return Err(TournamentError::InvalidState);
+        }
+        if cap == Some(0) {
+            return Err(TournamentError::InvalidConfig(
+                "round_cap must be at least 1",
+            ));
+        }
+        self.round_cap = cap;
+        Ok(())
+    }
+
+    /// A player's points under `points_config`.
+    pub fn points(&self, player: &Player) -> i32 {
+        self.points_config
+            .points_for(player.wins, player.draws, player.losses)
+    }
+
+    /// League table: every player (active and eliminated) ordered by points (desc), then wins (desc),
+    /// losses (asc) and join order. Stats accumulate over all meetings, so repeated pairings are summed.
+    pub fn standings(&self) -> Vec<&Player> {
+        use std::cmp::Reverse;
+        let mut all: Vec<&Player> = self
+            .players
+            .iter()
+            .chain(self.eliminated_players.iter())
+            .collect();
+        all.sort_by_key(|p| {
+            (
+                Reverse(self.points(p)),
+                Reverse(p.wins),
+                p.losses,
+                p.join_order,
+            )
+        });
+        all
+    }
+
+    /// Allow or forbid drawn group play matches (only valid in Setup).
+    pub fn set_draws_allowed(&mut self, draws_allowed: bool) -> Result<(), TournamentError> {
+        if self.state != TournamentState::Setup {
+            return Err(TournamentError::InvalidState);
+        }
+        self.draws_allowed = draws_allowed;
+        Ok(())
+    }
+
+    /// Pause the tournament (not once completed). Pausing an already paused tournament is a no-op.
+    pub fn pause(&mut self) -> Result<(), TournamentError> {
+        if self.state == TournamentState::Completed {
+            return Err(TournamentError::InvalidState);
+        }
+        self.paused = true;
+        Ok(())
+    }
+
+    /// Resume a paused tournament (no-op if it is not paused).
+    pub fn resume(&mut self) -> Result<(), TournamentError> {
+        self.paused = false;
+        Ok(())
+    }
+
+    /// `Err(Paused)` while paused; called before every mutation except pause/resume.
+    pub fn ensure_not_paused(&self) -> Result<(), TournamentError> {
+        if self.paused {
+            return Err(TournamentError::Paused);
+        }
+        Ok(())
+    }
+
+    /// Run `f` all-or-nothing: if it returns `Err`, the tournament is rolled back to exactly the
+    /// state before the call, so a multi-step operation failing midway changes nothing.
+    pub fn transaction<T>(
+        &mut self,
+        f: impl FnOnce(&mut Self) -> Result<T, TournamentError>,
+    ) -> Result<T, TournamentError> {
+        let before = self.clone();
+        let result = f(self);
+        if result.is_err() {
+            *self = before;
+        }
+        result
+    }
+
+    /// Run `action` only if `expected` (when given) is the current version, bumping the version
+    /// when it succeeds. The action runs as a [`Tournament::transaction`]: a failed action leaves
+    /// the tournament, including the version, unchanged. A successful action also restarts the
+    /// auto-submit countdown (`results_complete_at`).
+    pub fn apply_versioned(
+        &mut self,
+        expected: Option<u64>,
+        action: impl FnOnce(&mut Self) -> Result<(), TournamentError>,
+    ) -> Result<(), TournamentError> {
+        if let Some(expected) = expected {
+            if expected != self.version {
+                return Err(TournamentError::VersionConflict {
+                    expected,
+                    current: self.version,
+                });
+            }
+        }
+        self.transaction(action)?;
+        self.version += 1;
+        self.results_complete_at = self.all_results_in().then(epoch_millis);
+        Ok(())
+    }
+
+    /// Whether there is a current round and every one of its matches has a result.
+    pub fn all_results_in(&self) -> bool {
+        !self.matches.is_empty()
+            && self.matches.iter().all(|m| {
+                self.match_results.contains_key(&m.id)
+                    || self.final_match_results.contains_key(&m.id)
+                    || self.finals_draw == Some(m.id)
+            })
+    }
+
+    /// Turn kiosk auto-submit on (`Some(seconds)`, at least 1) or off.
+    pub fn set_auto_submit_after(&mut self, secs: Option<u64>) -> Result<(), TournamentError> {
+        if self.state == TournamentState::Completed {
+            return Err(TournamentError::InvalidState);
+        }
+        if secs == Some(0) {
+            return Err(TournamentError::InvalidConfig(
+                "auto_submit_after_secs must be at least 1",
+            ));
+        }
+        self.auto_submit_after_secs = secs;
+        Ok(())
+    }
+
+    /// Whether the kiosk timer should submit the current round at `now` (epoch ms): auto-submit is
+    /// on, the tournament is not paused, every current match has a result, and
+    /// `auto_submit_after_secs` have passed since the last change.
+    pub fn auto_submit_due(&self, now: u64) -> bool {
+        let (Some(after), Some(complete_at)) =
+            (self.auto_submit_after_secs, self.results_complete_at)
+        else {
+            return false;
+        };
+        !self.paused
+            && self.all_results_in()
+            && now.saturating_sub(complete_at) >= after.saturating_mul(1000)
+    }
+
+    /// Record the outcome of a current group play match (GroupPlay only). Draws need `draws_allowed`.
+    pub fn set_match_outcome(
+        &mut self,
+        match_id: MatchId,
+        outcome: MatchOutcome,
+    ) -> Result<(), TournamentError> {
+        if self.state != TournamentState::GroupPlay {
+            return Err(TournamentError::InvalidState);
+        }
+        if !self.matches.iter().any(|m| m.id == match_id) {
+            return Err(TournamentError::MatchNotFound(match_id));
+        }
+        if outcome == MatchOutcome::Draw && !self.draws_allowed {
+            return Err(TournamentError::DrawsNotAllowed);
+        }
+        self.match_results.insert(match_id, outcome);
+        Ok(())
+    }
+
+    /// Swap two players' places in the generated group play round (e.g. to split up a pairing),
+    /// even across matches. Only before any result of the round is entered.
+    pub fn swap_players_in_matches(
+        &mut self,
+        a: PlayerId,
+        b: PlayerId,
+    ) -> Result<(), TournamentError> {
+        if self.state != TournamentState::GroupPlay
+            || self.matches.is_empty()
+            || !self.match_results.is_empty()
+            || self.matches.iter().any(|m| m.winner.is_some())
+            || a == b
+        {
+            return Err(TournamentError::InvalidState);
+        }
+        for id in [a, b] {
+            let playing = self
+                .matches
+                .iter()
+                .any(|m| m.team_1.contains(&id) || m.team_2.contains(&id));
+            if !playing {
+                return Err(TournamentError::PlayerNotFound(id));
+            }
+        }
+        for m in &mut self.matches {
+            for slot in m.team_1.iter_mut().chain(m.team_2.iter_mut()) {
+                if *slot == a {
+                    *slot = b;
+                } else if *slot == b {
+                    *slot = a;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Enable or disable shuffling the order of group play matches (only valid in Setup).
+    pub fn set_shuffle_match_order(&mut self, shuffle: bool) -> Result<(), TournamentError> {
+        if self.state != TournamentState::Setup {
+            return Err(TournamentError::InvalidState);
+        }
+        self.shuffle_match_order = shuffle;
+        Ok(())
+    }
+
+    /// Turn generating the next group play round on submit on or off (until completed).
+    pub fn set_auto_generate_next(&mut self, enabled: bool) -> Result<(), TournamentError> {
+        if self.state == TournamentState::Completed {
+            return Err(TournamentError::InvalidState);
+        }
+        self.auto_generate_next = enabled;
+        Ok(())
+    }
+
+    /// Turn skipping FinalSelection on an exact semi-final field on or off (until group play ends).
+    pub fn set_auto_start_semis(&mut self, enabled: bool) -> Result<(), TournamentError> {
+        if !matches!(
+            self.state,
+            TournamentState::Setup | TournamentState::GroupPlay
+        ) {
+            return Err(TournamentError::InvalidState);
+        }
+        self.auto_start_semis = enabled;
+        Ok(())
+    }
+
+    /// Turn filling an odd 2v2 round with a ghost on or off (until completed).
+    pub fn set_allow_ghost(&mut self, enabled: bool) -> Result<(), TournamentError> {
+        if self.state == TournamentState::Completed {
+            return Err(TournamentError::InvalidState);
+        }
+        self.allow_ghost = enabled;
+        Ok(())
+    }
+
+    /// Set how many matches a player plays before losses can eliminate them (until completed).
+    pub fn set_grace_matches(&mut self, matches: u32) -> Result<(), TournamentError> {
+        if self.state == TournamentState::Completed {
+            return Err(TournamentError::InvalidState);
+        }
+        self.grace_matches = matches;
+        Ok(())
+    }
+
+    /// Turn strict bye rotation on or off (until completed); applies from the next round.
+    pub fn set_strict_bye_rotation(&mut self, strict: bool) -> Result<(), TournamentError> {
+        if self.state == TournamentState::Completed {
+            return Err(TournamentError::InvalidState);
+        }
+        self.strict_bye_rotation = strict;
+        Ok(())
+    }
+
+    /// Turn deterministic (shuffle-free) match generation on or off (Setup only).
+    pub fn set_deterministic(&mut self, deterministic: bool) -> Result<(), TournamentError> {
+        if self.state != TournamentState::Setup {
+            return Err(TournamentError::InvalidState);
+        }
+        self.deterministic = deterministic;
+        Ok(())
+    }
+
+    /// Choose between a tournament with finals and a group-only one (Setup only).
+    pub fn set_format(&mut self, format: TournamentFormat) -> Result<(), TournamentError> {
+        if self.state != TournamentState::Setup {
+            return Err(TournamentError::InvalidState);
+        }
+        self.format = format;
+        Ok(())
+    }
+
+    /// Group-only tournaments end once fewer players remain than one match needs.
+    pub(crate) fn group_only_finished(&self) -> bool {
+        self.format == TournamentFormat::GroupOnly && self.players.len() < 2 * self.mode.team_size()
+    }
+
+    /// Set the finals format (valid until the semi-finals start).
+    pub fn set_finals_format(
+        &mut self,
+        finals_format: FinalsFormat,
+    ) -> Result<(), TournamentError> {
+        use TournamentState::*;
+        if !matches!(self.state, Setup | GroupPlay | FinalSelection) {
+            return Err(TournamentError::InvalidState);
+        }
+        self.finals_format = finals_format;
+        Ok(())
+    }
+
+    /// Turn the single-champion grand final on or off (not once the finals have been played).
+    pub fn set_single_champion(&mut self, single_champion: bool) -> Result<(), TournamentError> {
+        if matches!(
+            self.state,
+            TournamentState::GrandFinals | TournamentState::Completed
+        ) {
+            return Err(TournamentError::InvalidState);
+        }
+        self.single_champion = single_champion;
+        Ok(())
+    }
+
+    /// Name (or with `None` or a blank name, unname) the winning finals team. Only once the finals
+    /// are under way; the name is trimmed and limited like a player name.
+    pub fn set_champion_team_name(&mut self, name: Option<&str>) -> Result<(), TournamentError> {
+        if !matches!(
+            self.state,
+            TournamentState::Finals | TournamentState::GrandFinals | TournamentState::Completed
+        ) {
+            return Err(TournamentError::InvalidState);
+        }
+        let name = name.map(str::trim).filter(|n| !n.is_empty());
+        if let Some(name) = name {
+            if name.chars().count() > self.limits.max_name_len {
+                return Err(TournamentError::NameTooLong {
+                    max: self.limits.max_name_len,
+                });
+            }
+        }
+        self.champion_team_name = name.map(str::to_string);
+        Ok(())
+    }
+
+    /// Set what a forfeit does (any time before the tournament is completed).
+    pub fn set_forfeit_policy(&mut self, policy: ForfeitPolicy) -> Result<(), TournamentError> {
+        if self.state == TournamentState::Completed {
+            return Err(TournamentError::InvalidState);
+        }
+        self.forfeit_policy = policy;
+        Ok(())
+    }
+
+    /// Set (or clear with `None`) the number of boards; takes effect from the next generated round.
+    pub fn set_num_boards(&mut self, num_boards: Option<u32>) -> Result<(), TournamentError> {
+        if self.state == TournamentState::Completed {
+            return Err(TournamentError::InvalidState);
+        }
+        if num_boards == Some(0) {
+            return Err(TournamentError::InvalidConfig(
+                "num_boards must be at least 1",
+            ));
+        }
+        self.num_boards = num_boards;
+        Ok(())
+    }
+
+    /// Set (or clear with `None`) the cap on group play matches per round; takes effect from the
+    /// next generated round.
+    pub fn set_max_concurrent_matches(
+        &mut self,
+        max: Option<usize>,
+    ) -> Result<(), TournamentError> {
+        if self.state == TournamentState::Completed {
+            return Err(TournamentError::InvalidState);
+        }
+        if max == Some(0) {
+            return Err(TournamentError::InvalidConfig(
+                "max_concurrent_matches must be at least 1",
+            ));
+        }
+        self.max_concurrent_matches = max;
+        Ok(())
+    }
+
+    /// Assign the current matches to boards 1..=`num_boards` in order, wrapping around.
+    pub(crate) fn assign_boards(&mut self) {
+        let num_boards = self.num_boards;
+        for (i, m) in self.matches.iter_mut().enumerate() {
+            m.board = num_boards.map(|n| i as u32 % n + 1);
+        }
+    }
+
+    /// Current matches grouped by board (in play order); matches without a board are left out.
+    pub fn matches_by_board(&self) -> BTreeMap<u32, Vec<&GameMatch>> {
+        let mut by_board: BTreeMap<u32, Vec<&GameMatch>> = BTreeMap::new();
+        for m in &self.matches {
+            if let Some(board) = m.board {
+                by_board.entry(board).or_default().push(m);
+            }
+        }
+        by_board
+    }
+
+    /// Set the number of legs per semi-final/finals series (odd, at least 1) before the playoffs start.
+    pub fn set_legs_per_final_match(&mut self, legs: u32) -> Result<(), TournamentError> {
+        use TournamentState::*;
+        if !matches!(self.state, Setup | GroupPlay | FinalSelection | Redemption) {
             return Err(TournamentError::InvalidState);
         }
-        self.max_losses = max_losses;
+        if legs.is_multiple_of(2) {
+            return Err(TournamentError::InvalidLegCount(legs));
+        }
+        self.legs_per_final_match = legs;
+        Ok(())
+    }
+
+    /// Set what a drawn final leads to (any time before the tournament is completed).
+    pub fn set_final_tie_resolution(
+        &mut self,
+        resolution: FinalTieResolution,
+    ) -> Result<(), TournamentError> {
+        if self.state == TournamentState::Completed {
+            return Err(TournamentError::InvalidState);
+        }
+        self.final_tie_resolution = resolution;
+        Ok(())
+    }
+
+    /// Legs a team must win to take a final-round series.
+    pub fn legs_to_win(&self) -> u32 {
+        self.legs_per_final_match / 2 + 1
+    }
+
+    /// How results of `round` matches are entered: the `scoring_modes` entry if set, otherwise
+    /// legs for semi-finals and finals when they are series (`legs_per_final_match` > 1) and a
+    /// bare winner everywhere else.
+    pub fn scoring_mode(&self, round: RoundType) -> ScoringMode {
+        if let Some(&mode) = self.scoring_modes.get(&round) {
+            return mode;
+        }
+        if self.legs_per_final_match > 1
+            && matches!(round, RoundType::SemiFinals | RoundType::Finals)
+        {
+            ScoringMode::Legs
+        } else {
+            ScoringMode::Winner
+        }
+    }
+
+    /// Set how results of `round` matches are entered, before the playoffs start. Only
+    /// semi-finals and finals can be scored in legs.
+    pub fn set_scoring_mode(
+        &mut self,
+        round: RoundType,
+        mode: ScoringMode,
+    ) -> Result<(), TournamentError> {
+        use TournamentState::*;
+        if !matches!(self.state, Setup | GroupPlay | FinalSelection | Redemption) {
+            return Err(TournamentError::InvalidState);
+        }
+        if mode == ScoringMode::Legs && !matches!(round, RoundType::SemiFinals | RoundType::Finals)
+        {
+            return Err(TournamentError::InvalidConfig(
+                "legs scoring needs semi_finals or finals",
+            ));
+        }
+        self.scoring_modes.insert(round, mode);
+        Ok(())
+    }
+
+    /// Set how the semi-final bracket is seeded (before the semi-final matches are generated).
+    pub fn set_seeding(&mut self, seeding: SeedingStrategy) -> Result<(), TournamentError> {
+        use TournamentState::*;
+        let bracket_pending = self.state == SemiFinals && self.matches.is_empty();
+        if !matches!(self.state, Setup | GroupPlay | FinalSelection) && !bracket_pending {
+            return Err(TournamentError::InvalidState);
+        }
+        self.seeding = seeding;
+        Ok(())
+    }
+
+    /// Set (or clear) a player's external rating. Any player, active or eliminated, before the
+    /// tournament is completed.
+    pub fn set_player_rating(
+        &mut self,
+        player_id: PlayerId,
+        rating: Option<i32>,
+    ) -> Result<(), TournamentError> {
+        if self.state == TournamentState::Completed {
+            return Err(TournamentError::InvalidState);
+        }
+        let mut found = false;
+        for p in self
+            .players
+            .iter_mut()
+            .chain(self.unused_players.iter_mut())
+            .chain(self.eliminated_players.iter_mut())
+            .filter(|p| p.id == player_id)
+        {
+            p.rating = rating;
+            found = true;
+        }
+        if !found {
+            return Err(TournamentError::PlayerNotFound(player_id));
+        }
+        Ok(())
+    }
+
+    /// Set or clear (`None` or blank) a player's color and avatar (any list). Display only.
+    pub fn set_player_appearance(
+        &mut self,
+        player_id: PlayerId,
+        color: Option<&str>,
+        avatar_url: Option<&str>,
+    ) -> Result<(), TournamentError> {
+        let (color, avatar_url) = appearance(color, avatar_url, &self.limits)?;
+        let mut found = false;
+        for p in self
+            .players
+            .iter_mut()
+            .chain(self.unused_players.iter_mut())
+            .chain(self.eliminated_players.iter_mut())
+            .chain(self.last_eliminated_players.iter_mut())
+            .filter(|p| p.id == player_id)
+        {
+            p.color = color.clone();
+            p.avatar_url = avatar_url.clone();
+            found = true;
+        }
+        if !found {
+            return Err(TournamentError::PlayerNotFound(player_id));
+        }
+        Ok(())
+    }
+
+    /// Set a player's handicap spot (any list); current matches are updated to show it.
+    pub fn set_player_handicap_spot(
+        &mut self,
+        player_id: PlayerId,
+        spot: i32,
+    ) -> Result<(), TournamentError> {
+        let mut found = false;
+        for p in self
+            .players
+            .iter_mut()
+            .chain(self.unused_players.iter_mut())
+            .chain(self.eliminated_players.iter_mut())
+            .filter(|p| p.id == player_id)
+        {
+            p.handicap_spot = spot;
+            found = true;
+        }
+        if !found {
+            return Err(TournamentError::PlayerNotFound(player_id));
+        }
+        self.compute_match_handicaps();
+        Ok(())
+    }
+
+    /// Combined handicap spot of a team, saturating at the `i32` bounds; unknown ids count as 0.
+    pub fn team_handicap(&self, ids: &[PlayerId]) -> i32 {
+        ids.iter()
+            .filter_map(|&id| self.find_player_anywhere(id))
+            .fold(0i32, |sum, (p, _)| sum.saturating_add(p.handicap_spot))
+    }
+
+    /// Fill in `team_1_handicap` / `team_2_handicap` on the current matches.
+    pub(crate) fn compute_match_handicaps(&mut self) {
+        let handicaps: Vec<(i32, i32)> = self
+            .matches
+            .iter()
+            .map(|m| (self.team_handicap(&m.team_1), self.team_handicap(&m.team_2)))
+            .collect();
+        for (m, (team_1, team_2)) in self.matches.iter_mut().zip(handicaps) {
+            m.team_1_handicap = team_1;
+            m.team_2_handicap = team_2;
+        }
+    }
+
+    /// Set a player's loss count manually (GroupPlay or FinalSelection). Player must be active (in players or unused_players).
+    /// When no matches have been generated yet, we do not set eliminated=true so that "Generate matches" still has enough players.
+    pub fn set_player_losses(
+        &mut self,
+        player_id: PlayerId,
+        losses: u32,
+    ) -> Result<(), TournamentError> {
+        if self.state != TournamentState::GroupPlay && self.state != TournamentState::FinalSelection
+        {
+            return Err(TournamentError::InvalidState);
+        }
+        let max_losses = self.max_losses;
+        let has_matches = !self.matches.is_empty();
+        let round = self.current_round_number();
+        let p = self
+            .get_player_mut_any(player_id)
+            .ok_or(TournamentError::PlayerNotFound(player_id))?;
+        p.losses = losses;
+        // Only mark eliminated once at least one round has been generated; otherwise editing losses
+        // before the first "Generate matches" would shrink the pool and block generating matches.
+        if has_matches && p.losses >= max_losses {
+            p.eliminate_in_round(EliminationReason::MaxLosses, round);
+        }
+        Ok(())
+    }
+
+    /// Manually eliminate a player (GroupPlay or FinalSelection). Moves them from active to eliminated_players.
+    /// If 8 or fewer active players remain after elimination, transitions to FinalSelection (ready for semi-finals).
+    pub fn eliminate_player(&mut self, player_id: PlayerId) -> Result<(), TournamentError> {
+        if self.state != TournamentState::GroupPlay && self.state != TournamentState::FinalSelection
+        {
+            return Err(TournamentError::InvalidState);
+        }
+        self.eliminate_active(player_id, EliminationReason::Manual)
+    }
+
+    /// An active player volunteers to sit out the next group play round (Setup or GroupPlay).
+    /// Volunteers take the round's byes before anyone else; if there are more volunteers than
+    /// byes, the ones with the fewest sit-outs get them. The sit-out counts as usual, so the
+    /// rotation stays fair for everyone else. Requests are used up by the next generated round
+    /// once it is submitted; generating that round again honors them again.
+    pub fn request_sit_out(&mut self, player_id: PlayerId) -> Result<(), TournamentError> {
+        if !matches!(
+            self.state,
+            TournamentState::Setup | TournamentState::GroupPlay
+        ) {
+            return Err(TournamentError::InvalidState);
+        }
+        if !self
+            .players
+            .iter()
+            .any(|p| p.id == player_id && !p.eliminated)
+        {
+            return Err(TournamentError::PlayerNotFound(player_id));
+        }
+        if !self.sit_out_requests.contains(&player_id) {
+            self.sit_out_requests.push(player_id);
+        }
+        Ok(())
+    }
+
+    /// Take back the byes of the current group play round before it is replaced: everyone in
+    /// `unused_players` gets their sit-out counters decremented and the list is cleared, so the
+    /// round can be generated again without counting those byes twice. The round's sit-out
+    /// requests are pending again. No-op for a practice round (its byes are not counted) or once
+    /// the round has been submitted.
+    pub fn undo_last_bye_assignment(&mut self) {
+        if self.state != TournamentState::GroupPlay || self.matches.iter().any(|m| m.practice) {
+            return;
+        }
+        for sat_out in std::mem::take(&mut self.unused_players) {
+            if let Some(p) = self.players.iter_mut().find(|p| p.id == sat_out.id) {
+                p.undo_sat_out();
+            }
+        }
+        self.restore_sit_out_requests();
+    }
+
+    /// Put the current round's sit-out requests back in front of any made since.
+    fn restore_sit_out_requests(&mut self) {
+        let mut requests = std::mem::take(&mut self.round_sit_out_requests);
+        for id in std::mem::take(&mut self.sit_out_requests) {
+            if !requests.contains(&id) {
+                requests.push(id);
+            }
+        }
+        self.sit_out_requests = requests;
+    }
+
+    /// A player forfeits (GroupPlay only), handled by `forfeit_policy`:
+    /// - `ImmediateElimination`: eliminated like [`Tournament::eliminate_player`]. A match left
+    ///   without any of its side goes to the walkover rules on submit; a 2v2 partner plays on and
+    ///   the result counts for them only.
+    /// - `CountsAsLoss`: an unplayed current match of theirs is given to the opponents (the loss
+    ///   comes with the round). Otherwise a loss is added now, eliminating them at `max_losses` once
+    ///   past their `grace_matches`.
+    pub fn forfeit_player(&mut self, player_id: PlayerId) -> Result<(), TournamentError> {
+        if self.state != TournamentState::GroupPlay {
+            return Err(TournamentError::InvalidState);
+        }
+        if self.forfeit_policy == ForfeitPolicy::ImmediateElimination {
+            return self.eliminate_active(player_id, EliminationReason::Forfeit);
+        }
+        if !self.is_eligible(player_id) {
+            return Err(TournamentError::PlayerNotFound(player_id));
+        }
+        let open_match = self
+            .matches
+            .iter()
+            .filter(|m| !self.match_results.contains_key(&m.id))
+            .find_map(|m| {
+                if m.team_1.contains(&player_id) {
+                    Some((m.id, Team::Two))
+                } else if m.team_2.contains(&player_id) {
+                    Some((m.id, Team::One))
+                } else {
+                    None
+                }
+            });
+        if let Some((match_id, opponents)) = open_match {
+            self.match_results.insert(match_id, opponents.into());
+            return Ok(());
+        }
+        for p in self
+            .players
+            .iter_mut()
+            .chain(self.unused_players.iter_mut())
+            .filter(|p| p.id == player_id)
+        {
+            p.add_loss();
+        }
+        let reached_max = self
+            .players
+            .iter()
+            .chain(&self.unused_players)
+            .any(|p| p.id == player_id && self.reached_max_losses(p));
+        if reached_max {
+            return self.eliminate_active(player_id, EliminationReason::Forfeit);
+        }
+        Ok(())
+    }
+
+    /// Move an active (or sitting-out) player to `eliminated_players` for `reason`, switching to
+    /// FinalSelection once the semi-final size is reached.
+    fn eliminate_active(
+        &mut self,
+        player_id: PlayerId,
+        reason: EliminationReason,
+    ) -> Result<(), TournamentError> {
+        let player = self
+            .players
+            .iter()
+            .chain(self.unused_players.iter())
+            .find(|p| p.id == player_id)
+            .cloned()
+            .ok_or(TournamentError::PlayerNotFound(player_id))?;
+        let mut p = player;
+        p.eliminate_in_round(reason, self.current_round_number());
+        self.players.retain(|x| x.id != player_id);
+        self.unused_players.retain(|x| x.id != player_id);
+        self.eliminated_players.push(p);
+        if self.format == TournamentFormat::GroupOnly {
+            if self.group_only_finished() {
+                self.unused_players.clear();
+                self.matches.clear();
+                self.match_results.clear();
+                self.complete();
+            }
+            return Ok(());
+        }
+        if self.format == TournamentFormat::KnockoutAll {
+            // The knockout starts after the round cap, however many players are left.
+            return Ok(());
+        }
+        let threshold = self.players_required_for_semi();
+        if self.players.len() + self.unused_players.len() <= threshold {
+            self.state = TournamentState::FinalSelection;
+        }
         Ok(())
     }
 
-    /// Set mode 1v1 or 2v2 (only valid in Setup).
-    pub fn set_mode(&mut self, mode: TournamentMode) -> Result<(), TournamentError> {
-        if self.state != TournamentState::Setup {
+    /// Eliminate several players at once (GroupPlay or FinalSelection). All-or-nothing: every id
+    /// must be an active player, otherwise `PlayerNotFound` for the first bad id and nobody is
+    /// eliminated. Repeated ids are eliminated once.
+    pub fn eliminate_players(&mut self, player_ids: &[PlayerId]) -> Result<(), TournamentError> {
+        if self.state != TournamentState::GroupPlay && self.state != TournamentState::FinalSelection
+        {
             return Err(TournamentError::InvalidState);
         }
-        self.mode = mode;
+        for &id in player_ids {
+            let active = self
+                .players
+                .iter()
+                .chain(self.unused_players.iter())
+                .any(|p| p.id == id);
+            if !active {
+                return Err(TournamentError::PlayerNotFound(id));
+            }
+        }
+        let mut done: Vec<PlayerId> = Vec::with_capacity(player_ids.len());
+        for &id in player_ids {
+            if !done.contains(&id) {
+                self.eliminate_player(id)?;
+                done.push(id);
+            }
+        }
         Ok(())
     }
 
-    /// Set a player's loss count manually (GroupPlay or FinalSelection). Player must be active (in players or unused_players).
-    /// When no matches have been generated yet, we do not set eliminated=true so that "Generate matches" still has enough players.
-    pub fn set_player_losses(
+    /// Correct the winner of an already submitted group play match in `match_history`. The old
+    /// result (win/loss or draw) is taken back and the new one applied, then elimination is
+    /// re-checked for the players involved: one eliminated for losses who is now under
+    /// `max_losses` rejoins, one who now reaches it is eliminated. Only between rounds (GroupPlay
+    /// with no matches generated, or FinalSelection); the state then follows the active count as
+    /// after a submitted round, per `format`.
+    /// Practice matches, walkovers and playoff matches cannot be amended.
+    pub fn amend_history_result(
         &mut self,
-        player_id: PlayerId,
-        losses: u32,
+        match_id: MatchId,
+        new_winner: Team,
     ) -> Result<(), TournamentError> {
-        if self.state != TournamentState::GroupPlay && self.state != TournamentState::FinalSelection
-        {
+        let between_rounds = match self.state {
+            TournamentState::GroupPlay => self.matches.is_empty(),
+            TournamentState::FinalSelection => true,
+            _ => false,
+        };
+        if !between_rounds {
+            return Err(TournamentError::InvalidState);
+        }
+        let entry = self
+            .match_history
+            .iter_mut()
+            .find(|m| m.id == match_id)
+            .ok_or(TournamentError::MatchNotFound(match_id))?;
+        if entry.round != RoundType::GroupPlay || entry.practice || entry.walkover {
             return Err(TournamentError::InvalidState);
         }
+        if entry.winner == Some(new_winner) {
+            return Ok(());
+        }
+        let old_winner = entry.winner;
+        let was_draw = entry.draw;
+        entry.winner = Some(new_winner);
+        entry.draw = false;
+        let (team_1, team_2) = (entry.team_1.clone(), entry.team_2.clone());
+        let side = |id: &PlayerId| {
+            if team_1.contains(id) {
+                Team::One
+            } else {
+                Team::Two
+            }
+        };
+
+        for id in team_1.iter().chain(&team_2) {
+            let Some(p) = self
+                .players
+                .iter_mut()
+                .chain(self.eliminated_players.iter_mut())
+                .find(|p| p.id == *id)
+            else {
+                continue;
+            };
+            match old_winner {
+                Some(w) if w == side(id) => p.wins = p.wins.saturating_sub(1),
+                Some(_) => p.losses = p.losses.saturating_sub(1),
+                None if was_draw => p.draws = p.draws.saturating_sub(1),
+                None => {}
+            }
+            if side(id) == new_winner {
+                p.add_win();
+            } else {
+                p.add_loss();
+            }
+        }
+
         let max_losses = self.max_losses;
-        let has_matches = !self.matches.is_empty();
-        let p = self
-            .get_player_mut_any(player_id)
-            .ok_or(TournamentError::PlayerNotFound(player_id))?;
-        p.losses = losses;
-        // Only mark eliminated once at least one round has been generated; otherwise editing losses
-        // before the first "Generate matches" would shrink the pool and block generating matches.
-        if has_matches && p.losses >= max_losses {
-            p.eliminated = true;
+        let round = self.rounds_completed;
+        for id in team_1.iter().chain(&team_2) {
+            if let Some(i) = self.players.iter().position(|p| p.id == *id) {
+                if self.reached_max_losses(&self.players[i]) {
+                    let mut p = self.players.remove(i);
+                    p.eliminate_in_round(EliminationReason::MaxLosses, round);
+                    self.last_eliminated_players.push(p.clone());
+                    self.eliminated_players.push(p);
+                }
+            } else if let Some(i) = self.eliminated_players.iter().position(|p| p.id == *id) {
+                let p = &self.eliminated_players[i];
+                if p.elimination_reason == Some(EliminationReason::MaxLosses)
+                    && p.losses < max_losses
+                {
+                    let mut p = self.eliminated_players.remove(i);
+                    p.reinstate();
+                    self.last_eliminated_players.retain(|l| l.id != p.id);
+                    self.players.push(p);
+                }
+            }
+        }
+        // Keep the "last eliminated" copies in step with the corrected stats.
+        for l in &mut self.last_eliminated_players {
+            if let Some(p) = self.eliminated_players.iter().find(|p| p.id == l.id) {
+                *l = p.clone();
+            }
+        }
+
+        self.state = if self.format == TournamentFormat::WithFinals
+            && self.players.len() <= self.players_required_for_semi()
+        {
+            TournamentState::FinalSelection
+        } else {
+            TournamentState::GroupPlay
+        };
+        if self.group_only_finished() {
+            self.complete();
+        }
+        if self.format == TournamentFormat::KnockoutAll && self.players.len() <= 2 {
+            crate::logic::start_knockout(self);
         }
         Ok(())
     }
 
-    /// Manually eliminate a player (GroupPlay or FinalSelection). Moves them from active to eliminated_players.
-    /// If 8 or fewer active players remain after elimination, transitions to FinalSelection (ready for semi-finals).
-    pub fn eliminate_player(&mut self, player_id: PlayerId) -> Result<(), TournamentError> {
-        if self.state != TournamentState::GroupPlay && self.state != TournamentState::FinalSelection
-        {
+    /// Append submitted matches to `match_history`, stamping each with its outcome from `results`.
+    pub(crate) fn record_history(&mut self, results: &HashMap<MatchId, MatchOutcome>) {
+        for m in &self.matches {
+            let mut played = m.clone();
+            let outcome = results.get(&m.id).copied();
+            played.winner = outcome.and_then(MatchOutcome::winner);
+            played.draw = outcome == Some(MatchOutcome::Draw);
+            self.match_history.push(played);
+        }
+    }
+
+    /// "If group play ended now": the active players with the best records (same order as
+    /// [`crate::auto_fill_finalists`]), at most the semi-final size. Empty outside GroupPlay.
+    pub fn projected_finalists(&self) -> Vec<PlayerSummary> {
+        if self.state != TournamentState::GroupPlay {
+            return Vec::new();
+        }
+        let mut ranked: Vec<&Player> = self.players.iter().filter(|p| !p.eliminated).collect();
+        ranked.sort_by_key(|p| record_rank(p));
+        ranked
+            .into_iter()
+            .take(self.players_required_for_semi())
+            .map(|p| self.player_summary(p))
+            .collect()
+    }
+
+    /// The semi-final draw in order (see `finals_seeding`), with names and stats. `InvalidState`
+    /// before the semi-finals are generated.
+    pub fn finals_seeding_view(&self) -> Result<Vec<PlayerSummary>, TournamentError> {
+        let seeding = self
+            .finals_seeding
+            .as_ref()
+            .ok_or(TournamentError::InvalidState)?;
+        Ok(seeding
+            .iter()
+            .filter_map(|&id| {
+                self.find_player_anywhere(id).map(|(p, _)| p).or_else(|| {
+                    self.bracket_semi_final_players
+                        .iter()
+                        .flatten()
+                        .find(|p| p.id == id)
+                })
+            })
+            .map(|p| self.player_summary(p))
+            .collect())
+    }
+
+    /// Preview entering `outcome` for a current group play match without changing anything: who
+    /// in that match it would eliminate and, if it completes the round, the state the submitted
+    /// round would leave. Errors like [`Tournament::set_match_outcome`].
+    pub fn simulate_winner(
+        &self,
+        match_id: MatchId,
+        outcome: MatchOutcome,
+    ) -> Result<SimResult, TournamentError> {
+        let mut copy = self.clone();
+        copy.set_match_outcome(match_id, outcome)?;
+        copy.auto_generate_next = false;
+        let completes_round = copy
+            .matches
+            .iter()
+            .all(|m| copy.match_results.contains_key(&m.id));
+        if !completes_round {
+            copy.matches.retain(|m| m.id == match_id);
+            copy.match_results.retain(|&id, _| id == match_id);
+        }
+        let summary = crate::logic::process_group_play_results_summary(&mut copy)?;
+        Ok(SimResult {
+            eliminated: summary
+                .eliminated
+                .iter()
+                .filter(|p| {
+                    self.matches
+                        .iter()
+                        .any(|m| m.id == match_id && m.team_of(p.id).is_some())
+                })
+                .map(|p| self.player_summary(p))
+                .collect(),
+            completes_round,
+            state: if completes_round {
+                copy.state
+            } else {
+                self.state
+            },
+        })
+    }
+
+    /// All settings in one object.
+    pub fn config(&self) -> TournamentConfig {
+        TournamentConfig {
+            max_losses: self.max_losses,
+            mode: self.mode,
+            format: self.format,
+            balance_teams: self.balance_teams,
+            reshuffle_every: self.reshuffle_every,
+            draws_allowed: self.draws_allowed,
+            points_config: self.points_config,
+            redemption: self.redemption,
+            single_champion: self.single_champion,
+            num_boards: self.num_boards,
+            max_concurrent_matches: self.max_concurrent_matches,
+            legs_per_final_match: self.legs_per_final_match,
+            final_tie_resolution: self.final_tie_resolution,
+            deterministic: self.deterministic,
+            shuffle_match_order: self.shuffle_match_order,
+            auto_generate_next: self.auto_generate_next,
+            strict_bye_rotation: self.strict_bye_rotation,
+            knockout_after_rounds: self.knockout_after_rounds,
+            grace_matches: self.grace_matches,
+            round_cap: self.round_cap,
+            auto_start_semis: self.auto_start_semis,
+            allow_ghost: self.allow_ghost,
+            finals_format: self.finals_format,
+            forfeit_policy: self.forfeit_policy,
+            seeding: self.seeding,
+            scoring_modes: self.scoring_modes.clone(),
+            auto_submit_after_secs: self.auto_submit_after_secs,
+        }
+    }
+
+    /// Replace every setting at once (Setup only). Each value is checked like its own setter, and
+    /// the 2v2-only options (`balance_teams`, `reshuffle_every` above 1, `single_champion`) are
+    /// rejected in 1v1, the 1v1-only knockout-all format in 2v2. On any error nothing changes.
+    pub fn set_config(&mut self, config: TournamentConfig) -> Result<(), TournamentError> {
+        if self.state != TournamentState::Setup {
             return Err(TournamentError::InvalidState);
         }
-        let player = self
-            .players
+        if config.mode == TournamentMode::OneVOne {
+            if config.balance_teams {
+                return Err(TournamentError::InvalidConfig("balance_teams needs 2v2"));
+            }
+            if config.reshuffle_every > 1 {
+                return Err(TournamentError::InvalidConfig("reshuffle_every needs 2v2"));
+            }
+            if config.single_champion {
+                return Err(TournamentError::InvalidConfig("single_champion needs 2v2"));
+            }
+        } else if config.format == TournamentFormat::KnockoutAll {
+            return Err(TournamentError::InvalidConfig("knockout_all needs 1v1"));
+        }
+        self.transaction(|updated| {
+            updated.set_max_losses(config.max_losses)?;
+            updated.set_mode(config.mode)?;
+            updated.set_format(config.format)?;
+            updated.set_balance_teams(config.balance_teams)?;
+            updated.set_reshuffle_every(config.reshuffle_every)?;
+            updated.set_draws_allowed(config.draws_allowed)?;
+            updated.points_config = config.points_config;
+            updated.redemption = config.redemption;
+            updated.set_single_champion(config.single_champion)?;
+            updated.set_num_boards(config.num_boards)?;
+            updated.set_max_concurrent_matches(config.max_concurrent_matches)?;
+            updated.set_legs_per_final_match(config.legs_per_final_match)?;
+            updated.set_final_tie_resolution(config.final_tie_resolution)?;
+            updated.set_deterministic(config.deterministic)?;
+            updated.set_shuffle_match_order(config.shuffle_match_order)?;
+            updated.set_auto_generate_next(config.auto_generate_next)?;
+            updated.set_strict_bye_rotation(config.strict_bye_rotation)?;
+            updated.set_knockout_after_rounds(config.knockout_after_rounds)?;
+            updated.set_grace_matches(config.grace_matches)?;
+            updated.set_round_cap(config.round_cap)?;
+            updated.set_auto_start_semis(config.auto_start_semis)?;
+            updated.set_allow_ghost(config.allow_ghost)?;
+            updated.set_finals_format(config.finals_format)?;
+            updated.set_forfeit_policy(config.forfeit_policy)?;
+            updated.set_seeding(config.seeding)?;
+            updated.scoring_modes.clear();
+            for (&round, &mode) in &config.scoring_modes {
+                updated.set_scoring_mode(round, mode)?;
+            }
+            updated.set_auto_submit_after(config.auto_submit_after_secs)?;
+            Ok(())
+        })
+    }
+
+    /// List row for an admin view. `last_activity` is when a request last touched the tournament
+    /// (kept by the server, not part of the tournament); its age is taken now.
+    pub fn summary(&self, last_activity: Instant) -> TournamentSummary {
+        TournamentSummary {
+            id: self.id,
+            state: self.state,
+            mode: self.mode,
+            format: self.format,
+            active: self.players.len(),
+            version: self.version,
+            started_at: self.started_at,
+            last_activity_seconds_ago: last_activity.elapsed().as_secs(),
+        }
+    }
+
+    /// Sizes of the player lists, plus `max_losses` and the state.
+    pub fn counts(&self) -> Counts {
+        Counts {
+            active: self.players.len(),
+            unused: self.unused_players.len(),
+            eliminated: self.eliminated_players.len(),
+            last_eliminated: self.last_eliminated_players.len(),
+            max_losses: self.max_losses,
+            state: self.state,
+        }
+    }
+
+    /// Team leaderboard for 2v2: every pairing that played together in a submitted (non-practice)
+    /// match, with its combined record, sorted by wins (then fewest losses, then first played).
+    /// Empty in 1v1.
+    pub fn team_standings(&self) -> Vec<TeamStats> {
+        if self.mode != TournamentMode::TwoVTwo {
+            return Vec::new();
+        }
+        let mut teams: Vec<TeamStats> = Vec::new();
+        let decided = |m: &&GameMatch| !m.practice && (m.winner.is_some() || m.draw);
+        for m in self.match_history.iter().filter(decided) {
+            for (side, team) in [(Team::One, &m.team_1), (Team::Two, &m.team_2)] {
+                if team.len() != 2 {
+                    continue;
+                }
+                let mut players = team.clone();
+                players.sort();
+                let stats = match teams.iter().position(|t| t.players == players) {
+                    Some(i) => &mut teams[i],
+                    None => {
+                        let names = players
+                            .iter()
+                            .map(|&id| {
+                                self.find_player_anywhere(id)
+                                    .map(|(p, _)| p.name.clone())
+                                    .unwrap_or_default()
+                            })
+                            .collect();
+                        teams.push(TeamStats {
+                            players,
+                            names,
+                            ..TeamStats::default()
+                        });
+                        teams.last_mut().expect("just pushed")
+                    }
+                };
+                stats.matches_played += 1;
+                match m.winner {
+                    Some(w) if w == side => stats.wins += 1,
+                    Some(_) => stats.losses += 1,
+                    None => stats.draws += 1,
+                }
+            }
+        }
+        teams.sort_by_key(|t| (std::cmp::Reverse(t.wins), t.losses));
+        teams
+    }
+
+    /// The current or submitted match with this `match_number`.
+    pub fn match_by_number(&self, number: u32) -> Option<&GameMatch> {
+        self.matches
+            .iter()
+            .chain(&self.match_history)
+            .find(|m| m.match_number == number && number != 0)
+    }
+
+    /// Give the current matches that have no number yet the next numbers, in order.
+    pub(crate) fn number_new_matches(&mut self) {
+        for m in self.matches.iter_mut().filter(|m| m.match_number == 0) {
+            self.last_match_number += 1;
+            m.match_number = self.last_match_number;
+        }
+    }
+
+    /// Drop `match_results` / `final_match_results` entries for matches that are not in the
+    /// current round (left over from a replaced or removed round), returning their match ids.
+    /// Called whenever a round is generated.
+    pub fn prune_stale_results(&mut self) -> Vec<MatchId> {
+        let current: HashSet<MatchId> = self.matches.iter().map(|m| m.id).collect();
+        let mut stale: Vec<MatchId> = self
+            .match_results
+            .keys()
+            .chain(self.final_match_results.keys())
+            .filter(|id| !current.contains(id))
+            .copied()
+            .collect();
+        stale.sort();
+        self.match_results.retain(|id, _| current.contains(id));
+        self.final_match_results
+            .retain(|id, _| current.contains(id));
+        if self.finals_draw.is_some_and(|id| !current.contains(&id)) {
+            self.finals_draw = None;
+        }
+        stale
+    }
+
+    /// Every match the player has been in: submitted matches in chronological order, then the
+    /// current round's. Empty for a player who has not played (or is unknown).
+    pub fn player_match_log(&self, player_id: PlayerId) -> Vec<&GameMatch> {
+        self.match_history
+            .iter()
+            .chain(&self.matches)
+            .filter(|m| m.team_of(player_id).is_some())
+            .collect()
+    }
+
+    /// One page of `match_history` (chronological) and the total number of recorded matches.
+    pub fn history_page(&self, offset: usize, limit: usize) -> (&[GameMatch], usize) {
+        let total = self.match_history.len();
+        let start = offset.min(total);
+        let end = start.saturating_add(limit).min(total);
+        (&self.match_history[start..end], total)
+    }
+
+    /// Whether the player is out of lives: at `max_losses` and past their `grace_matches`.
+    pub(crate) fn reached_max_losses(&self, player: &Player) -> bool {
+        player.losses >= self.max_losses
+            && (self.grace_matches == 0 || player.matches_played > self.grace_matches)
+    }
+
+    /// Whether the player is still in the current round's pool (active or sitting out) and not eliminated.
+    fn is_eligible(&self, id: PlayerId) -> bool {
+        self.players
             .iter()
             .chain(self.unused_players.iter())
-            .find(|p| p.id == player_id)
-            .cloned()
-            .ok_or(TournamentError::PlayerNotFound(player_id))?;
-        let mut p = player;
-        p.eliminate();
-        self.players.retain(|x| x.id != player_id);
-        self.unused_players.retain(|x| x.id != player_id);
-        self.eliminated_players.push(p);
-        let threshold = self.players_required_for_semi();
-        if self.players.len() + self.unused_players.len() <= threshold {
-            self.state = TournamentState::FinalSelection;
+            .any(|p| p.id == id && !p.eliminated)
+    }
+
+    /// Award walkovers in the current round: any unplayed match where one team has no eligible players
+    /// left (eliminated or removed, e.g. by manual editing) is won by the other team and flagged
+    /// `walkover`. A match where neither team has eligible players is dropped from the round.
+    /// Returns the ids of awarded matches.
+    pub fn detect_walkovers(&mut self) -> Vec<MatchId> {
+        let mut awarded = Vec::new();
+        let mut dropped = Vec::new();
+        for i in 0..self.matches.len() {
+            let m = &self.matches[i];
+            let has_result = match m.round {
+                RoundType::GroupPlay => self.match_results.contains_key(&m.id),
+                RoundType::Redemption
+                | RoundType::SemiFinals
+                | RoundType::Finals
+                | RoundType::GrandFinals
+                | RoundType::Knockout => self.final_match_results.contains_key(&m.id),
+            };
+            if has_result {
+                continue;
+            }
+            let team_1_empty = !m.team_1.iter().any(|&id| self.is_eligible(id));
+            let team_2_empty = !m.team_2.iter().any(|&id| self.is_eligible(id));
+            let winner = match (team_1_empty, team_2_empty) {
+                (false, false) => continue,
+                (true, true) => {
+                    dropped.push(m.id);
+                    continue;
+                }
+                (true, false) => Team::Two,
+                (false, true) => Team::One,
+            };
+            let (id, round) = (m.id, m.round);
+            self.matches[i].walkover = true;
+            match round {
+                RoundType::GroupPlay => {
+                    self.match_results.insert(id, winner.into());
+                }
+                RoundType::Redemption
+                | RoundType::SemiFinals
+                | RoundType::Finals
+                | RoundType::GrandFinals
+                | RoundType::Knockout => {
+                    self.final_match_results.insert(id, winner);
+                }
+            }
+            awarded.push(id);
+        }
+        self.matches.retain(|m| !dropped.contains(&m.id));
+        awarded
+    }
+
+    /// Void a recorded but not yet submitted result so it can be re-entered (group play or final rounds).
+    /// Clears the entry in `match_results` / `final_match_results` and the match's `winner`.
+    pub fn void_match_result(&mut self, match_id: MatchId) -> Result<(), TournamentError> {
+        let m = self
+            .matches
+            .iter_mut()
+            .find(|m| m.id == match_id)
+            .ok_or(TournamentError::MatchNotFound(match_id))?;
+        let had_winner = m.winner.take().is_some();
+        let had_legs = !std::mem::take(&mut m.legs).is_empty();
+        let had_group_result = self.match_results.remove(&match_id).is_some();
+        let had_final_result = self.final_match_results.remove(&match_id).is_some();
+        if !(had_winner || had_legs || had_group_result || had_final_result) {
+            return Err(TournamentError::NoResultRecorded(match_id));
         }
         Ok(())
     }
 
-    /// Restart tournament: go back to Setup with same player names (active + eliminated). Clears matches and state.
+    /// Restart tournament: go back to Setup with same player names (active + eliminated), keeping
+    /// their rating, appearance and handicap spot. Clears matches and state.
     pub fn restart_tournament(&mut self) -> Result<(), TournamentError> {
         if self.state != TournamentState::GroupPlay && self.state != TournamentState::FinalSelection
         {
             return Err(TournamentError::InvalidState);
         }
-        let names: Vec<String> = self
+        let mut roster: Vec<&Player> = self
             .players
             .iter()
             .chain(self.unused_players.iter())
             .chain(self.eliminated_players.iter())
-            .map(|p| p.name.clone())
             .collect();
-        let max_losses = self.max_losses;
-        let mode = self.mode;
-        *self = Self::new(max_losses, mode);
-        for name in names {
-            let _ = self.add_player(name);
+        roster.sort_by_key(|p| p.join_order);
+        let roster: Vec<Player> = roster.into_iter().cloned().collect();
+        // Every setting carries over through `set_config`; on an error nothing changes.
+        let config = self.config();
+        let mut fresh = Self::new(config.max_losses, config.mode);
+        fresh.version = self.version;
+        fresh.limits = self.limits;
+        fresh.set_config(config)?;
+        for p in roster {
+            if fresh
+                .add_player_with_appearance(
+                    p.name,
+                    p.rating,
+                    p.color.as_deref(),
+                    p.avatar_url.as_deref(),
+                )
+                .is_ok()
+            {
+                if let Some(player) = fresh.players.last_mut() {
+                    player.handicap_spot = p.handicap_spot;
+                }
+            }
+        }
+        *self = fresh;
+        Ok(())
+    }
+
+    /// Return to a fresh group play round without touching anyone's stats (wins, losses, sit-outs
+    /// and seeds carry over, including results from any playoff rounds already played). Clears the
+    /// current matches and results, the finals bracket and the knockout bracket; match history is
+    /// kept.
+    ///
+    /// Semi-final losers rejoin the active players. Players eliminated in group play stay eliminated
+    /// unless `reactivate_eliminated` is set, in which case they are reinstated with their losses
+    /// intact (so their next loss eliminates them again). As when starting, a field no larger than
+    /// the semi-final size goes straight to FinalSelection. Not allowed in Setup.
+    pub fn reset_to_group_play_keeping_stats(
+        &mut self,
+        reactivate_eliminated: bool,
+    ) -> Result<(), TournamentError> {
+        if self.state == TournamentState::Setup {
+            return Err(TournamentError::InvalidState);
+        }
+        let mut active = std::mem::take(&mut self.players);
+        for p in self.bracket_semi_final_players.take().unwrap_or_default() {
+            if !active.iter().any(|a| a.id == p.id) {
+                active.push(p);
+            }
+        }
+        let mut eliminated = std::mem::take(&mut self.eliminated_players);
+        if reactivate_eliminated {
+            for mut p in eliminated.drain(..) {
+                p.reinstate();
+                active.push(p);
+            }
+        }
+        for p in &mut active {
+            p.played_last_round = false;
+        }
+        active.sort_by_key(|p| p.join_order);
+        self.players = active;
+        self.eliminated_players = eliminated;
+        self.last_eliminated_players.clear();
+        self.unused_players.clear();
+        self.restore_sit_out_requests();
+        self.matches.clear();
+        self.match_results.clear();
+        self.final_match_results.clear();
+        self.bracket_semi_final_matches = None;
+        self.bracket_semi_final_results = None;
+        self.bracket_finals_match = None;
+        self.bracket_finals_result = None;
+        self.finals_draw = None;
+        self.finals_decider_for = None;
+        self.shared_title = false;
+        self.bracket_grand_finals_match = None;
+        self.bracket_grand_finals_result = None;
+        self.champion_team_name = None;
+        self.completed_at = None;
+        self.semi_final_byes.clear();
+        self.finals_seeding = None;
+        self.last_round_summary = None;
+        self.ghost_player = None;
+        self.last_round_teams.clear();
+        self.knockout_seeds.clear();
+        self.knockout_eliminated.clear();
+        self.state = if self.players.len() > self.players_required_to_start() {
+            TournamentState::GroupPlay
+        } else {
+            TournamentState::FinalSelection
+        };
+        Ok(())
+    }
+
+    /// The operations valid right now, mirroring the state guards of the tournament methods and
+    /// the per-phase logic functions, so clients can offer exactly these. While paused, only
+    /// [`TournamentAction::Resume`].
+    pub fn available_actions(&self) -> Vec<TournamentAction> {
+        use TournamentAction::*;
+        use TournamentState as S;
+        if self.paused {
+            return vec![Resume];
+        }
+        let mut actions = Vec::new();
+        if matches!(self.state, S::Setup | S::GroupPlay | S::FinalSelection) {
+            actions.push(AddPlayer);
+        }
+        match self.state {
+            S::Setup => {
+                if !self.players.is_empty() {
+                    actions.push(RemovePlayer);
+                }
+                actions.push(SetMode);
+                let knockout_2v2 = self.format == TournamentFormat::KnockoutAll
+                    && self.mode != TournamentMode::OneVOne;
+                if self.players.len() >= self.players_required_to_start() && !knockout_2v2 {
+                    actions.push(Start);
+                }
+            }
+            S::FinalSelection => {
+                let needed = self
+                    .players_required_for_semi()
+                    .saturating_sub(self.players.len());
+                if needed > 0 {
+                    if self.last_eliminated_players.len() >= needed {
+                        actions.push(AddPlayersBack);
+                        actions.push(AutoFillFinalists);
+                    } else if self.eliminated_players.len() >= needed {
+                        actions.push(AddBackFromAllEliminated);
+                    }
+                }
+                if self.can_start_semi_finals() {
+                    actions.push(StartSemiFinals);
+                }
+            }
+            S::GroupPlay | S::Redemption | S::SemiFinals | S::Knockout
+                if self.matches.is_empty() =>
+            {
+                actions.push(GenerateMatches);
+            }
+            S::Completed => {
+                let winning_pair = match (&self.bracket_finals_match, self.bracket_finals_result) {
+                    (Some(m), Some(Team::One)) => m.team_1.len() == 2,
+                    (Some(m), Some(Team::Two)) => m.team_2.len() == 2,
+                    _ => false,
+                };
+                if winning_pair && self.bracket_grand_finals_match.is_none() {
+                    actions.push(StartGrandFinals);
+                }
+            }
+            _ => {
+                let results_in = self.matches.iter().all(|m| {
+                    let recorded = match m.round {
+                        RoundType::GroupPlay => self.match_results.contains_key(&m.id),
+                        _ => self.final_match_results.contains_key(&m.id),
+                    };
+                    recorded
+                        || !m.team_1.iter().any(|&id| self.is_eligible(id))
+                        || !m.team_2.iter().any(|&id| self.is_eligible(id))
+                });
+                actions.push(if results_in { Submit } else { RecordResult });
+            }
+        }
+        if self.state == S::GroupPlay {
+            if !self.players.is_empty() || !self.unused_players.is_empty() {
+                actions.push(Forfeit);
+            }
+            if !self.matches.is_empty()
+                && self.match_results.is_empty()
+                && self.matches.iter().all(|m| m.winner.is_none())
+            {
+                actions.push(SwapPlayers);
+            }
+        }
+        if matches!(self.state, S::GroupPlay | S::FinalSelection) {
+            actions.push(Restart);
+        }
+        if self.state != S::Completed {
+            actions.push(Pause);
+        }
+        actions
+    }
+
+    /// The current round only (see [`CurrentRoundView`]); empty lists when no matches are generated.
+    pub fn current_round_view(&self) -> CurrentRoundView {
+        let mut results: HashMap<MatchId, MatchOutcome> = self.match_results.clone();
+        results.extend(
+            self.final_match_results
+                .iter()
+                .map(|(&id, &team)| (id, MatchOutcome::from(team))),
+        );
+        let player_names = self
+            .matches
+            .iter()
+            .flat_map(|m| m.team_1.iter().chain(&m.team_2))
+            .filter_map(|&id| self.find_player_anywhere(id))
+            .map(|(p, _)| (p.id, p.name.clone()))
+            .collect();
+        CurrentRoundView {
+            state: self.state,
+            matches: self.matches.clone(),
+            results,
+            player_names,
+            seed: self.last_round_seed,
+            match_views: None,
+        }
+    }
+
+    /// [`Tournament::current_round_view`] with `match_views` filled in, so clients need no name
+    /// lookup.
+    pub fn current_round_view_with_names(&self) -> CurrentRoundView {
+        CurrentRoundView {
+            match_views: Some(self.matches.iter().map(|m| self.match_view(m)).collect()),
+            ..self.current_round_view()
+        }
+    }
+
+    /// A player's name wherever they are (including semi-finalists since knocked out); `"?"` for
+    /// an unknown id.
+    pub fn player_name(&self, id: PlayerId) -> String {
+        self.find_player_anywhere(id)
+            .map(|(p, _)| p)
+            .or_else(|| {
+                self.bracket_semi_final_players
+                    .iter()
+                    .flatten()
+                    .find(|p| p.id == id)
+            })
+            .map_or_else(|| "?".to_string(), |p| p.name.clone())
+    }
+
+    /// `m` with its players' names inline.
+    pub fn match_view(&self, m: &GameMatch) -> GameMatchView {
+        GameMatchView::new(m, |id| self.player_name(id))
+    }
+
+    /// The playoff bracket so far with names inline: semi-finals, finals and grand final, each
+    /// from its snapshot or, while being played, the current matches. Winners come from the
+    /// recorded results. Empty before the semi-finals are generated.
+    pub fn bracket_view(&self) -> Vec<GameMatchView> {
+        let live = |state: TournamentState| -> Vec<(GameMatch, Option<Team>)> {
+            if self.state != state {
+                return Vec::new();
+            }
+            self.matches
+                .iter()
+                .map(|m| (m.clone(), self.final_match_results.get(&m.id).copied()))
+                .collect()
+        };
+        let mut bracket: Vec<(GameMatch, Option<Team>)> = match &self.bracket_semi_final_matches {
+            Some(semis) => semis
+                .iter()
+                .map(|m| {
+                    let winner = self
+                        .bracket_semi_final_results
+                        .as_ref()
+                        .and_then(|r| r.get(&m.id))
+                        .copied();
+                    (m.clone(), winner)
+                })
+                .collect(),
+            None => live(TournamentState::SemiFinals),
+        };
+        match &self.bracket_finals_match {
+            Some(m) => bracket.push((m.clone(), self.bracket_finals_result)),
+            None => bracket.extend(live(TournamentState::Finals)),
+        }
+        match &self.bracket_grand_finals_match {
+            Some(m) => bracket.push((m.clone(), self.bracket_grand_finals_result)),
+            None => bracket.extend(live(TournamentState::GrandFinals)),
+        }
+        bracket
+            .into_iter()
+            .map(|(mut m, winner)| {
+                m.winner = m.winner.or(winner);
+                self.match_view(&m)
+            })
+            .collect()
+    }
+
+    /// Possible finals pairings for the semi-finals in play (see [`ProjectedBracket`]). Recorded
+    /// semi results are fixed; every combination of the undecided ones is listed. In Finals the
+    /// actual finals match is the only entry; in other states (or before the semi-finals are
+    /// generated) the list is empty.
+    pub fn projected_bracket(&self) -> ProjectedBracket {
+        let mut finals = Vec::new();
+        let mut undecided = Vec::new();
+        match self.state {
+            TournamentState::SemiFinals if !self.matches.is_empty() => {
+                undecided = self
+                    .matches
+                    .iter()
+                    .map(|m| m.id)
+                    .filter(|id| !self.final_match_results.contains_key(id))
+                    .collect();
+                for combination in 0..1u32 << undecided.len() {
+                    let mut semi_winners = self.final_match_results.clone();
+                    for (bit, &id) in undecided.iter().enumerate() {
+                        let team = if combination & (1 << bit) == 0 {
+                            Team::One
+                        } else {
+                            Team::Two
+                        };
+                        semi_winners.insert(id, team);
+                    }
+                    let (team_1, team_2) = self.finals_teams(&self.matches, &semi_winners);
+                    finals.push(ProjectedFinal {
+                        semi_winners,
+                        team_1,
+                        team_2,
+                    });
+                }
+            }
+            TournamentState::Finals => {
+                if let Some(m) = self.matches.first() {
+                    finals.push(ProjectedFinal {
+                        semi_winners: self.bracket_semi_final_results.clone().unwrap_or_default(),
+                        team_1: m.team_1.clone(),
+                        team_2: m.team_2.clone(),
+                    });
+                }
+            }
+            _ => {}
+        }
+        let player_names = finals
+            .iter()
+            .flat_map(|f| f.team_1.iter().chain(&f.team_2))
+            .filter_map(|&id| self.find_player_anywhere(id))
+            .map(|(p, _)| (p.id, p.name.clone()))
+            .collect();
+        ProjectedBracket {
+            state: self.state,
+            undecided,
+            finals,
+            player_names,
+        }
+    }
+
+    /// Final standings once Completed (empty before): champion(s) first, then the finals losers,
+    /// then the semi-final losers. With a grand final, 1st and 2nd are the two players of the
+    /// winning finals team. Places are dense (tied players share a place, the next tier is +1).
+    /// Group-only tournaments rank everyone: the survivors first, then the eliminated players by
+    /// the round they went out in (later is better), then by wins.
+    pub fn final_placements(&self) -> Vec<Placement> {
+        if self.state != TournamentState::Completed {
+            return Vec::new();
+        }
+        if self.format == TournamentFormat::GroupOnly {
+            return self.group_only_placements();
+        }
+        if self.format == TournamentFormat::KnockoutAll && self.bracket_finals_match.is_none() {
+            // Knocked out down to a single survivor without a finals match.
+            let mut tiers = vec![self.players.iter().map(|p| p.id).collect()];
+            tiers.extend(self.knockout_eliminated.iter().rev().cloned());
+            return Self::placements_from_tiers(tiers);
+        }
+        let sides = |m: &GameMatch, winner: Team| match winner {
+            Team::One => (m.team_1.clone(), m.team_2.clone()),
+            Team::Two => (m.team_2.clone(), m.team_1.clone()),
+        };
+        let mut tiers: Vec<Vec<PlayerId>> = Vec::new();
+        if let (Some(m), true) = (&self.bracket_finals_match, self.shared_title) {
+            tiers.push(m.team_1.iter().chain(&m.team_2).copied().collect());
+        }
+        if let (Some(m), Some(w)) = (&self.bracket_finals_match, self.bracket_finals_result) {
+            let (winners, losers) = sides(m, w);
+            match (
+                &self.bracket_grand_finals_match,
+                self.bracket_grand_finals_result,
+            ) {
+                (Some(g), Some(gw)) => {
+                    let (champion, runner_up) = sides(g, gw);
+                    tiers.push(champion);
+                    tiers.push(runner_up);
+                }
+                _ => tiers.push(winners),
+            }
+            tiers.push(losers);
+        }
+        if let (Some(semis), Some(results)) = (
+            &self.bracket_semi_final_matches,
+            &self.bracket_semi_final_results,
+        ) {
+            let semi_losers: Vec<PlayerId> = semis
+                .iter()
+                .filter_map(|m| results.get(&m.id).map(|&w| sides(m, w).1))
+                .flatten()
+                .collect();
+            tiers.push(semi_losers);
+        }
+        tiers.extend(self.knockout_eliminated.iter().rev().cloned());
+        Self::placements_from_tiers(tiers)
+    }
+
+    /// Dense places from best-first tiers, skipping empty ones.
+    fn placements_from_tiers(tiers: Vec<Vec<PlayerId>>) -> Vec<Placement> {
+        tiers
+            .into_iter()
+            .filter(|players| !players.is_empty())
+            .enumerate()
+            .map(|(i, players)| Placement {
+                place: i as u32 + 1,
+                players,
+            })
+            .collect()
+    }
+
+    /// Winners and runners-up from the final placements; `None` until Completed. A shared title
+    /// has no runners-up.
+    pub fn final_result(&self) -> Option<FinalResult> {
+        if self.state != TournamentState::Completed {
+            return None;
+        }
+        let placements = self.final_placements();
+        let summaries = |place: u32| -> Vec<PlayerSummary> {
+            placements
+                .iter()
+                .filter(|p| p.place == place)
+                .flat_map(|p| &p.players)
+                .filter_map(|&id| self.find_player_anywhere(id))
+                .map(|(p, _)| self.player_summary(p))
+                .collect()
+        };
+        Some(FinalResult {
+            completed: true,
+            winners: summaries(1),
+            runners_up: if self.shared_title {
+                Vec::new()
+            } else {
+                summaries(2)
+            },
+        })
+    }
+
+    /// [`Tournament::final_placements`] for a group-only tournament.
+    fn group_only_placements(&self) -> Vec<Placement> {
+        let mut placements = vec![Placement {
+            place: 1,
+            players: self.players.iter().map(|p| p.id).collect(),
+        }];
+        let mut eliminated: Vec<&Player> = self.eliminated_players.iter().collect();
+        eliminated.sort_by_key(|p| {
+            (
+                std::cmp::Reverse(p.eliminated_at_round),
+                std::cmp::Reverse(p.wins),
+                p.join_order,
+            )
+        });
+        let mut last_key = None;
+        for p in eliminated {
+            let key = (p.eliminated_at_round, p.wins);
+            match placements.last_mut() {
+                Some(tier) if last_key == Some(key) => tier.players.push(p.id),
+                _ => {
+                    let place = placements.len() as u32 + 1;
+                    placements.push(Placement {
+                        place,
+                        players: vec![p.id],
+                    });
+                }
+            }
+            last_key = Some(key);
+        }
+        placements.retain(|p| !p.players.is_empty());
+        for (i, p) in placements.iter_mut().enumerate() {
+            p.place = i as u32 + 1;
+        }
+        placements
+    }
+
+    /// Finish the tournament, recording when.
+    pub(crate) fn complete(&mut self) {
+        self.state = TournamentState::Completed;
+        self.completed_at = Some(epoch_millis());
+    }
+
+    /// How long the tournament took from start to completion; `None` until completed.
+    pub fn duration(&self) -> Option<Duration> {
+        let started = self.started_at?;
+        let completed = self.completed_at?;
+        Some(Duration::from_millis(completed.saturating_sub(started)))
+    }
+
+    /// The two finals teams from the semi-final `matches` and their `winners`. In 2v2 a pair that
+    /// won a semi-final together stays together; single-match winners and `semi_final_byes` fill
+    /// the remaining places in player list order.
+    pub(crate) fn finals_teams(
+        &self,
+        matches: &[GameMatch],
+        winners: &HashMap<MatchId, Team>,
+    ) -> (Vec<PlayerId>, Vec<PlayerId>) {
+        let team_size = self.mode.team_size();
+        let mut finalists: Vec<PlayerId> = Vec::new();
+        let mut loose: Vec<PlayerId> = self.semi_final_byes.clone();
+        for m in matches {
+            let side = match winners.get(&m.id) {
+                Some(Team::One) => &m.team_1,
+                Some(Team::Two) => &m.team_2,
+                None => continue,
+            };
+            if team_size > 1 && side.len() == team_size {
+                finalists.extend(side);
+            } else {
+                loose.extend(side);
+            }
+        }
+        loose.sort_by_key(|id| self.players.iter().position(|p| p.id == *id));
+        finalists.extend(loose);
+        let (team_1, team_2) = finalists.split_at(finalists.len() / 2);
+        (team_1.to_vec(), team_2.to_vec())
+    }
+
+    /// Add-back requirements for FinalSelection (same rules as `add_players_back_from_last_eliminated`).
+    pub fn final_selection_info(&self) -> Result<FinalSelectionInfo, TournamentError> {
+        if self.state != TournamentState::FinalSelection {
+            return Err(TournamentError::InvalidState);
+        }
+        Ok(FinalSelectionInfo {
+            needed: self
+                .players_required_for_semi()
+                .saturating_sub(self.players.len()),
+            candidates: self
+                .last_eliminated_players
+                .iter()
+                .map(|p| self.player_summary(p))
+                .collect(),
+            tied_at_cutoff: self.tied_at_cutoff(),
+        })
+    }
+
+    /// Final-selection candidates tied on the cutoff line: those with the same record (wins and
+    /// losses) as the last one [`crate::auto_fill_finalists`] would add back, when that record
+    /// is shared by someone it would leave out. Only sit-outs and join order separate them, so the
+    /// pick among them is arbitrary. Empty outside FinalSelection, when no slot is open, or when
+    /// the cutoff falls between two different records.
+    pub fn tied_at_cutoff(&self) -> Vec<PlayerId> {
+        if self.state != TournamentState::FinalSelection {
+            return Vec::new();
+        }
+        let needed = self
+            .players_required_for_semi()
+            .saturating_sub(self.players.len());
+        if needed == 0 || self.last_eliminated_players.len() <= needed {
+            return Vec::new();
+        }
+        let mut ranked: Vec<&Player> = self.last_eliminated_players.iter().collect();
+        ranked.sort_by_key(|p| record_rank(p));
+        let record = |p: &Player| (p.wins, p.losses);
+        let cutoff = record(ranked[needed - 1]);
+        if record(ranked[needed]) != cutoff {
+            return Vec::new();
+        }
+        ranked
+            .into_iter()
+            .filter(|p| record(p) == cutoff)
+            .map(|p| p.id)
+            .collect()
+    }
+
+    /// Explain an error from `add_players_back_from_last_eliminated` in terms of this tournament's
+    /// eligible players. `None` for errors that are not about the selection itself.
+    pub fn add_back_rejection(&self, error: &TournamentError) -> Option<AddBackRejection> {
+        let eligible_ids = self.last_eliminated_players.iter().map(|p| p.id).collect();
+        match *error {
+            TournamentError::WrongNumberOfPlayers { needed, selected } => {
+                Some(AddBackRejection::WrongCount {
+                    needed,
+                    selected,
+                    eligible_ids,
+                })
+            }
+            TournamentError::PlayerNotInLastEliminated(player_id) => {
+                Some(AddBackRejection::IneligiblePlayer {
+                    player_id,
+                    eligible_ids,
+                })
+            }
+            _ => None,
+        }
+    }
+
+    /// Fallback for FinalSelection when `last_eliminated_players` cannot fill the semi-finals:
+    /// add selected players back from the full `eliminated_players` pool.
+    /// Must select exactly (required - players.len()) players; state becomes SemiFinals when reached.
+    pub fn add_back_from_all_eliminated(
+        &mut self,
+        player_ids: &[PlayerId],
+    ) -> Result<(), TournamentError> {
+        if self.state != TournamentState::FinalSelection {
+            return Err(TournamentError::InvalidState);
+        }
+        let required = self.players_required_for_semi();
+        let current = self.players.len();
+        if current >= required {
+            return Err(TournamentError::InvalidState);
+        }
+        let needed = required - current;
+        // Use the regular add-back while the last eliminated round can still fill the field.
+        if self.last_eliminated_players.len() >= needed {
+            return Err(TournamentError::InvalidState);
+        }
+        if self.eliminated_players.len() < needed {
+            return Err(TournamentError::NotEnoughEliminatedPlayers {
+                needed,
+                available: self.eliminated_players.len(),
+            });
+        }
+        let ids_to_add: HashSet<PlayerId> = player_ids.iter().copied().collect();
+        if ids_to_add.len() != needed {
+            return Err(TournamentError::WrongNumberOfPlayers {
+                needed,
+                selected: ids_to_add.len(),
+            });
+        }
+        for &id in player_ids {
+            if !self.eliminated_players.iter().any(|p| p.id == id) {
+                return Err(TournamentError::PlayerNotEliminated(id));
+            }
+        }
+
+        let (mut to_add, remaining): (Vec<_>, Vec<_>) =
+            std::mem::take(&mut self.eliminated_players)
+                .into_iter()
+                .partition(|p| ids_to_add.contains(&p.id));
+        self.eliminated_players = remaining;
+        self.last_eliminated_players
+            .retain(|p| !ids_to_add.contains(&p.id));
+        for p in &mut to_add {
+            p.reinstate();
+        }
+        self.players.append(&mut to_add);
+
+        if self.players.len() == required {
+            self.state = TournamentState::SemiFinals;
         }
         Ok(())
     }
@@ -0,0 +1,1662 @@
+//! Hand-maintained OpenAPI 3 description of the web API, served at `GET /api/openapi.json`.
+//!
+//! [`ENDPOINTS`] lists every route registered by the web binary; `tests/openapi.rs` checks it
+//! against the route attributes in `src/bin/web.rs`, so adding an endpoint without documenting it
+//! fails the tests. Request body and response shapes are described in [`schemas`].
+
+use serde_json::{json, Map, Value};
+
+/// One documented route.
+#[derive(Clone, Copy, Debug)]
+pub struct Endpoint {
+    /// Lowercase HTTP method (`get`, `post`, `put`, `delete`).
+    pub method: &'static str,
+    /// Path as registered, with `{id}`-style parameters.
+    pub path: &'static str,
+    pub summary: &'static str,
+    /// Schema name of the JSON request body, if any (`Csv` for a `text/csv` body).
+    pub body: Option<&'static str>,
+    /// Schema name of the 200 response (`Svg` for the bracket image, `Html` for the results sheet,
+    /// `EventStream` for server-sent events, `None` for empty responses).
+    pub response: &'static str,
+}
+
+const fn endpoint(
+    method: &'static str,
+    path: &'static str,
+    summary: &'static str,
+    body: Option<&'static str>,
+    response: &'static str,
+) -> Endpoint {
+    Endpoint {
+        method,
+        path,
+        summary,
+        body,
+        response,
+    }
+}
+
+/// Every API route, in registration order.
+pub const ENDPOINTS: &[Endpoint] = &[
+    endpoint("get", "/api/health", "Health check", None, "Health"),
+    endpoint("get", "/favicon.ico", "Empty favicon (204)", None, "None"),
+    endpoint(
+        "get",
+        "/api/site-gate/check",
+        "204 if the site gate header is valid",
+        None,
+        "None",
+    ),
+    endpoint(
+        "post",
+        "/api/site-gate",
+        "Unlock the site with the password",
+        Some("SiteGateLoginBody"),
+        "SiteGateToken",
+    ),
+    endpoint("get", "/api/openapi.json", "This document", None, "OpenApi"),
+    endpoint(
+        "get",
+        "/api/tournaments",
+        "Every tournament, most recently active first (?state=&mode= filter; X-Dart-Admin-Token)",
+        None,
+        "TournamentSummaries",
+    ),
+    endpoint(
+        "post",
+        "/api/tournaments",
+        "Create a tournament",
+        Some("CreateTournamentBody"),
+        "Tournament",
+    ),
+    endpoint(
+        "post",
+        "/api/tournaments/quickstart",
+        "Create a tournament, add players and start it",
+        Some("QuickstartBody"),
+        "QuickstartTournament",
+    ),
+    endpoint(
+        "get",
+        "/api/tournaments/{id}",
+        "Get a tournament",
+        None,
+        "Tournament",
+    ),
+    endpoint(
+        "get",
+        "/api/tournaments/{id}/history",
+        "Paginated match history (?offset=&limit=)",
+        None,
+        "HistoryPage",
+    ),
+    endpoint(
+        "get",
+        "/api/tournaments/{id}/matches/current",
+        "Current round only (?names=true adds match_views with names inline)",
+        None,
+        "CurrentRoundView",
+    ),
+    endpoint(
+        "get",
+        "/api/tournaments/{id}/eliminated",
+        "Eliminated players with the round they went out in",
+        None,
+        "EliminatedPlayers",
+    ),
+    endpoint(
+        "post",
+        "/api/tournaments/{id}/share",
+        "Create a read-only share link",
+        None,
+        "ShareLink",
+    ),
+    endpoint(
+        "delete",
+        "/api/tournaments/{id}/share",
+        "Revoke the share link",
+        None,
+        "None",
+    ),
+    endpoint(
+        "get",
+        "/api/share/{share_id}",
+        "Read-only tournament through a share link",
+        None,
+        "SharedTournament",
+    ),
+    endpoint(
+        "get",
+        "/api/tournaments/{id}/result",
+        "Whether the tournament is over and who won",
+        None,
+        "FinalResult",
+    ),
+    endpoint(
+        "get",
+        "/api/tournaments/{id}/projected-finalists",
+        "Who would reach the semi-finals if group play ended now",
+        None,
+        "PlayerSummaries",
+    ),
+    endpoint(
+        "get",
+        "/api/tournaments/{id}/config",
+        "Every setting in one object",
+        None,
+        "TournamentConfig",
+    ),
+    endpoint(
+        "put",
+        "/api/tournaments/{id}/config",
+        "Replace every setting at once (Setup only)",
+        Some("TournamentConfig"),
+        "Tournament",
+    ),
+    endpoint(
+        "get",
+        "/api/tournaments/{id}/counts",
+        "Player list sizes, max losses and state",
+        None,
+        "Counts",
+    ),
+    endpoint(
+        "get",
+        "/api/tournaments/{id}/validate",
+        "Consistency diagnostics: broken invariants (empty when healthy)",
+        None,
+        "InvariantViolations",
+    ),
+    endpoint(
+        "get",
+        "/api/tournaments/{id}/team-standings",
+        "2v2 leaderboard of the pairings that played together",
+        None,
+        "TeamStandings",
+    ),
+    endpoint(
+        "get",
+        "/api/tournaments/{id}/projection",
+        "Possible finals pairings from the semi-final results so far",
+        None,
+        "ProjectedBracket",
+    ),
+    endpoint(
+        "get",
+        "/api/tournaments/{id}/bracket",
+        "Playoff matches so far, with names inline",
+        None,
+        "GameMatchViews",
+    ),
+    endpoint(
+        "get",
+        "/api/tournaments/{id}/bracket.svg",
+        "Printable bracket",
+        None,
+        "Svg",
+    ),
+    endpoint(
+        "get",
+        "/api/tournaments/{id}/results.html",
+        "Printable results sheet",
+        None,
+        "Html",
+    ),
+    endpoint(
+        "get",
+        "/api/tournaments/{id}/events",
+        "Server-sent events with the tournament JSON after every change",
+        None,
+        "EventStream",
+    ),
+    endpoint(
+        "post",
+        "/api/tournaments/{id}/players",
+        "Add a player",
+        Some("AddPlayerBody"),
+        "Tournament",
+    ),
+    endpoint(
+        "get",
+        "/api/tournaments/{id}/players/{player_id}",
+        "One player's record",
+        None,
+        "PlayerRecord",
+    ),
+    endpoint(
+        "get",
+        "/api/tournaments/{id}/players/{player_id}/matches",
+        "Every match a player has been in, with whether they won",
+        None,
+        "PlayerMatches",
+    ),
+    endpoint(
+        "delete",
+        "/api/tournaments/{id}/players/{player_id}",
+        "Remove a player (Setup)",
+        None,
+        "Tournament",
+    ),
+    endpoint(
+        "put",
+        "/api/tournaments/{id}/players/order",
+        "Reorder the roster (Setup)",
+        Some("PlayerIdsBody"),
+        "Tournament",
+    ),
+    endpoint(
+        "put",
+        "/api/tournaments/{id}/max-losses",
+        "Set max losses",
+        Some("MaxLossesBody"),
+        "Tournament",
+    ),
+    endpoint(
+        "post",
+        "/api/tournaments/{id}/start",
+        "Start group play",
+        None,
+        "StartedTournament",
+    ),
+    endpoint(
+        "post",
+        "/api/tournaments/{id}/matches/generate",
+        "Generate a group play round",
+        None,
+        "Tournament",
+    ),
+    endpoint(
+        "post",
+        "/api/tournaments/{id}/matches/practice",
+        "Generate a practice round",
+        None,
+        "Tournament",
+    ),
+    endpoint(
+        "put",
+        "/api/tournaments/{id}/matches/winner",
+        "Set a group play match outcome",
+        Some("SetMatchOutcomeBody"),
+        "Tournament",
+    ),
+    endpoint(
+        "post",
+        "/api/tournaments/{id}/matches/simulate",
+        "Preview what a group play match outcome would do",
+        Some("SetMatchOutcomeBody"),
+        "SimResult",
+    ),
+    endpoint(
+        "post",
+        "/api/tournaments/{id}/matches/swap-players",
+        "Swap two players between the current round's teams",
+        Some("SwapPlayersBody"),
+        "Tournament",
+    ),
+    endpoint(
+        "post",
+        "/api/tournaments/{id}/pause",
+        "Pause the tournament",
+        None,
+        "Tournament",
+    ),
+    endpoint(
+        "post",
+        "/api/tournaments/{id}/resume",
+        "Resume the tournament",
+        None,
+        "Tournament",
+    ),
+    endpoint(
+        "put",
+        "/api/tournaments/{id}/draws-allowed",
+        "Allow or forbid draws",
+        Some("SetDrawsAllowedBody"),
+        "Tournament",
+    ),
+    endpoint(
+        "delete",
+        "/api/tournaments/{id}/matches/{match_id}/result",
+        "Void an entered result",
+        None,
+        "Tournament",
+    ),
+    endpoint(
+        "put",
+        "/api/tournaments/{id}/history/{match_id}/result",
+        "Correct a submitted group play result",
+        Some("AmendResultBody"),
+        "Tournament",
+    ),
+    endpoint(
+        "post",
+        "/api/tournaments/{id}/matches/submit",
+        "Submit the group play round",
+        None,
+        "SubmittedRound",
+    ),
+    endpoint(
+        "put",
+        "/api/tournaments/{id}/players/{player_id}/losses",
+        "Set a player's losses",
+        Some("SetPlayerLossesBody"),
+        "Tournament",
+    ),
+    endpoint(
+        "put",
+        "/api/tournaments/{id}/players/{player_id}/rating",
+        "Set a player's rating",
+        Some("SetPlayerRatingBody"),
+        "Tournament",
+    ),
+    endpoint(
+        "put",
+        "/api/tournaments/{id}/players/{player_id}/appearance",
+        "Set a player's color and avatar",
+        Some("SetPlayerAppearanceBody"),
+        "Tournament",
+    ),
+    endpoint(
+        "put",
+        "/api/tournaments/{id}/players/{player_id}/handicap-spot",
+        "Set a player's handicap spot",
+        Some("SetHandicapSpotBody"),
+        "Tournament",
+    ),
+    endpoint(
+        "post",
+        "/api/tournaments/{id}/players/{player_id}/eliminate",
+        "Eliminate a player",
+        None,
+        "Tournament",
+    ),
+    endpoint(
+        "post",
+        "/api/tournaments/{id}/players/{player_id}/forfeit",
+        "Record a player's forfeit",
+        None,
+        "Tournament",
+    ),
+    endpoint(
+        "post",
+        "/api/tournaments/{id}/players/{player_id}/sit-out",
+        "Volunteer a player to sit out the next round",
+        None,
+        "Tournament",
+    ),
+    endpoint(
+        "post",
+        "/api/tournaments/{id}/players/import-csv",
+        "Import players from name[,rating] CSV rows",
+        Some("Csv"),
+        "RosterImportResult",
+    ),
+    endpoint(
+        "post",
+        "/api/tournaments/{id}/players/import-stats",
+        "Import players with existing wins, losses and sit-outs",
+        Some("ImportPlayersWithStatsBody"),
+        "StatsImportResult",
+    ),
+    endpoint(
+        "post",
+        "/api/tournaments/{id}/players/eliminate-bulk",
+        "Eliminate several players (all or nothing)",
+        Some("EliminateBulkBody"),
+        "Tournament",
+    ),
+    endpoint(
+        "put",
+        "/api/tournaments/{id}/mode",
+        "Set 1v1 or 2v2",
+        Some("SetModeBody"),
+        "Tournament",
+    ),
+    endpoint(
+        "put",
+        "/api/tournaments/{id}/balance-teams",
+        "Toggle balanced 2v2 teams",
+        Some("SetBalanceTeamsBody"),
+        "Tournament",
+    ),
+    endpoint(
+        "put",
+        "/api/tournaments/{id}/shuffle-match-order",
+        "Toggle shuffled match order",
+        Some("SetShuffleMatchOrderBody"),
+        "Tournament",
+    ),
+    endpoint(
+        "put",
+        "/api/tournaments/{id}/auto-generate-next",
+        "Toggle generating the next round on submit",
+        Some("SetAutoGenerateNextBody"),
+        "Tournament",
+    ),
+    endpoint(
+        "put",
+        "/api/tournaments/{id}/grace-matches",
+        "Matches a player plays before they can be eliminated",
+        Some("SetGraceMatchesBody"),
+        "Tournament",
+    ),
+    endpoint(
+        "put",
+        "/api/tournaments/{id}/auto-start-semis",
+        "Skip final selection when exactly the semi-final size remains",
+        Some("SetAutoStartSemisBody"),
+        "Tournament",
+    ),
+    endpoint(
+        "put",
+        "/api/tournaments/{id}/allow-ghost",
+        "Let one player play twice instead of three sitting out in 2v2",
+        Some("SetAllowGhostBody"),
+        "Tournament",
+    ),
+    endpoint(
+        "put",
+        "/api/tournaments/{id}/strict-bye-rotation",
+        "Toggle strict bye rotation",
+        Some("SetStrictByeRotationBody"),
+        "Tournament",
+    ),
+    endpoint(
+        "put",
+        "/api/tournaments/{id}/deterministic",
+        "Toggle deterministic (shuffle-free) match generation",
+        Some("SetDeterministicBody"),
+        "Tournament",
+    ),
+    endpoint(
+        "put",
+        "/api/tournaments/{id}/single-champion",
+        "Toggle the single-champion grand final",
+        Some("SetSingleChampionBody"),
+        "Tournament",
+    ),
+    endpoint(
+        "put",
+        "/api/tournaments/{id}/champion-name",
+        "Name the winning finals team",
+        Some("SetChampionNameBody"),
+        "Tournament",
+    ),
+    endpoint(
+        "put",
+        "/api/tournaments/{id}/finals-format",
+        "Set the finals format",
+        Some("SetFinalsFormatBody"),
+        "Tournament",
+    ),
+    endpoint(
+        "put",
+        "/api/tournaments/{id}/format",
+        "Choose a tournament with finals or group play only",
+        Some("SetFormatBody"),
+        "Tournament",
+    ),
+    endpoint(
+        "put",
+        "/api/tournaments/{id}/forfeit-policy",
+        "Set what a forfeit does",
+        Some("SetForfeitPolicyBody"),
+        "Tournament",
+    ),
+    endpoint(
+        "put",
+        "/api/tournaments/{id}/seeding",
+        "Set the semi-final seeding",
+        Some("SetSeedingBody"),
+        "Tournament",
+    ),
+    endpoint(
+        "put",
+        "/api/tournaments/{id}/num-boards",
+        "Set the number of boards",
+        Some("SetNumBoardsBody"),
+        "Tournament",
+    ),
+    endpoint(
+        "put",
+        "/api/tournaments/{id}/max-concurrent-matches",
+        "Cap the group play matches per round",
+        Some("SetMaxConcurrentMatchesBody"),
+        "Tournament",
+    ),
+    endpoint(
+        "put",
+        "/api/tournaments/{id}/reshuffle-every",
+        "How many rounds 2v2 teams stay together",
+        Some("SetReshuffleEveryBody"),
+        "Tournament",
+    ),
+    endpoint(
+        "put",
+        "/api/tournaments/{id}/knockout-after-rounds",
+        "Group play rounds before the knockout",
+        Some("SetKnockoutAfterRoundsBody"),
+        "Tournament",
+    ),
+    endpoint(
+        "put",
+        "/api/tournaments/{id}/auto-submit-after",
+        "Kiosk mode: submit a fully entered round after a countdown",
+        Some("SetAutoSubmitAfterBody"),
+        "Tournament",
+    ),
+    endpoint(
+        "put",
+        "/api/tournaments/{id}/round-cap",
+        "Group play rounds before the finals field is filled by record",
+        Some("SetRoundCapBody"),
+        "Tournament",
+    ),
+    endpoint(
+        "post",
+        "/api/tournaments/{id}/restart",
+        "Restart with the same roster",
+        None,
+        "Tournament",
+    ),
+    endpoint(
+        "post",
+        "/api/tournaments/{id}/reset",
+        "Restart, or return to group play keeping stats",
+        Some("ResetBody"),
+        "Tournament",
+    ),
+    endpoint(
+        "put",
+        "/api/tournaments/{id}/scoring-mode",
+        "Enter a round type's results as a bare winner or leg by leg",
+        Some("SetScoringModeBody"),
+        "Tournament",
+    ),
+    endpoint(
+        "get",
+        "/api/tournaments/{id}/finals/seeding",
+        "The semi-final draw order, with names",
+        None,
+        "PlayerSummaries",
+    ),
+    endpoint(
+        "get",
+        "/api/tournaments/{id}/final-selection/candidates",
+        "Add-back requirements",
+        None,
+        "FinalSelectionInfo",
+    ),
+    endpoint(
+        "post",
+        "/api/tournaments/{id}/final-selection/add-back",
+        "Add back last eliminated players",
+        Some("PlayerIdsBody"),
+        "Tournament",
+    ),
+    endpoint(
+        "post",
+        "/api/tournaments/{id}/final-selection/add-back-any",
+        "Add back any eliminated players",
+        Some("PlayerIdsBody"),
+        "Tournament",
+    ),
+    endpoint(
+        "post",
+        "/api/tournaments/{id}/final-selection/auto-fill",
+        "Add back the best records from the last eliminated round",
+        None,
+        "Tournament",
+    ),
+    endpoint(
+        "post",
+        "/api/tournaments/{id}/final-selection/start-semi",
+        "Start the semi-finals",
+        None,
+        "Tournament",
+    ),
+    endpoint(
+        "post",
+        "/api/tournaments/{id}/grand-finals/start",
+        "Play a 1v1 grand final between the winning finals pair",
+        None,
+        "Tournament",
+    ),
+    endpoint(
+        "post",
+        "/api/tournaments/{id}/redemption/matches",
+        "Generate the redemption round",
+        None,
+        "Tournament",
+    ),
+    endpoint(
+        "post",
+        "/api/tournaments/{id}/redemption/submit",
+        "Submit the redemption round",
+        None,
+        "Tournament",
+    ),
+    endpoint(
+        "post",
+        "/api/tournaments/{id}/finals/matches",
+        "Generate the semi-finals",
+        None,
+        "Tournament",
+    ),
+    endpoint(
+        "post",
+        "/api/tournaments/{id}/knockout/matches",
+        "Generate the next knockout round",
+        None,
+        "Tournament",
+    ),
+    endpoint(
+        "put",
+        "/api/tournaments/{id}/finals/winner",
+        "Set a final-round match winner",
+        Some("SetMatchWinnerBody"),
+        "Tournament",
+    ),
+    endpoint(
+        "put",
+        "/api/tournaments/{id}/finals/legs",
+        "Record the next leg of a series",
+        Some("SetMatchWinnerBody"),
+        "Tournament",
+    ),
+    endpoint(
+        "put",
+        "/api/tournaments/{id}/legs-per-final-match",
+        "Set legs per final series",
+        Some("SetLegsPerFinalMatchBody"),
+        "Tournament",
+    ),
+    endpoint(
+        "put",
+        "/api/tournaments/{id}/finals/draw",
+        "Record the finals match as drawn",
+        Some("FinalsDrawBody"),
+        "Tournament",
+    ),
+    endpoint(
+        "put",
+        "/api/tournaments/{id}/finals/sudden-death-players",
+        "Pick who plays the sudden-death decider",
+        Some("SuddenDeathPlayersBody"),
+        "Tournament",
+    ),
+    endpoint(
+        "put",
+        "/api/tournaments/{id}/final-tie-resolution",
+        "Set what a drawn final leads to",
+        Some("SetFinalTieResolutionBody"),
+        "Tournament",
+    ),
+    endpoint(
+        "post",
+        "/api/tournaments/{id}/finals/submit",
+        "Submit the current final round",
+        None,
+        "Tournament",
+    ),
+    endpoint(
+        "post",
+        "/api/tournaments/{id}/advance",
+        "Do the next natural step",
+        None,
+        "SubmittedRound",
+    ),
+];
+
+/// The full OpenAPI 3 document.
+pub fn openapi_document() -> Value {
+    let mut paths = Map::new();
+    for e in ENDPOINTS {
+        let item = paths
+            .entry(e.path)
+            .or_insert_with(|| json!({}))
+            .as_object_mut()
+            .expect("path item is an object");
+        item.insert(e.method.to_string(), operation(e));
+    }
+    json!({
+        "openapi": "3.0.3",
+        "info": {
+            "title": "Dart tournament API",
+            "version": env!("CARGO_PKG_VERSION"),
+        },
+        "paths": paths,
+        "components": {
+            "schemas": schemas(),
+            "parameters": {
+                "IdempotencyKey": {
+                    "name": "Idempotency-Key",
+                    "in": "header",
+                    "required": false,
+                    "description": "Repeated keys on the same method and path replay the cached response instead of re-running the action.",
+                    "schema": { "type": "string" },
+                },
+                "IfMatch": {
+                    "name": "If-Match",
+                    "in": "header",
+                    "required": false,
+                    "description": "Tournament version the change is based on; a stale version is rejected with 409.",
+                    "schema": { "type": "string" },
+                },
+                "ExpectedVersion": {
+                    "name": "expected_version",
+                    "in": "query",
+                    "required": false,
+                    "description": "Alternative to If-Match.",
+                    "schema": { "type": "integer", "format": "int64", "minimum": 0 },
+                },
+            },
+        },
+    })
+}
+
+fn operation(e: &Endpoint) -> Value {
+    let mut parameters: Vec<Value> = path_params(e.path)
+        .map(|name| {
+            json!({
+                "name": name,
+                "in": "path",
+                "required": true,
+                "schema": { "type": "string", "format": "uuid" },
+            })
+        })
+        .collect();
+    if e.method != "get" {
+        parameters.push(json!({ "$ref": "#/components/parameters/IdempotencyKey" }));
+        parameters.push(json!({ "$ref": "#/components/parameters/IfMatch" }));
+        parameters.push(json!({ "$ref": "#/components/parameters/ExpectedVersion" }));
+    }
+    let ok = match e.response {
+        "None" => json!({ "description": "No content" }),
+        "Svg" => json!({
+            "description": "SVG image",
+            "content": { "image/svg+xml": { "schema": { "type": "string" } } },
+        }),
+        "Html" => json!({
+            "description": "HTML fragment",
+            "content": { "text/html": { "schema": { "type": "string" } } },
+        }),
+        "EventStream" => json!({
+            "description": "Event stream: a snapshot on connect, then one `data:` event per change",
+            "content": { "text/event-stream": { "schema": { "type": "string" } } },
+        }),
+        name => json!({
+            "description": "OK",
+            "content": { "application/json": { "schema": schema_ref(name) } },
+        }),
+    };
+    let mut op = json!({
+        "summary": e.summary,
+        "parameters": parameters,
+        "responses": {
+            "200": ok,
+            "400": error_response("Invalid request for the tournament's state"),
+            "404": error_response("No tournament"),
+        },
+    });
+    if e.method != "get" {
+        op["responses"]["409"] =
+            error_response("The tournament changed since the expected version");
+    }
+    if let Some(body) = e.body {
+        let media_type = if body == "Csv" {
+            "text/csv"
+        } else {
+            "application/json"
+        };
+        op["requestBody"] = json!({
+            "required": true,
+            "content": { media_type: { "schema": schema_ref(body) } },
+        });
+    }
+    op
+}
+
+/// Names of the `{param}` segments in a path.
+fn path_params(path: &str) -> impl Iterator<Item = &str> {
+    path.split('/')
+        .filter_map(|s| s.strip_prefix('{').and_then(|s| s.strip_suffix('}')))
+}
+
+fn schema_ref(name: &str) -> Value {
+    json!({ "$ref": format!("#/components/schemas/{name}") })
+}
+
+fn error_response(description: &str) -> Value {
+    json!({
+        "description": description,
+        "content": { "application/json": { "schema": schema_ref("Error") } },
+    })
+}
+
+/// Object schema with the given properties; `required` lists the mandatory ones.
+fn object(properties: Value, required: &[&str]) -> Value {
+    json!({ "type": "object", "properties": properties, "required": required })
+}
+
+fn string_enum(values: &[&str]) -> Value {
+    json!({ "type": "string", "enum": values })
+}
+
+/// Component schemas: request bodies, responses and the core models.
+pub fn schemas() -> Value {
+    let uuid = json!({ "type": "string", "format": "uuid" });
+    let uuids = json!({ "type": "array", "items": uuid });
+    let int = json!({ "type": "integer" });
+    let boolean = json!({ "type": "boolean" });
+    let team = string_enum(&["one", "two"]);
+    let mode = string_enum(&["1v1", "2v2"]);
+    let finals_format = string_enum(&["standard", "small_field"]);
+    let forfeit_policy = string_enum(&["immediate_elimination", "counts_as_loss"]);
+    let format = string_enum(&["with_finals", "group_only", "knockout_all"]);
+    let seeding = string_enum(&["random", "snake_by_wins", "snake_by_rating"]);
+    let final_tie_resolution = string_enum(&["replay", "sudden_death", "shared_title"]);
+    let state = string_enum(&[
+        "setup",
+        "group_play",
+        "redemption",
+        "final_selection",
+        "semi_finals",
+        "finals",
+        "grand_finals",
+        "knockout",
+        "completed",
+    ]);
+    let players = json!({ "type": "array", "items": schema_ref("Player") });
+    let matches = json!({ "type": "array", "items": schema_ref("GameMatch") });
+
+    let mut schemas = Map::new();
+    schemas.insert(
+        "Error".into(),
+        object(
+            json!({
+                "error": { "type": "string" },
+                "reason": {
+                    "description": "Why a final-selection add-back was rejected.",
+                    "type": "string",
+                    "enum": ["wrong_count", "ineligible_player"],
+                },
+                "needed": int,
+                "selected": int,
+                "player_id": uuid,
+                "eligible_ids": uuids,
+            }),
+            &["error"],
+        ),
+    );
+    schemas.insert(
+        "Health".into(),
+        object(
+            json!({ "ok": boolean, "service": { "type": "string" } }),
+            &["ok", "service"],
+        ),
+    );
+    schemas.insert(
+        "SiteGateLoginBody".into(),
+        object(json!({ "password": { "type": "string" } }), &["password"]),
+    );
+    schemas.insert(
+        "SiteGateToken".into(),
+        object(json!({ "token": { "type": "string" } }), &["token"]),
+    );
+    schemas.insert(
+        "ResetBody".into(),
+        object(
+            json!({ "keep_stats": boolean, "reactivate_eliminated": boolean }),
+            &["keep_stats"],
+        ),
+    );
+    schemas.insert(
+        "SetNumBoardsBody".into(),
+        object(
+            json!({ "num_boards": { "type": "integer", "nullable": true } }),
+            &["num_boards"],
+        ),
+    );
+    schemas.insert(
+        "SetMaxConcurrentMatchesBody".into(),
+        object(
+            json!({ "max_concurrent_matches": { "type": "integer", "nullable": true } }),
+            &["max_concurrent_matches"],
+        ),
+    );
+    schemas.insert(
+        "SetReshuffleEveryBody".into(),
+        object(json!({ "reshuffle_every": int }), &["reshuffle_every"]),
+    );
+    schemas.insert(
+        "SetKnockoutAfterRoundsBody".into(),
+        object(
+            json!({ "knockout_after_rounds": int }),
+            &["knockout_after_rounds"],
+        ),
+    );
+    schemas.insert(
+        "SetAutoSubmitAfterBody".into(),
+        object(
+            json!({ "auto_submit_after_secs": { "type": "integer", "nullable": true } }),
+            &["auto_submit_after_secs"],
+        ),
+    );
+    schemas.insert(
+        "SetRoundCapBody".into(),
+        object(
+            json!({ "round_cap": { "type": "integer", "nullable": true } }),
+            &["round_cap"],
+        ),
+    );
+    schemas.insert("OpenApi".into(), json!({ "type": "object" }));
+    schemas.insert(
+        "QuickstartBody".into(),
+        object(
+            json!({
+                "names": { "type": "array", "items": { "type": "string" } },
+                "max_losses": int,
+                "mode": mode,
+            }),
+            &["names"],
+        ),
+    );
+    schemas.insert(
+        "QuickstartTournament".into(),
+        json!({
+            "allOf": [
+                schema_ref("Tournament"),
+                object(
+                    json!({ "rejected": { "type": "array", "items": object(
+                        json!({ "name": { "type": "string" }, "error": { "type": "string" } }),
+                        &["name", "error"],
+                    ) } }),
+                    &["rejected"],
+                ),
+            ],
+        }),
+    );
+    schemas.insert(
+        "CreateTournamentBody".into(),
+        object(
+            json!({
+                "max_losses": int,
+                "mode": mode,
+                "balance_teams": boolean,
+                "finals_format": finals_format,
+                "format": format,
+                "forfeit_policy": forfeit_policy,
+                "shuffle_match_order": boolean,
+                "auto_generate_next": boolean,
+                "strict_bye_rotation": boolean,
+                "deterministic": boolean,
+                "single_champion": boolean,
+                "draws_allowed": boolean,
+                "seeding": seeding,
+                "points_config": schema_ref("PointsConfig"),
+                "redemption": boolean,
+                "legs_per_final_match": int,
+                "num_boards": { "type": "integer", "minimum": 1 },
+                "max_concurrent_matches": { "type": "integer", "minimum": 1 },
+                "reshuffle_every": { "type": "integer", "minimum": 1 },
+                "knockout_after_rounds": { "type": "integer", "minimum": 1 },
+                "grace_matches": int,
+                "round_cap": { "type": "integer", "minimum": 1 },
+                "auto_start_semis": boolean,
+                "allow_ghost": boolean,
+            }),
+            &[],
+        ),
+    );
+    schemas.insert(
+        "AddPlayerBody".into(),
+        object(
+            json!({
+                "name": { "type": "string" },
+                "rating": int,
+                "color": { "type": "string", "pattern": "^#([0-9a-fA-F]{3}|[0-9a-fA-F]{6})$" },
+                "avatar_url": { "type": "string" },
+            }),
+            &["name"],
+        ),
+    );
+    schemas.insert(
+        "MaxLossesBody".into(),
+        object(json!({ "max_losses": int }), &["max_losses"]),
+    );
+    schemas.insert(
+        "SetMatchOutcomeBody".into(),
+        object(
+            json!({
+                "match_id": uuid,
+                "match_number": int,
+                "team": string_enum(&["one", "two", "draw"]),
+            }),
+            &["team"],
+        ),
+    );
+    schemas.insert(
+        "SwapPlayersBody".into(),
+        object(
+            json!({ "player_a": uuid, "player_b": uuid }),
+            &["player_a", "player_b"],
+        ),
+    );
+    schemas.insert(
+        "AmendResultBody".into(),
+        object(json!({ "team": team }), &["team"]),
+    );
+    schemas.insert(
+        "SetMatchWinnerBody".into(),
+        object(
+            json!({ "match_id": uuid, "match_number": int, "team": team }),
+            &["team"],
+        ),
+    );
+    schemas.insert(
+        "FinalsDrawBody".into(),
+        object(json!({ "match_id": uuid, "match_number": int }), &[]),
+    );
+    schemas.insert(
+        "SuddenDeathPlayersBody".into(),
+        object(
+            json!({ "team_1_player": uuid, "team_2_player": uuid }),
+            &["team_1_player", "team_2_player"],
+        ),
+    );
+    schemas.insert(
+        "SetDrawsAllowedBody".into(),
+        object(json!({ "draws_allowed": boolean }), &["draws_allowed"]),
+    );
+    schemas.insert(
+        "SetPlayerLossesBody".into(),
+        object(json!({ "losses": int }), &["losses"]),
+    );
+    schemas.insert(
+        "SetPlayerAppearanceBody".into(),
+        object(
+            json!({
+                "color": { "type": "string", "nullable": true },
+                "avatar_url": { "type": "string", "nullable": true },
+            }),
+            &[],
+        ),
+    );
+    schemas.insert(
+        "SetPlayerRatingBody".into(),
+        object(
+            json!({ "rating": { "type": "integer", "nullable": true } }),
+            &["rating"],
+        ),
+    );
+    schemas.insert(
+        "SetHandicapSpotBody".into(),
+        object(json!({ "handicap_spot": int }), &["handicap_spot"]),
+    );
+    schemas.insert(
+        "EliminateBulkBody".into(),
+        object(json!({ "player_ids": uuids }), &["player_ids"]),
+    );
+    schemas.insert(
+        "PlayerIdsBody".into(),
+        object(json!({ "player_ids": uuids }), &["player_ids"]),
+    );
+    schemas.insert(
+        "SetModeBody".into(),
+        object(json!({ "mode": mode }), &["mode"]),
+    );
+    schemas.insert(
+        "SetBalanceTeamsBody".into(),
+        object(json!({ "balance_teams": boolean }), &["balance_teams"]),
+    );
+    schemas.insert(
+        "SetSingleChampionBody".into(),
+        object(json!({ "single_champion": boolean }), &["single_champion"]),
+    );
+    schemas.insert(
+        "SetChampionNameBody".into(),
+        object(
+            json!({ "champion_team_name": { "type": "string", "nullable": true } }),
+            &["champion_team_name"],
+        ),
+    );
+    schemas.insert(
+        "SetDeterministicBody".into(),
+        object(json!({ "deterministic": boolean }), &["deterministic"]),
+    );
+    schemas.insert(
+        "SetShuffleMatchOrderBody".into(),
+        object(
+            json!({ "shuffle_match_order": boolean }),
+            &["shuffle_match_order"],
+        ),
+    );
+    schemas.insert(
+        "SetAutoGenerateNextBody".into(),
+        object(
+            json!({ "auto_generate_next": boolean }),
+            &["auto_generate_next"],
+        ),
+    );
+    schemas.insert(
+        "SetGraceMatchesBody".into(),
+        object(json!({ "grace_matches": int }), &["grace_matches"]),
+    );
+    schemas.insert(
+        "SetAutoStartSemisBody".into(),
+        object(
+            json!({ "auto_start_semis": boolean }),
+            &["auto_start_semis"],
+        ),
+    );
+    schemas.insert(
+        "SetAllowGhostBody".into(),
+        object(json!({ "allow_ghost": boolean }), &["allow_ghost"]),
+    );
+    schemas.insert(
+        "SetStrictByeRotationBody".into(),
+        object(
+            json!({ "strict_bye_rotation": boolean }),
+            &["strict_bye_rotation"],
+        ),
+    );
+    schemas.insert(
+        "SetFinalsFormatBody".into(),
+        object(
+            json!({ "finals_format": finals_format }),
+            &["finals_format"],
+        ),
+    );
+    schemas.insert(
+        "SetFormatBody".into(),
+        object(json!({ "format": format }), &["format"]),
+    );
+    schemas.insert(
+        "SetForfeitPolicyBody".into(),
+        object(
+            json!({ "forfeit_policy": forfeit_policy }),
+            &["forfeit_policy"],
+        ),
+    );
+    schemas.insert(
+        "SetSeedingBody".into(),
+        object(json!({ "seeding": seeding }), &["seeding"]),
+    );
+    schemas.insert(
+        "SetScoringModeBody".into(),
+        object(
+            json!({
+                "round": string_enum(&["group_play", "redemption", "semi_finals", "finals", "grand_finals", "knockout"]),
+                "mode": string_enum(&["winner", "legs"]),
+            }),
+            &["round", "mode"],
+        ),
+    );
+    schemas.insert(
+        "SetLegsPerFinalMatchBody".into(),
+        object(
+            json!({ "legs_per_final_match": int }),
+            &["legs_per_final_match"],
+        ),
+    );
+    schemas.insert(
+        "SetFinalTieResolutionBody".into(),
+        object(
+            json!({ "final_tie_resolution": final_tie_resolution }),
+            &["final_tie_resolution"],
+        ),
+    );
+    schemas.insert(
+        "PointsConfig".into(),
+        object(
+            json!({ "win": int, "draw": int, "loss": int }),
+            &["win", "draw", "loss"],
+        ),
+    );
+    schemas.insert("Player".into(), object(json!({
+            "id": uuid,
+            "name": { "type": "string" },
+            "wins": int,
+            "losses": int,
+            "draws": int,
+            "matches_played": int,
+            "times_sat_out": int,
+            "eliminated": boolean,
+            "join_order": int,
+            "elimination_reason": { "type": "string", "nullable": true, "enum": ["max_losses", "manual", "forfeit", "knockout", "round_cap"] },
+            "rating": { "type": "integer", "nullable": true },
+            "handicap_spot": int,
+            "current_streak": int,
+            "longest_streak": int,
+            "eliminated_at_round": { "type": "integer", "nullable": true },
+            "color": { "type": "string", "nullable": true },
+            "avatar_url": { "type": "string", "nullable": true },
+        }), &["id", "name", "wins", "losses", "eliminated"]));
+    schemas.insert(
+        "PlayerSummary".into(),
+        object(
+            json!({
+                "id": uuid,
+                "name": { "type": "string" },
+                "wins": int,
+                "losses": int,
+                "draws": int,
+                "matches_played": int,
+                "times_sat_out": int,
+                "eliminated_status": boolean,
+                "lives_remaining": int,
+                "current_streak": int,
+                "longest_streak": int,
+            }),
+            &["id", "name"],
+        ),
+    );
+    schemas.insert(
+        "ProjectedBracket".into(),
+        object(
+            json!({
+                "state": state,
+                "undecided": uuids,
+                "finals": { "type": "array", "items": object(
+                    json!({
+                        "semi_winners": { "type": "object", "additionalProperties": team },
+                        "team_1": uuids,
+                        "team_2": uuids,
+                    }),
+                    &["semi_winners", "team_1", "team_2"],
+                ) },
+                "player_names": { "type": "object", "additionalProperties": { "type": "string" } },
+            }),
+            &["state", "undecided", "finals", "player_names"],
+        ),
+    );
+    let summaries = json!({ "type": "array", "items": schema_ref("PlayerSummary") });
+    schemas.insert("PlayerSummaries".into(), summaries.clone());
+    schemas.insert(
+        "TournamentConfig".into(),
+        object(
+            json!({
+                "max_losses": int,
+                "mode": mode,
+                "format": format,
+                "balance_teams": boolean,
+                "reshuffle_every": int,
+                "draws_allowed": boolean,
+                "points_config": schema_ref("PointsConfig"),
+                "redemption": boolean,
+                "single_champion": boolean,
+                "num_boards": { "type": "integer", "nullable": true },
+                "max_concurrent_matches": { "type": "integer", "nullable": true },
+                "legs_per_final_match": int,
+                "final_tie_resolution": final_tie_resolution,
+                "deterministic": boolean,
+                "shuffle_match_order": boolean,
+                "auto_generate_next": boolean,
+                "strict_bye_rotation": boolean,
+                "knockout_after_rounds": int,
+                "grace_matches": int,
+                "round_cap": { "type": "integer", "nullable": true },
+                "auto_start_semis": boolean,
+                "allow_ghost": boolean,
+                "finals_format": finals_format,
+                "forfeit_policy": forfeit_policy,
+                "seeding": seeding,
+                "scoring_modes": { "type": "object", "additionalProperties": string_enum(&["winner", "legs"]) },
+                "auto_submit_after_secs": { "type": "integer", "nullable": true },
+            }),
+            &[
+                "max_losses",
+                "mode",
+                "format",
+                "balance_teams",
+                "reshuffle_every",
+                "draws_allowed",
+                "points_config",
+                "redemption",
+                "single_champion",
+                "num_boards",
+                "max_concurrent_matches",
+                "legs_per_final_match",
+                "final_tie_resolution",
+                "deterministic",
+                "shuffle_match_order",
+                "auto_generate_next",
+                "strict_bye_rotation",
+                "knockout_after_rounds",
+                "grace_matches",
+                "round_cap",
+                "auto_start_semis",
+                "allow_ghost",
+                "finals_format",
+                "forfeit_policy",
+                "seeding",
+            ],
+        ),
+    );
+    schemas.insert(
+        "TournamentSummaries".into(),
+        json!({ "type": "array", "items": object(
+            json!({
+                "id": uuid,
+                "state": state,
+                "mode": mode,
+                "format": format,
+                "active": int,
+                "version": int,
+                "started_at": { "type": "integer", "format": "int64", "nullable": true },
+                "last_activity_seconds_ago": int,
+            }),
+            &["id", "state", "mode", "format", "active", "version", "last_activity_seconds_ago"],
+        ) }),
+    );
+    schemas.insert(
+        "ShareLink".into(),
+        object(json!({ "share_id": uuid }), &["share_id"]),
+    );
+    schemas.insert(
+        "Counts".into(),
+        object(
+            json!({
+                "active": int,
+                "unused": int,
+                "eliminated": int,
+                "last_eliminated": int,
+                "max_losses": int,
+                "state": state,
+            }),
+            &[
+                "active",
+                "unused",
+                "eliminated",
+                "last_eliminated",
+                "max_losses",
+                "state",
+            ],
+        ),
+    );
+    schemas.insert(
+        "InvariantViolations".into(),
+        json!({ "type": "array", "items": { "type": "string" } }),
+    );
+    schemas.insert(
+        "TeamStandings".into(),
+        json!({ "type": "array", "items": object(
+            json!({
+                "players": uuids,
+                "names": { "type": "array", "items": { "type": "string" } },
+                "wins": int,
+                "losses": int,
+                "draws": int,
+                "matches_played": int,
+            }),
+            &["players", "names", "wins", "losses", "draws", "matches_played"],
+        ) }),
+    );
+    schemas.insert(
+        "FinalResult".into(),
+        object(
+            json!({ "completed": boolean, "winners": summaries, "runners_up": summaries }),
+            &["completed", "winners", "runners_up"],
+        ),
+    );
+    schemas.insert(
+        "SimResult".into(),
+        object(
+            json!({ "eliminated": summaries, "completes_round": boolean, "state": state }),
+            &["eliminated", "completes_round", "state"],
+        ),
+    );
+    schemas.insert(
+        "EliminatedPlayers".into(),
+        json!({ "type": "array", "items": {
+            "allOf": [
+                schema_ref("PlayerSummary"),
+                object(
+                    json!({
+                        "round_number": { "type": "integer", "nullable": true },
+                        "reason": { "type": "string", "nullable": true, "enum": ["max_losses", "manual", "forfeit", "knockout", "round_cap"] },
+                    }),
+                    &["round_number"],
+                ),
+            ],
+        } }),
+    );
+    schemas.insert(
+        "GameMatch".into(),
+        object(
+            json!({
+                "id": uuid,
+                "match_number": int,
+                "team_1": uuids,
+                "team_2": uuids,
+                "winner": { "type": "string", "nullable": true, "enum": ["one", "two"] },
+                "draw": boolean,
+                "round": string_enum(&["group_play", "redemption", "semi_finals", "finals", "grand_finals", "knockout"]),
+                "practice": boolean,
+                "walkover": boolean,
+                "legs": { "type": "array", "items": team },
+            "board": { "type": "integer", "nullable": true },
+                "team_1_handicap": int,
+                "team_2_handicap": int,
+            }),
+            &["id", "team_1", "team_2", "round"],
+        ),
+    );
+    let members = json!({ "type": "array", "items": object(
+        json!({ "id": uuid, "name": { "type": "string" } }),
+        &["id", "name"],
+    ) });
+    schemas.insert(
+        "GameMatchView".into(),
+        object(
+            json!({
+                "id": uuid,
+                "match_number": int,
+                "team_1": members,
+                "team_2": members,
+                "winner": { "type": "string", "nullable": true, "enum": ["one", "two"] },
+                "draw": boolean,
+                "round": string_enum(&["group_play", "redemption", "semi_finals", "finals", "grand_finals", "knockout"]),
+                "practice": boolean,
+                "walkover": boolean,
+                "legs": { "type": "array", "items": team },
+                "board": { "type": "integer", "nullable": true },
+                "team_1_handicap": int,
+                "team_2_handicap": int,
+            }),
+            &["id", "match_number", "team_1", "team_2", "round"],
+        ),
+    );
+    schemas.insert(
+        "GameMatchViews".into(),
+        json!({ "type": "array", "items": schema_ref("GameMatchView") }),
+    );
+    let mut tournament = json!({
+        "id": uuid,
+        "version": int,
+        "state": state,
+        "format": format,
+        "mode": mode,
+        "max_losses": int,
+        "players": players,
+        "unused_players": players,
+        "eliminated_players": players,
+        "last_eliminated_players": players,
+        "matches": matches,
+        "match_results": { "type": "object", "additionalProperties": string_enum(&["one", "two", "draw"]) },
+        "final_match_results": { "type": "object", "additionalProperties": team },
+        "match_history": matches,
+        "paused": boolean,
+        "auto_submit_after_secs": { "type": "integer", "nullable": true },
+        "results_complete_at": { "type": "integer", "format": "int64", "nullable": true },
+        "champion_team_name": { "type": "string", "nullable": true },
+        "started_at": { "type": "integer", "format": "int64", "nullable": true },
+        "completed_at": { "type": "integer", "format": "int64", "nullable": true },
+        "duration_seconds": { "type": "integer", "nullable": true },
+        "last_round_seed": { "type": "integer", "format": "int64", "nullable": true },
+        "last_activity_seconds_ago": int,
+        "knockout_after_rounds": int,
+        "knockout_seeds": uuids,
+        "scoring_modes": { "type": "object", "additionalProperties": string_enum(&["winner", "legs"]) },
+        "final_tie_resolution": final_tie_resolution,
+        "finals_draw": { "type": "string", "format": "uuid", "nullable": true },
+        "shared_title": boolean,
+        "finals_seeding": { "type": "array", "items": uuid, "nullable": true },
+        "max_possible_rounds": int,
+        "players_required_to_start": int,
+        "can_start": boolean,
+        "next_actions": { "type": "array", "items": string_enum(&[
+            "add_player", "remove_player", "set_mode", "start", "generate_matches",
+            "record_result", "submit", "add_players_back", "add_back_from_all_eliminated", "auto_fill_finalists",
+            "start_semi_finals", "forfeit", "swap_players", "start_grand_finals", "restart",
+            "pause", "resume",
+        ]) },
+        "final_placements": { "type": "array", "items": object(
+            json!({ "place": int, "players": uuids }),
+            &["place", "players"],
+        ) },
+    });
+    schemas.insert(
+        "Tournament".into(),
+        object(tournament.clone(), &["id", "state", "mode", "players"]),
+    );
+    // Through a share link the tournament id is left out.
+    if let Some(props) = tournament.as_object_mut() {
+        props.remove("id");
+    }
+    schemas.insert(
+        "SharedTournament".into(),
+        object(tournament, &["state", "mode", "players"]),
+    );
+    schemas.insert(
+        "StartedTournament".into(),
+        json!({
+            "allOf": [
+                schema_ref("Tournament"),
+                object(
+                    json!({ "warnings": { "type": "array", "items": { "type": "string" } } }),
+                    &["warnings"],
+                ),
+            ],
+        }),
+    );
+    schemas.insert(
+        "Csv".into(),
+        json!({ "type": "string", "description": "One name[,rating] row per line" }),
+    );
+    schemas.insert(
+        "RosterImportResult".into(),
+        json!({
+            "allOf": [
+                schema_ref("Tournament"),
+                object(
+                    json!({
+                        "added": int,
+                        "malformed": { "type": "array", "items": object(
+                            json!({ "line": int, "error": { "type": "string" } }),
+                            &["line", "error"],
+                        ) },
+                        "rejected": { "type": "array", "items": object(
+                            json!({ "name": { "type": "string" }, "error": { "type": "string" } }),
+                            &["name", "error"],
+                        ) },
+                    }),
+                    &["added", "malformed", "rejected"],
+                ),
+            ],
+        }),
+    );
+    schemas.insert(
+        "ImportPlayersWithStatsBody".into(),
+        object(
+            json!({ "players": { "type": "array", "items": object(
+                json!({
+                    "name": { "type": "string" },
+                    "wins": int,
+                    "losses": int,
+                    "sat_out": int,
+                }),
+                &["name"],
+            ) } }),
+            &["players"],
+        ),
+    );
+    schemas.insert(
+        "StatsImportResult".into(),
+        json!({
+            "allOf": [
+                schema_ref("Tournament"),
+                object(
+                    json!({
+                        "rejected": { "type": "array", "items": object(
+                            json!({ "name": { "type": "string" }, "error": { "type": "string" } }),
+                            &["name", "error"],
+                        ) },
+                    }),
+                    &["rejected"],
+                ),
+            ],
+        }),
+    );
+    schemas.insert(
+        "SubmittedRound".into(),
+        json!({
+            "allOf": [
+                schema_ref("Tournament"),
+                object(
+                    json!({ "recently_eliminated": { "type": "array", "items": schema_ref("PlayerSummary") } }),
+                    &["recently_eliminated"],
+                ),
+            ],
+        }),
+    );
+    schemas.insert(
+        "HistoryPage".into(),
+        object(
+            json!({ "matches": matches, "total": int, "offset": int, "limit": int }),
+            &["matches", "total", "offset", "limit"],
+        ),
+    );
+    schemas.insert("CurrentRoundView".into(), object(json!({
+            "state": state,
+            "matches": matches,
+            "results": { "type": "object", "additionalProperties": string_enum(&["one", "two", "draw"]) },
+            "player_names": { "type": "object", "additionalProperties": { "type": "string" } },
+            "seed": { "type": "integer", "format": "int64", "nullable": true },
+            "match_views": { "type": "array", "items": schema_ref("GameMatchView") },
+        }), &["state", "matches", "results", "player_names"]));
+    schemas.insert(
+        "PlayerRecord".into(),
+        object(
+            json!({
+                "player": schema_ref("PlayerSummary"),
+                "status": string_enum(&["active", "sitting_out", "eliminated"]),
+                "matches": matches,
+            }),
+            &["player", "status", "matches"],
+        ),
+    );
+    schemas.insert(
+        "PlayerMatches".into(),
+        json!({ "type": "array", "items": {
+            "allOf": [
+                schema_ref("GameMatch"),
+                object(json!({ "won": { "type": "boolean", "nullable": true } }), &["won"]),
+            ],
+        } }),
+    );
+    schemas.insert(
+        "FinalSelectionInfo".into(),
+        object(
+            json!({
+                "needed": int,
+                "candidates": { "type": "array", "items": schema_ref("PlayerSummary") },
+                "tied_at_cutoff": uuids,
+            }),
+            &["needed", "candidates", "tied_at_cutoff"],
+        ),
+    );
+    Value::Object(schemas)
+}
@@ -0,0 +1,59 @@
+//! Read-only share links: a separate id that resolves to a tournament, so results can be shown
+//! publicly without handing out the tournament id that controls it.
+
+use crate::models::{Tournament, TournamentId};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+/// The tournament as shown through a share link: [`Tournament::public_view`] without the
+/// tournament id, which is the write credential the link must not hand out.
+pub fn shared_view(tournament: &Tournament) -> serde_json::Value {
+    let mut json = serde_json::to_value(tournament.public_view()).unwrap_or_default();
+    if let Some(obj) = json.as_object_mut() {
+        obj.remove("id");
+    }
+    json
+}
+
+/// Share id → tournament, at most one link per tournament.
+#[derive(Clone, Debug, Default)]
+pub struct ShareLinks {
+    links: HashMap<Uuid, TournamentId>,
+}
+
+impl ShareLinks {
+    /// The tournament's share id, creating one if it has none yet.
+    pub fn share(&mut self, tournament: TournamentId) -> Uuid {
+        if let Some(share_id) = self.share_id(tournament) {
+            return share_id;
+        }
+        let share_id = Uuid::new_v4();
+        self.links.insert(share_id, tournament);
+        share_id
+    }
+
+    /// The tournament's current share id, if it is shared.
+    pub fn share_id(&self, tournament: TournamentId) -> Option<Uuid> {
+        self.links
+            .iter()
+            .find(|(_, &id)| id == tournament)
+            .map(|(&share_id, _)| share_id)
+    }
+
+    /// The tournament a share id points to (`None` if unknown or revoked).
+    pub fn resolve(&self, share_id: Uuid) -> Option<TournamentId> {
+        self.links.get(&share_id).copied()
+    }
+
+    /// Invalidate the tournament's share link. Returns whether it had one.
+    pub fn revoke(&mut self, tournament: TournamentId) -> bool {
+        let before = self.links.len();
+        self.links.retain(|_, id| *id != tournament);
+        self.links.len() != before
+    }
+
+    /// Drop the links of tournaments that no longer exist.
+    pub fn retain_tournaments(&mut self, exists: impl Fn(TournamentId) -> bool) {
+        self.links.retain(|_, id| exists(*id));
+    }
+}
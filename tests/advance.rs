@@ -0,0 +1,264 @@
+//! Integration tests for the one-step `advance_tournament` dispatcher.
+
+mod common;
+
+use common::tournament_in;
+use dart_tournament_web::{
+    advance_tournament, start_grand_finals, Player, Team, Tournament, TournamentAction,
+    TournamentError, TournamentMode, TournamentState,
+};
+
+fn setup_tournament(n: usize, max_losses: u32) -> Tournament {
+    tournament_in(
+        n,
+        max_losses,
+        TournamentMode::OneVOne,
+        TournamentState::Setup,
+    )
+}
+
+fn pick_team_one(t: &mut Tournament) {
+    let ids: Vec<_> = t.matches.iter().map(|m| m.id).collect();
+    for id in ids {
+        if t.state == TournamentState::GroupPlay {
+            t.match_results.insert(id, Team::One.into());
+        } else {
+            t.final_match_results.insert(id, Team::One);
+        }
+    }
+}
+
+#[test]
+fn advance_runs_whole_tournament() {
+    let mut t = setup_tournament(6, 1);
+    advance_tournament(&mut t).unwrap();
+    assert_eq!(t.state, TournamentState::GroupPlay);
+    assert!(t.matches.is_empty());
+
+    advance_tournament(&mut t).unwrap();
+    assert_eq!(t.matches.len(), 3);
+
+    // 3 losers eliminated at max_losses=1 → 3 left, below the 1v1 threshold of 4.
+    pick_team_one(&mut t);
+    advance_tournament(&mut t).unwrap();
+    assert_eq!(t.state, TournamentState::FinalSelection);
+    assert_eq!(
+        advance_tournament(&mut t),
+        Err(TournamentError::InvalidState)
+    );
+}
+
+#[test]
+fn advance_generates_next_group_round_after_submit() {
+    let mut t = setup_tournament(12, 3);
+    advance_tournament(&mut t).unwrap();
+    advance_tournament(&mut t).unwrap();
+    let first_round: Vec<_> = t.matches.iter().map(|m| m.id).collect();
+    pick_team_one(&mut t);
+
+    advance_tournament(&mut t).unwrap();
+    assert_eq!(t.state, TournamentState::GroupPlay);
+    assert_eq!(t.matches.len(), 6);
+    assert!(t.matches.iter().all(|m| !first_round.contains(&m.id)));
+}
+
+#[test]
+fn advance_requires_complete_results() {
+    let mut t = setup_tournament(8, 3);
+    advance_tournament(&mut t).unwrap();
+    advance_tournament(&mut t).unwrap();
+    assert_eq!(
+        advance_tournament(&mut t),
+        Err(TournamentError::IncompleteResults)
+    );
+    assert_eq!(t.matches.len(), 4);
+}
+
+#[test]
+fn advance_through_semis_and_finals() {
+    let mut t = setup_tournament(4, 3);
+    advance_tournament(&mut t).unwrap();
+    assert_eq!(t.state, TournamentState::FinalSelection);
+    advance_tournament(&mut t).unwrap();
+    assert_eq!(t.state, TournamentState::SemiFinals);
+    advance_tournament(&mut t).unwrap();
+    assert_eq!(t.matches.len(), 2);
+    pick_team_one(&mut t);
+    advance_tournament(&mut t).unwrap();
+    assert_eq!(t.state, TournamentState::Finals);
+    pick_team_one(&mut t);
+    advance_tournament(&mut t).unwrap();
+    assert_eq!(t.state, TournamentState::Completed);
+    assert_eq!(
+        advance_tournament(&mut t),
+        Err(TournamentError::InvalidState)
+    );
+}
+
+#[test]
+fn setup_actions_offer_start_once_the_roster_is_big_enough() {
+    use TournamentAction::*;
+    let mut t = Tournament::new(3, TournamentMode::OneVOne);
+    assert_eq!(t.available_actions(), vec![AddPlayer, SetMode, Pause]);
+
+    t = setup_tournament(4, 3);
+    assert_eq!(
+        t.available_actions(),
+        vec![AddPlayer, RemovePlayer, SetMode, Start, Pause]
+    );
+}
+
+#[test]
+fn group_play_actions_follow_the_round() {
+    use TournamentAction::*;
+    let mut t = setup_tournament(8, 3);
+    advance_tournament(&mut t).unwrap();
+    assert_eq!(
+        t.available_actions(),
+        vec![AddPlayer, GenerateMatches, Forfeit, Restart, Pause]
+    );
+
+    advance_tournament(&mut t).unwrap();
+    assert_eq!(
+        t.available_actions(),
+        vec![
+            AddPlayer,
+            RecordResult,
+            Forfeit,
+            SwapPlayers,
+            Restart,
+            Pause
+        ]
+    );
+
+    pick_team_one(&mut t);
+    assert_eq!(
+        t.available_actions(),
+        vec![AddPlayer, Submit, Forfeit, Restart, Pause]
+    );
+    assert!(advance_tournament(&mut t).is_ok());
+}
+
+#[test]
+fn final_selection_actions_depend_on_the_field() {
+    use TournamentAction::*;
+    // 6 players, one life: 3 left after a round, with 3 to pick from.
+    let mut t = setup_tournament(6, 1);
+    advance_tournament(&mut t).unwrap();
+    advance_tournament(&mut t).unwrap();
+    pick_team_one(&mut t);
+    advance_tournament(&mut t).unwrap();
+    assert_eq!(t.state, TournamentState::FinalSelection);
+    assert_eq!(
+        t.available_actions(),
+        vec![AddPlayer, AddPlayersBack, AutoFillFinalists, Restart, Pause]
+    );
+
+    // Only the full eliminated pool can fill the open slot.
+    let mut fallback = t.clone();
+    fallback.last_eliminated_players.clear();
+    assert_eq!(
+        fallback.available_actions(),
+        vec![AddPlayer, AddBackFromAllEliminated, Restart, Pause]
+    );
+    let id = fallback.eliminated_players[0].id;
+    fallback.add_back_from_all_eliminated(&[id]).unwrap();
+    assert_eq!(fallback.state, TournamentState::SemiFinals);
+
+    let mut t = setup_tournament(4, 3);
+    advance_tournament(&mut t).unwrap();
+    assert_eq!(
+        t.available_actions(),
+        vec![AddPlayer, StartSemiFinals, Restart, Pause]
+    );
+}
+
+#[test]
+fn playoff_actions_and_completion() {
+    use TournamentAction::*;
+    let mut t = setup_tournament(4, 3);
+    advance_tournament(&mut t).unwrap();
+    advance_tournament(&mut t).unwrap();
+    assert_eq!(t.state, TournamentState::SemiFinals);
+    assert_eq!(t.available_actions(), vec![GenerateMatches, Pause]);
+    advance_tournament(&mut t).unwrap();
+    assert_eq!(t.available_actions(), vec![RecordResult, Pause]);
+    pick_team_one(&mut t);
+    assert_eq!(t.available_actions(), vec![Submit, Pause]);
+    advance_tournament(&mut t).unwrap();
+
+    assert_eq!(t.state, TournamentState::Finals);
+    assert_eq!(t.available_actions(), vec![RecordResult, Pause]);
+    pick_team_one(&mut t);
+    advance_tournament(&mut t).unwrap();
+    assert_eq!(t.state, TournamentState::Completed);
+    assert!(t.available_actions().is_empty());
+}
+
+#[test]
+fn completed_2v2_offers_a_grand_final_once() {
+    let players: Vec<Player> = (0..8).map(|i| Player::new(format!("P{i}"))).collect();
+    let mut t = Tournament::with_players(players, 3, TournamentMode::TwoVTwo);
+    while t.state != TournamentState::Completed {
+        pick_team_one(&mut t);
+        advance_tournament(&mut t).unwrap();
+    }
+    assert_eq!(
+        t.available_actions(),
+        vec![TournamentAction::StartGrandFinals]
+    );
+    start_grand_finals(&mut t).unwrap();
+    pick_team_one(&mut t);
+    advance_tournament(&mut t).unwrap();
+    assert_eq!(t.state, TournamentState::Completed);
+    assert!(t.available_actions().is_empty());
+}
+
+#[test]
+fn paused_tournament_only_offers_resume() {
+    let mut t = setup_tournament(4, 3);
+    t.pause().unwrap();
+    assert_eq!(t.available_actions(), vec![TournamentAction::Resume]);
+}
+
+#[test]
+fn auto_submit_is_due_only_after_the_countdown_with_all_results_in() {
+    let mut t = setup_tournament(8, 2);
+    t.apply_versioned(None, advance_tournament).unwrap();
+    t.apply_versioned(None, advance_tournament).unwrap();
+    assert_eq!(
+        t.set_auto_submit_after(Some(0)),
+        Err(TournamentError::InvalidConfig(
+            "auto_submit_after_secs must be at least 1"
+        ))
+    );
+    t.set_auto_submit_after(Some(30)).unwrap();
+    assert_eq!(t.results_complete_at, None);
+    assert!(!t.auto_submit_due(u64::MAX));
+
+    let last = t.matches.last().unwrap().id;
+    t.apply_versioned(None, |t| {
+        pick_team_one(t);
+        t.match_results.remove(&last);
+        Ok(())
+    })
+    .unwrap();
+    assert_eq!(t.results_complete_at, None);
+
+    t.apply_versioned(None, |t| t.set_match_outcome(last, Team::Two.into()))
+        .unwrap();
+    let complete_at = t.results_complete_at.expect("all results are in");
+    assert!(!t.auto_submit_due(complete_at + 29_999));
+    assert!(t.auto_submit_due(complete_at + 30_000));
+
+    t.pause().unwrap();
+    assert!(!t.auto_submit_due(complete_at + 30_000));
+    t.resume().unwrap();
+    t.set_auto_submit_after(None).unwrap();
+    assert!(!t.auto_submit_due(complete_at + 30_000));
+
+    t.set_auto_submit_after(Some(30)).unwrap();
+    t.apply_versioned(None, advance_tournament).unwrap();
+    assert_eq!(t.rounds_completed, 1);
+    assert!(!t.auto_submit_due(u64::MAX));
+}
@@ -0,0 +1,89 @@
+//! Integration tests for the append-only audit log of API mutations.
+
+use dart_tournament_web::audit::{AuditEvent, AuditLog};
+use dart_tournament_web::{Tournament, TournamentMode};
+use uuid::Uuid;
+
+#[test]
+fn create_then_add_player_appends_two_lines() {
+    let path = std::env::temp_dir().join(format!("audit-{}.jsonl", Uuid::new_v4()));
+    let mut t = Tournament::new(3, TournamentMode::OneVOne);
+    {
+        let audit_log = AuditLog::open(&path).unwrap();
+        audit_log
+            .record(t.id, "POST /api/tournaments", "setup, version 0, 0 active")
+            .unwrap();
+        t.add_player("Alice").unwrap();
+        audit_log
+            .record(
+                t.id,
+                "POST /api/tournaments/{id}/players",
+                "setup, version 1, 1 active",
+            )
+            .unwrap();
+    }
+
+    let contents = std::fs::read_to_string(&path).unwrap();
+    let events: Vec<AuditEvent> = contents
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert_eq!(events.len(), 2);
+    assert!(events.iter().all(|e| e.tournament_id == t.id));
+    assert_eq!(events[0].action, "POST /api/tournaments");
+    assert_eq!(events[1].action, "POST /api/tournaments/{id}/players");
+    assert!(events[0].timestamp <= events[1].timestamp);
+
+    // Reopening appends instead of truncating.
+    AuditLog::open(&path)
+        .unwrap()
+        .record(
+            t.id,
+            "DELETE /api/tournaments/{id}/share",
+            "setup, version 1, 1 active",
+        )
+        .unwrap();
+    assert_eq!(std::fs::read_to_string(&path).unwrap().lines().count(), 3);
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn concurrent_records_are_all_written_as_whole_lines() {
+    let path = std::env::temp_dir().join(format!("audit-{}.jsonl", Uuid::new_v4()));
+    let t = Tournament::new(3, TournamentMode::OneVOne);
+    let audit_log = std::sync::Arc::new(AuditLog::open(&path).unwrap());
+    let writers: Vec<_> = (0..4)
+        .map(|i| {
+            let audit_log = audit_log.clone();
+            std::thread::spawn(move || {
+                for n in 0..50 {
+                    audit_log
+                        .record(t.id, &format!("writer {i}"), &format!("event {n}"))
+                        .unwrap();
+                }
+            })
+        })
+        .collect();
+    for w in writers {
+        w.join().unwrap();
+    }
+    drop(audit_log);
+
+    let contents = std::fs::read_to_string(&path).unwrap();
+    let events: Vec<AuditEvent> = contents
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert_eq!(events.len(), 200);
+    for i in 0..4 {
+        let action = format!("writer {i}");
+        let summaries: Vec<_> = events
+            .iter()
+            .filter(|e| e.action == action)
+            .map(|e| e.summary.clone())
+            .collect();
+        let expected: Vec<_> = (0..50).map(|n| format!("event {n}")).collect();
+        assert_eq!(summaries, expected);
+    }
+    std::fs::remove_file(&path).unwrap();
+}
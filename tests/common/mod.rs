@@ -0,0 +1,43 @@
+//! Helpers shared by the integration tests.
+#![allow(dead_code)]
+
+use dart_tournament_web::{
+    generate_group_play_matches, process_group_play_results, Player, PlayerId, Team, Tournament,
+    TournamentMode, TournamentState,
+};
+
+/// `n` players named `P0`, `P1`, ... in a tournament of `mode` with `max_losses`, put in `state`.
+pub fn tournament_in(
+    n: usize,
+    max_losses: u32,
+    mode: TournamentMode,
+    state: TournamentState,
+) -> Tournament {
+    let players: Vec<Player> = (0..n).map(|i| Player::new(format!("P{i}"))).collect();
+    let mut t = Tournament::with_players(players, max_losses, mode);
+    t.state = state;
+    t
+}
+
+/// Team 1 wins every current group play match, then submit.
+pub fn submit_team_one_wins(t: &mut Tournament) {
+    let ids: Vec<_> = t.matches.iter().map(|m| m.id).collect();
+    for id in ids {
+        t.match_results.insert(id, Team::One.into());
+    }
+    process_group_play_results(t).unwrap();
+}
+
+/// Generate a group play round and submit it with team 1 winning every match.
+pub fn play_round(t: &mut Tournament) {
+    generate_group_play_matches(t).unwrap();
+    submit_team_one_wins(t);
+}
+
+/// The current matches as (team 1, team 2) pairs.
+pub fn teams(t: &Tournament) -> Vec<(Vec<PlayerId>, Vec<PlayerId>)> {
+    t.matches
+        .iter()
+        .map(|m| (m.team_1.clone(), m.team_2.clone()))
+        .collect()
+}
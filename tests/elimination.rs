@@ -0,0 +1,618 @@
+//! Integration tests for elimination paths and the recorded elimination reason.
+
+mod common;
+
+use common::{play_round, tournament_in};
+use dart_tournament_web::{
+    generate_group_play_matches, process_group_play_results, process_group_play_results_summary,
+    EliminationReason, ForfeitPolicy, MatchOutcome, Player, PlayerLocation, PointsConfig, Team,
+    Tournament, TournamentError, TournamentFormat, TournamentMode, TournamentState,
+};
+
+fn group_play_with(n: usize, max_losses: u32) -> Tournament {
+    tournament_in(
+        n,
+        max_losses,
+        TournamentMode::OneVOne,
+        TournamentState::GroupPlay,
+    )
+}
+
+#[test]
+fn active_players_have_no_reason() {
+    let t = group_play_with(6, 1);
+    assert!(t.players.iter().all(|p| p.elimination_reason.is_none()));
+}
+
+#[test]
+fn max_losses_elimination_sets_reason() {
+    let mut t = group_play_with(10, 1);
+    generate_group_play_matches(&mut t).unwrap();
+    let ids: Vec<_> = t.matches.iter().map(|m| m.id).collect();
+    for id in ids {
+        t.match_results.insert(id, Team::One.into());
+    }
+    process_group_play_results(&mut t).unwrap();
+
+    assert_eq!(t.eliminated_players.len(), 5);
+    for p in &t.eliminated_players {
+        assert_eq!(p.elimination_reason, Some(EliminationReason::MaxLosses));
+    }
+}
+
+#[test]
+fn manual_elimination_sets_reason() {
+    let mut t = group_play_with(6, 3);
+    let id = t.players[0].id;
+    t.eliminate_player(id).unwrap();
+    assert_eq!(
+        t.eliminated_players[0].elimination_reason,
+        Some(EliminationReason::Manual)
+    );
+}
+
+#[test]
+fn setting_losses_to_max_sets_reason() {
+    let mut t = group_play_with(6, 2);
+    generate_group_play_matches(&mut t).unwrap();
+    let id = t.players[0].id;
+    t.set_player_losses(id, 2).unwrap();
+    let p = t.players.iter().find(|p| p.id == id).unwrap();
+    assert_eq!(p.elimination_reason, Some(EliminationReason::MaxLosses));
+}
+
+#[test]
+fn emptied_team_is_awarded_as_walkover() {
+    let players: Vec<Player> = (0..12).map(|i| Player::new(format!("P{i}"))).collect();
+    let mut t = Tournament::with_players(players, 3, TournamentMode::TwoVTwo);
+    t.state = TournamentState::GroupPlay;
+    generate_group_play_matches(&mut t).unwrap();
+    let m = t.matches[0].clone();
+    for &id in &m.team_2 {
+        t.eliminate_player(id).unwrap();
+    }
+
+    assert_eq!(t.detect_walkovers(), vec![m.id]);
+    assert_eq!(
+        t.match_results.get(&m.id),
+        Some(&MatchOutcome::Win(Team::One))
+    );
+    assert!(t.matches[0].walkover);
+    assert!(t.detect_walkovers().is_empty());
+
+    for other in &t.matches[1..] {
+        t.match_results.insert(other.id, Team::One.into());
+    }
+    process_group_play_results(&mut t).unwrap();
+    for id in &m.team_1 {
+        let p = t.players.iter().find(|p| p.id == *id).unwrap();
+        assert_eq!(p.wins, 1);
+    }
+    let recorded = t.match_history.iter().find(|h| h.id == m.id).unwrap();
+    assert!(recorded.walkover);
+    assert_eq!(recorded.winner, Some(Team::One));
+}
+
+#[test]
+fn submit_awards_walkover_without_explicit_result() {
+    let players: Vec<Player> = (0..12).map(|i| Player::new(format!("P{i}"))).collect();
+    let mut t = Tournament::with_players(players, 3, TournamentMode::TwoVTwo);
+    t.state = TournamentState::GroupPlay;
+    generate_group_play_matches(&mut t).unwrap();
+    let m = t.matches[2].clone();
+    for &id in &m.team_1 {
+        t.eliminate_player(id).unwrap();
+    }
+    for other in &t.matches[..2] {
+        t.match_results.insert(other.id, Team::Two.into());
+    }
+
+    process_group_play_results(&mut t).unwrap();
+    let recorded = t.match_history.iter().find(|h| h.id == m.id).unwrap();
+    assert_eq!(recorded.winner, Some(Team::Two));
+}
+
+#[test]
+fn incomplete_submit_leaves_walkovers_unawarded() {
+    let players: Vec<Player> = (0..12).map(|i| Player::new(format!("P{i}"))).collect();
+    let mut t = Tournament::with_players(players, 3, TournamentMode::TwoVTwo);
+    t.state = TournamentState::GroupPlay;
+    generate_group_play_matches(&mut t).unwrap();
+    let m = t.matches[2].clone();
+    for &id in &m.team_1 {
+        t.eliminate_player(id).unwrap();
+    }
+    t.match_results.insert(t.matches[0].id, Team::Two.into());
+
+    assert_eq!(
+        process_group_play_results(&mut t),
+        Err(TournamentError::IncompleteResults)
+    );
+    assert!(!t.matches[2].walkover);
+    assert!(!t.match_results.contains_key(&m.id));
+    assert_eq!(t.match_results.len(), 1);
+}
+
+#[test]
+fn find_player_anywhere_reports_location() {
+    let mut t = group_play_with(7, 3);
+    generate_group_play_matches(&mut t).unwrap();
+    let sitting = t.unused_players[0].id;
+    let playing = t.matches[0].team_1[0];
+    let gone = t.matches[1].team_1[0];
+    t.eliminate_player(gone).unwrap();
+
+    let location = |id| t.find_player_anywhere(id).map(|(_, loc)| loc);
+    assert_eq!(location(sitting), Some(PlayerLocation::SittingOut));
+    assert_eq!(location(playing), Some(PlayerLocation::Active));
+    assert_eq!(location(gone), Some(PlayerLocation::Eliminated));
+    assert_eq!(location(Player::new("X").id), None);
+}
+
+#[test]
+fn bulk_elimination_is_all_or_nothing() {
+    let mut t = group_play_with(10, 3);
+    t.state = TournamentState::FinalSelection;
+    let bad = Player::new("Ghost").id;
+    let ids = vec![t.players[0].id, bad, t.players[1].id];
+
+    assert_eq!(
+        t.eliminate_players(&ids),
+        Err(TournamentError::PlayerNotFound(bad))
+    );
+    assert_eq!(t.players.len(), 10);
+    assert!(t.eliminated_players.is_empty());
+
+    let ids = vec![t.players[0].id, t.players[1].id, t.players[0].id];
+    t.eliminate_players(&ids).unwrap();
+    assert_eq!(t.players.len(), 8);
+    assert_eq!(t.eliminated_players.len(), 2);
+    assert!(t
+        .eliminated_players
+        .iter()
+        .all(|p| p.elimination_reason == Some(EliminationReason::Manual)));
+}
+
+#[test]
+fn round_summary_lists_players_eliminated_this_round() {
+    let mut t = group_play_with(6, 1);
+    generate_group_play_matches(&mut t).unwrap();
+    let losers: Vec<_> = t.matches.iter().map(|m| m.team_2[0]).collect();
+    for m in &t.matches {
+        t.match_results.insert(m.id, Team::One.into());
+    }
+
+    let summary = process_group_play_results_summary(&mut t).unwrap();
+
+    assert_eq!(summary.matches_played, 3);
+    let mut eliminated: Vec<_> = summary.eliminated.iter().map(|p| p.id).collect();
+    eliminated.sort();
+    let mut expected = losers;
+    expected.sort();
+    assert_eq!(eliminated, expected);
+    assert!(summary.advanced_to_final_selection);
+    assert_eq!(t.last_round_summary, Some(summary));
+}
+
+/// One round of 10 players with max_losses 1: five winners stay, five losers are eliminated.
+fn after_one_round() -> Tournament {
+    let mut t = group_play_with(10, 1);
+    play_round(&mut t);
+    t
+}
+
+#[test]
+fn reset_keeping_stats_leaves_eliminated_players_out() {
+    let mut t = after_one_round();
+    t.reset_to_group_play_keeping_stats(false).unwrap();
+
+    assert_eq!(t.state, TournamentState::GroupPlay);
+    assert!(t.matches.is_empty());
+    assert_eq!(t.players.len(), 5);
+    assert!(t.players.iter().all(|p| p.wins == 1 && p.losses == 0));
+    assert_eq!(t.eliminated_players.len(), 5);
+    assert!(t.last_eliminated_players.is_empty());
+}
+
+#[test]
+fn reset_keeping_stats_can_reactivate_eliminated_players() {
+    let mut t = after_one_round();
+    t.reset_to_group_play_keeping_stats(true).unwrap();
+
+    assert_eq!(t.state, TournamentState::GroupPlay);
+    assert_eq!(t.players.len(), 10);
+    assert!(t.eliminated_players.is_empty());
+    let returned: Vec<_> = t.players.iter().filter(|p| p.losses == 1).collect();
+    assert_eq!(returned.len(), 5);
+    assert!(returned
+        .iter()
+        .all(|p| !p.eliminated && p.elimination_reason.is_none()));
+}
+
+#[test]
+fn reset_keeping_stats_not_allowed_in_setup() {
+    let mut t = Tournament::new(3, TournamentMode::OneVOne);
+    assert_eq!(
+        t.reset_to_group_play_keeping_stats(true),
+        Err(TournamentError::InvalidState)
+    );
+}
+
+#[test]
+fn eliminations_record_their_round() {
+    let mut t = group_play_with(24, 2);
+    play_round(&mut t);
+    assert!(t.eliminated_players.is_empty());
+    assert_eq!(t.rounds_completed, 1);
+
+    // Round 2: every first-round loser who loses again goes out.
+    play_round(&mut t);
+    let round_2 = t.eliminated_players.len();
+    assert!(round_2 > 0);
+    assert!(t
+        .eliminated_players
+        .iter()
+        .all(|p| p.eliminated_at_round == Some(2)));
+
+    // A manual elimination before round 3 is generated counts toward round 3.
+    let id = t.players[0].id;
+    t.eliminate_player(id).unwrap();
+
+    let view = t.eliminated_view();
+    assert_eq!(view.len(), round_2 + 1);
+    assert_eq!(view.last().unwrap().player.id, id);
+    assert_eq!(view.last().unwrap().round_number, Some(3));
+    assert_eq!(view.last().unwrap().reason, Some(EliminationReason::Manual));
+    assert!(t.players.iter().all(|p| p.eliminated_at_round.is_none()));
+}
+
+#[test]
+fn recently_eliminated_lists_only_the_last_round() {
+    let mut t = group_play_with(24, 2);
+    assert!(t.recently_eliminated().is_empty());
+    play_round(&mut t);
+    assert!(t.recently_eliminated().is_empty());
+
+    play_round(&mut t);
+    let recent = t.recently_eliminated();
+    assert!(!recent.is_empty());
+    let ids: Vec<_> = recent.iter().map(|p| p.id).collect();
+    let last: Vec<_> = t.last_eliminated_players.iter().map(|p| p.id).collect();
+    assert_eq!(ids, last);
+    assert!(recent.iter().all(|p| p.stats.eliminated_status));
+}
+
+#[test]
+fn amending_a_result_flips_stats_and_eliminations() {
+    let mut t = after_one_round();
+    let m = t.match_history[0].clone();
+    let (old_winner, old_loser) = (m.team_1[0], m.team_2[0]);
+
+    t.amend_history_result(m.id, Team::Two).unwrap();
+
+    assert_eq!(t.match_history[0].winner, Some(Team::Two));
+    let winner = t.players.iter().find(|p| p.id == old_loser).unwrap();
+    assert_eq!((winner.wins, winner.losses), (1, 0));
+    assert!(!winner.eliminated);
+    let loser = t
+        .eliminated_players
+        .iter()
+        .find(|p| p.id == old_winner)
+        .unwrap();
+    assert_eq!((loser.wins, loser.losses), (0, 1));
+    assert_eq!(loser.elimination_reason, Some(EliminationReason::MaxLosses));
+    assert!(t.last_eliminated_players.iter().any(|p| p.id == old_winner));
+    assert!(!t.last_eliminated_players.iter().any(|p| p.id == old_loser));
+    assert_eq!(t.players.len(), 5);
+    assert_eq!(t.eliminated_players.len(), 5);
+    assert_eq!(t.state, TournamentState::GroupPlay);
+}
+
+#[test]
+fn amending_keeps_formats_without_final_selection_in_group_play() {
+    for format in [TournamentFormat::GroupOnly, TournamentFormat::KnockoutAll] {
+        let mut t = group_play_with(8, 1);
+        t.format = format;
+        generate_group_play_matches(&mut t).unwrap();
+        let ids: Vec<_> = t.matches.iter().map(|m| m.id).collect();
+        for id in ids {
+            t.match_results.insert(id, Team::One.into());
+        }
+        process_group_play_results(&mut t).unwrap();
+        assert_eq!(t.state, TournamentState::GroupPlay);
+        assert_eq!(t.players.len(), 4);
+
+        let id = t.match_history[0].id;
+        t.amend_history_result(id, Team::Two).unwrap();
+        assert_eq!(t.players.len(), 4);
+        assert_eq!(t.state, TournamentState::GroupPlay, "{format:?}");
+    }
+}
+
+#[test]
+fn amending_needs_a_submitted_group_match_between_rounds() {
+    let mut t = after_one_round();
+    let id = t.match_history[0].id;
+    assert_eq!(
+        t.amend_history_result(id, Team::One),
+        Ok(()),
+        "same winner is a no-op"
+    );
+    generate_group_play_matches(&mut t).unwrap();
+    assert_eq!(
+        t.amend_history_result(id, Team::Two),
+        Err(TournamentError::InvalidState)
+    );
+    let current = t.matches[0].id;
+    t.matches.clear();
+    assert_eq!(
+        t.amend_history_result(current, Team::Two),
+        Err(TournamentError::MatchNotFound(current))
+    );
+}
+
+#[test]
+fn eliminated_player_name_cannot_be_reused() {
+    let mut players: Vec<Player> = (0..5).map(|i| Player::new(format!("P{i}"))).collect();
+    players.push(Player::new("Bob"));
+    let bob = players[5].id;
+    let mut t = Tournament::with_players(players, 3, TournamentMode::OneVOne);
+    t.state = TournamentState::GroupPlay;
+    t.eliminate_player(bob).unwrap();
+    assert!(t.players.iter().all(|p| p.id != bob));
+
+    assert_eq!(
+        t.add_player("bob"),
+        Err(TournamentError::DuplicatePlayerName)
+    );
+    assert_eq!(t.players.len(), 5);
+}
+
+#[test]
+fn forfeit_eliminates_immediately_by_default() {
+    let mut t = group_play_with(10, 3);
+    let id = t.players[0].id;
+    t.forfeit_player(id).unwrap();
+    let p = t.eliminated_players.iter().find(|p| p.id == id).unwrap();
+    assert_eq!(p.losses, 0);
+    assert_eq!(p.elimination_reason, Some(EliminationReason::Forfeit));
+}
+
+#[test]
+fn partial_2v2_forfeit_still_lets_the_round_be_submitted() {
+    let players: Vec<Player> = (0..10).map(|i| Player::new(format!("P{i}"))).collect();
+    let mut t = Tournament::with_players(players, 3, TournamentMode::TwoVTwo);
+    t.state = TournamentState::GroupPlay;
+    generate_group_play_matches(&mut t).unwrap();
+    let (forfeiter, partner) = (t.matches[0].team_1[0], t.matches[0].team_1[1]);
+    t.forfeit_player(forfeiter).unwrap();
+    let ids: Vec<_> = t.matches.iter().map(|m| m.id).collect();
+    for id in ids {
+        t.match_results.insert(id, Team::One.into());
+    }
+    process_group_play_results(&mut t).unwrap();
+
+    assert_eq!(t.rounds_completed, 1);
+    let partner = t.players.iter().find(|p| p.id == partner).unwrap();
+    assert_eq!(partner.wins, 1);
+    let gone = t
+        .eliminated_players
+        .iter()
+        .find(|p| p.id == forfeiter)
+        .unwrap();
+    assert_eq!((gone.wins, gone.losses), (0, 0));
+}
+
+#[test]
+fn eliminating_a_player_after_their_result_is_entered_keeps_the_round_submittable() {
+    let mut t = group_play_with(10, 3);
+    generate_group_play_matches(&mut t).unwrap();
+    let ids: Vec<_> = t.matches.iter().map(|m| m.id).collect();
+    for id in ids {
+        t.match_results.insert(id, Team::One.into());
+    }
+    let loser = t.matches[0].team_2[0];
+    t.eliminate_player(loser).unwrap();
+    process_group_play_results(&mut t).unwrap();
+    assert_eq!(t.rounds_completed, 1);
+}
+
+#[test]
+fn forfeit_counting_as_loss_eliminates_only_at_max_losses() {
+    let mut t = group_play_with(10, 3);
+    t.set_forfeit_policy(ForfeitPolicy::CountsAsLoss).unwrap();
+    let (survivor, goner) = (t.players[0].id, t.players[1].id);
+    t.players[0].losses = 1;
+    t.players[1].losses = 2;
+
+    t.forfeit_player(survivor).unwrap();
+    let p = t.players.iter().find(|p| p.id == survivor).unwrap();
+    assert_eq!(p.losses, 2);
+    assert!(!p.eliminated);
+
+    t.forfeit_player(goner).unwrap();
+    assert!(t.players.iter().all(|p| p.id != goner));
+    let p = t.eliminated_players.iter().find(|p| p.id == goner).unwrap();
+    assert_eq!(p.losses, 3);
+    assert_eq!(p.elimination_reason, Some(EliminationReason::Forfeit));
+}
+
+#[test]
+fn forfeit_counting_as_loss_concedes_the_current_match() {
+    let mut t = group_play_with(10, 3);
+    t.set_forfeit_policy(ForfeitPolicy::CountsAsLoss).unwrap();
+    generate_group_play_matches(&mut t).unwrap();
+    let m = t.matches[0].clone();
+    let id = m.team_1[0];
+    t.players.iter_mut().find(|p| p.id == id).unwrap().losses = 2;
+
+    t.forfeit_player(id).unwrap();
+    assert_eq!(t.match_results[&m.id], MatchOutcome::Win(Team::Two));
+    // The loss arrives with the round, not on top of it.
+    assert_eq!(t.players.iter().find(|p| p.id == id).unwrap().losses, 2);
+
+    let ids: Vec<_> = t.matches.iter().map(|m| m.id).collect();
+    for match_id in ids {
+        t.match_results.entry(match_id).or_insert(Team::One.into());
+    }
+    process_group_play_results(&mut t).unwrap();
+    let p = t.eliminated_players.iter().find(|p| p.id == id).unwrap();
+    assert_eq!(p.losses, 3);
+}
+
+#[test]
+fn player_match_log_lists_history_then_current_matches() {
+    let mut t = group_play_with(24, 3);
+    let fresh = t.players[0].id;
+    assert!(t.player_match_log(fresh).is_empty());
+
+    play_round(&mut t);
+    generate_group_play_matches(&mut t).unwrap();
+    let id = t.matches[0].team_2[0];
+    let log = t.player_match_log(id);
+    let played = t.match_history.iter().filter(|m| m.team_of(id).is_some());
+    let expected: Vec<_> = played.chain(std::iter::once(&t.matches[0])).collect();
+    assert_eq!(log, expected);
+
+    let current = log.last().unwrap();
+    assert_eq!(current.won_by(id), None);
+    for m in &log[..log.len() - 1] {
+        // play_round always lets team one win.
+        assert_eq!(m.won_by(id), Some(m.team_of(id) == Some(Team::One)));
+    }
+}
+
+#[test]
+fn grace_match_loss_does_not_eliminate_until_the_next_loss() {
+    let mut t = group_play_with(10, 1);
+    t.set_grace_matches(1).unwrap();
+    generate_group_play_matches(&mut t).unwrap();
+    let results: Vec<_> = t.matches.iter().map(|m| (m.id, m.team_2[0])).collect();
+    for &(id, _) in &results {
+        t.match_results.insert(id, Team::One.into());
+    }
+    process_group_play_results(&mut t).unwrap();
+
+    // Everyone lost at most their grace match, so nobody is out despite max_losses = 1.
+    assert!(t.eliminated_players.is_empty());
+    let loser = results[0].1;
+    let p = t.players.iter().find(|p| p.id == loser).unwrap();
+    assert_eq!(p.losses, 1);
+
+    generate_group_play_matches(&mut t).unwrap();
+    let ids: Vec<_> = t.matches.iter().map(|m| (m.id, m.team_1[0])).collect();
+    for (id, team_1) in ids {
+        let winner = if team_1 == loser {
+            Team::Two
+        } else {
+            Team::One
+        };
+        t.match_results.insert(id, winner.into());
+    }
+    process_group_play_results(&mut t).unwrap();
+
+    let p = t
+        .eliminated_players
+        .iter()
+        .find(|p| p.id == loser)
+        .expect("second loss eliminates");
+    assert_eq!(p.losses, 2);
+    assert_eq!(p.elimination_reason, Some(EliminationReason::MaxLosses));
+}
+
+#[test]
+fn a_win_that_ends_the_grace_still_eliminates_at_max_losses() {
+    let mut t = group_play_with(10, 1);
+    t.set_grace_matches(1).unwrap();
+    generate_group_play_matches(&mut t).unwrap();
+    let ids: Vec<_> = t.matches.iter().map(|m| m.id).collect();
+    for id in ids {
+        t.match_results.insert(id, Team::One.into());
+    }
+    process_group_play_results(&mut t).unwrap();
+    assert!(t.eliminated_players.is_empty());
+
+    // Whoever won or lost, everyone has now played past the grace with a loss or more is out.
+    generate_group_play_matches(&mut t).unwrap();
+    let ids: Vec<_> = t.matches.iter().map(|m| m.id).collect();
+    for id in ids {
+        t.match_results.insert(id, Team::One.into());
+    }
+    process_group_play_results(&mut t).unwrap();
+    assert!(t.players.iter().all(|p| p.losses == 0));
+    assert!(t
+        .eliminated_players
+        .iter()
+        .all(|p| p.losses >= 1 && p.elimination_reason == Some(EliminationReason::MaxLosses)));
+    assert!(t.eliminated_players.iter().any(|p| p.wins == 1));
+}
+
+#[test]
+fn forfeit_counted_as_a_loss_respects_the_grace() {
+    let mut t = group_play_with(6, 1);
+    t.set_grace_matches(1).unwrap();
+    t.set_forfeit_policy(ForfeitPolicy::CountsAsLoss).unwrap();
+    let id = t.players[0].id;
+    t.forfeit_player(id).unwrap();
+
+    let p = t.players.iter().find(|p| p.id == id).expect("still in");
+    assert_eq!(p.losses, 1);
+    assert!(t.eliminated_players.is_empty());
+}
+
+#[test]
+fn round_cap_moves_to_final_selection_with_the_best_records() {
+    let mut t = group_play_with(16, 5);
+    assert_eq!(
+        t.set_round_cap(Some(0)),
+        Err(TournamentError::InvalidConfig(
+            "round_cap must be at least 1"
+        ))
+    );
+    t.set_round_cap(Some(2)).unwrap();
+
+    play_round(&mut t);
+    assert_eq!(t.state, TournamentState::GroupPlay);
+    assert_eq!(t.players.len(), 16);
+
+    play_round(&mut t);
+    assert_eq!(t.state, TournamentState::FinalSelection);
+    assert_eq!(t.players.len(), 4);
+    assert!(t.can_start_semi_finals());
+
+    let worst_finalist = t.players.iter().map(|p| p.wins).min().unwrap();
+    let cut: Vec<_> = t
+        .eliminated_players
+        .iter()
+        .filter(|p| p.elimination_reason == Some(EliminationReason::RoundCap))
+        .collect();
+    assert_eq!(cut.len(), 12);
+    assert!(cut.iter().all(|p| p.wins <= worst_finalist));
+    assert!(cut.iter().all(|p| p.eliminated_at_round == Some(2)));
+}
+
+#[test]
+fn round_cap_keeps_the_projected_finalists_whatever_the_points() {
+    let mut t = group_play_with(8, 5);
+    t.points_config = PointsConfig {
+        win: 1,
+        draw: 5,
+        loss: 0,
+    };
+    for (i, p) in t.players.iter_mut().enumerate() {
+        if i < 4 {
+            p.wins = 10;
+        } else {
+            p.draws = 20;
+        }
+    }
+    let mut projected: Vec<_> = t.projected_finalists().iter().map(|p| p.id).collect();
+    t.set_round_cap(Some(1)).unwrap();
+
+    play_round(&mut t);
+    assert_eq!(t.state, TournamentState::FinalSelection);
+    let mut kept: Vec<_> = t.players.iter().map(|p| p.id).collect();
+    kept.sort();
+    projected.sort();
+    assert_eq!(kept, projected);
+}
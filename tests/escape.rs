@@ -0,0 +1,54 @@
+//! Player names must come out escaped in every server-rendered format.
+
+use dart_tournament_web::escape::html;
+use dart_tournament_web::{
+    bracket_svg, generate_semi_final_matches, process_finals_results, process_semi_final_results,
+    results_html, start_semi_finals, Player, Team, Tournament, TournamentMode, TournamentState,
+};
+
+const SCRIPT: &str = "<script>";
+
+/// Completed 1v1 tournament in which `name` plays (and wins) the semi-final and the finals.
+fn completed_with(name: &str) -> Tournament {
+    let mut players: Vec<Player> = (0..4).map(|i| Player::new(format!("P{i}"))).collect();
+    players[0].name = name.to_string();
+    let mut t = Tournament::with_players(players, 3, TournamentMode::OneVOne);
+    t.deterministic = true;
+    t.state = TournamentState::FinalSelection;
+    start_semi_finals(&mut t).unwrap();
+    generate_semi_final_matches(&mut t).unwrap();
+    let ids: Vec<_> = t.matches.iter().map(|m| m.id).collect();
+    for id in ids {
+        t.final_match_results.insert(id, Team::One);
+    }
+    process_semi_final_results(&mut t).unwrap();
+    let finals = t.matches[0].id;
+    t.final_match_results.insert(finals, Team::One);
+    process_finals_results(&mut t).unwrap();
+    t
+}
+
+#[test]
+fn html_escapes_markup_characters() {
+    assert_eq!(
+        html(r#"<a href="x">Tom & 'Jerry'</a>"#),
+        "&lt;a href=&quot;x&quot;&gt;Tom &amp; &apos;Jerry&apos;&lt;/a&gt;"
+    );
+    assert_eq!(html("Plain name"), "Plain name");
+}
+
+#[test]
+fn script_name_is_escaped_in_the_bracket_svg() {
+    let svg = bracket_svg(&completed_with(SCRIPT));
+    assert!(svg.contains("&lt;script&gt;"));
+    assert!(!svg.contains(SCRIPT));
+}
+
+#[test]
+fn script_name_is_escaped_in_the_results_html() {
+    let t = completed_with(SCRIPT);
+    let sheet = results_html(&t);
+    assert!(sheet.contains("Champion: &lt;script&gt;"));
+    assert!(sheet.contains("<td>&lt;script&gt;</td>"));
+    assert!(!sheet.contains(SCRIPT));
+}
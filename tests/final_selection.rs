@@ -0,0 +1,295 @@
+//! Integration tests for final selection: adding eliminated players back to reach the semi-finals.
+
+use dart_tournament_web::{
+    add_players_back_from_last_eliminated, auto_fill_finalists, generate_group_play_matches,
+    process_group_play_results, process_group_play_results_summary, AddBackRejection,
+    EliminationReason, Player, Team, Tournament, TournamentError, TournamentMode, TournamentState,
+};
+
+/// 2v2 tournament stuck in FinalSelection: `active` players left, `last` eliminated in the final
+/// group round and `earlier` eliminated in previous rounds.
+fn stuck_tournament(active: usize, last: usize, earlier: usize) -> Tournament {
+    let players: Vec<Player> = (0..active).map(|i| Player::new(format!("A{i}"))).collect();
+    let mut t = Tournament::with_players(players, 2, TournamentMode::TwoVTwo);
+    t.state = TournamentState::FinalSelection;
+    for i in 0..earlier {
+        let mut p = Player::new(format!("E{i}"));
+        p.eliminate(EliminationReason::MaxLosses);
+        t.eliminated_players.push(p);
+    }
+    for i in 0..last {
+        let mut p = Player::new(format!("L{i}"));
+        p.eliminate(EliminationReason::MaxLosses);
+        t.eliminated_players.push(p.clone());
+        t.last_eliminated_players.push(p);
+    }
+    t
+}
+
+#[test]
+fn last_eliminated_alone_cannot_fill_semis() {
+    let mut t = stuck_tournament(4, 2, 3);
+    let ids: Vec<_> = t.last_eliminated_players.iter().map(|p| p.id).collect();
+    assert_eq!(
+        add_players_back_from_last_eliminated(&mut t, &ids),
+        Err(TournamentError::WrongNumberOfPlayers {
+            needed: 4,
+            selected: 2
+        })
+    );
+    assert_eq!(t.state, TournamentState::FinalSelection);
+}
+
+#[test]
+fn add_back_from_all_eliminated_reaches_semis() {
+    let mut t = stuck_tournament(4, 2, 3);
+    let mut ids: Vec<_> = t.last_eliminated_players.iter().map(|p| p.id).collect();
+    ids.extend(t.eliminated_players.iter().take(2).map(|p| p.id));
+
+    t.add_back_from_all_eliminated(&ids).unwrap();
+
+    assert_eq!(t.state, TournamentState::SemiFinals);
+    assert_eq!(t.players.len(), 8);
+    assert!(t.players.iter().all(|p| !p.eliminated));
+    assert_eq!(t.eliminated_players.len(), 1);
+    assert!(t.last_eliminated_players.is_empty());
+}
+
+#[test]
+fn add_back_from_all_eliminated_errors_when_pool_too_small() {
+    let mut t = stuck_tournament(4, 1, 1);
+    let ids: Vec<_> = t.eliminated_players.iter().map(|p| p.id).collect();
+    assert_eq!(
+        t.add_back_from_all_eliminated(&ids),
+        Err(TournamentError::NotEnoughEliminatedPlayers {
+            needed: 4,
+            available: 2
+        })
+    );
+}
+
+#[test]
+fn add_back_from_all_eliminated_requires_insufficient_last_eliminated() {
+    let mut t = stuck_tournament(6, 2, 2);
+    let ids: Vec<_> = t.eliminated_players.iter().take(2).map(|p| p.id).collect();
+    assert_eq!(
+        t.add_back_from_all_eliminated(&ids),
+        Err(TournamentError::InvalidState)
+    );
+}
+
+#[test]
+fn add_back_from_all_eliminated_rejects_non_eliminated_player() {
+    let mut t = stuck_tournament(4, 2, 3);
+    let mut ids: Vec<_> = t.eliminated_players.iter().take(3).map(|p| p.id).collect();
+    let stranger = Player::new("X").id;
+    ids.push(stranger);
+    assert_eq!(
+        t.add_back_from_all_eliminated(&ids),
+        Err(TournamentError::PlayerNotEliminated(stranger))
+    );
+}
+
+#[test]
+fn reinstated_players_lose_elimination_reason() {
+    let mut t = stuck_tournament(6, 2, 0);
+    let ids: Vec<_> = t.last_eliminated_players.iter().map(|p| p.id).collect();
+    add_players_back_from_last_eliminated(&mut t, &ids).unwrap();
+    assert!(t.players.iter().all(|p| p.elimination_reason.is_none()));
+}
+
+#[test]
+fn final_selection_info_lists_needed_and_candidates() {
+    let t = stuck_tournament(5, 4, 2);
+    let info = t.final_selection_info().unwrap();
+    assert_eq!(info.needed, 3);
+    let expected: Vec<_> = t.last_eliminated_players.iter().map(|p| p.id).collect();
+    let got: Vec<_> = info.candidates.iter().map(|c| c.id).collect();
+    assert_eq!(got, expected);
+    assert!(info.candidates.iter().all(|c| c.stats.eliminated_status));
+}
+
+#[test]
+fn final_selection_info_requires_final_selection() {
+    let mut t = stuck_tournament(9, 0, 0);
+    t.state = TournamentState::GroupPlay;
+    assert_eq!(t.final_selection_info(), Err(TournamentError::InvalidState));
+}
+
+#[test]
+fn add_back_rejection_explains_wrong_count() {
+    let mut t = stuck_tournament(6, 3, 0);
+    let eligible: Vec<_> = t.last_eliminated_players.iter().map(|p| p.id).collect();
+    let err = add_players_back_from_last_eliminated(&mut t, &eligible[..1]).unwrap_err();
+    assert_eq!(
+        t.add_back_rejection(&err),
+        Some(AddBackRejection::WrongCount {
+            needed: 2,
+            selected: 1,
+            eligible_ids: eligible,
+        })
+    );
+}
+
+#[test]
+fn add_back_rejection_names_the_ineligible_player() {
+    let mut t = stuck_tournament(6, 3, 1);
+    let eligible: Vec<_> = t.last_eliminated_players.iter().map(|p| p.id).collect();
+    let earlier = t.eliminated_players[0].id;
+    let err = add_players_back_from_last_eliminated(&mut t, &[eligible[0], earlier]).unwrap_err();
+    let rejection = t.add_back_rejection(&err).unwrap();
+    assert_eq!(
+        rejection,
+        AddBackRejection::IneligiblePlayer {
+            player_id: earlier,
+            eligible_ids: eligible.clone(),
+        }
+    );
+
+    let json = serde_json::to_value(&rejection).unwrap();
+    assert_eq!(json["reason"], "ineligible_player");
+    assert_eq!(json["player_id"], serde_json::json!(earlier));
+    assert_eq!(json["eligible_ids"], serde_json::json!(eligible));
+    assert_eq!(t.add_back_rejection(&TournamentError::InvalidState), None);
+}
+
+#[test]
+fn auto_fill_finalists_picks_best_records() {
+    let mut t = stuck_tournament(6, 4, 0);
+    // (wins, losses, times sat out, join order)
+    let records = [(1, 3, 0, 0), (2, 3, 0, 1), (2, 2, 0, 3), (2, 2, 1, 2)];
+    for (p, &(wins, losses, sat_out, join_order)) in
+        t.last_eliminated_players.iter_mut().zip(&records)
+    {
+        p.wins = wins;
+        p.losses = losses;
+        p.times_sat_out = sat_out;
+        p.join_order = join_order;
+    }
+    let expected = [
+        t.last_eliminated_players[2].id,
+        t.last_eliminated_players[3].id,
+    ];
+
+    auto_fill_finalists(&mut t).unwrap();
+
+    assert_eq!(t.state, TournamentState::SemiFinals);
+    let rejoined: Vec<_> = t.players[6..].iter().map(|p| p.id).collect();
+    assert_eq!(rejoined, expected);
+    assert!(t.players.iter().all(|p| !p.eliminated));
+    // Same as a manual add-back: the unpicked candidates are no longer offered.
+    assert!(t.last_eliminated_players.is_empty());
+    assert_eq!(t.eliminated_players.len(), 2);
+}
+
+#[test]
+fn auto_fill_finalists_breaks_ties_by_join_order() {
+    let mut t = stuck_tournament(7, 3, 0);
+    for (p, join_order) in t.last_eliminated_players.iter_mut().zip([9, 4, 6]) {
+        p.join_order = join_order;
+    }
+    let earliest = t.last_eliminated_players[1].id;
+    auto_fill_finalists(&mut t).unwrap();
+    assert_eq!(t.players.last().unwrap().id, earliest);
+
+    let mut short = stuck_tournament(4, 2, 3);
+    assert_eq!(
+        auto_fill_finalists(&mut short),
+        Err(TournamentError::NotEnoughEliminatedPlayers {
+            needed: 4,
+            available: 2
+        })
+    );
+}
+
+/// 16-player 2v2 group play with one life: one round leaves exactly 8.
+fn sixteen_one_life(auto_start_semis: bool) -> Tournament {
+    let players: Vec<Player> = (0..16).map(|i| Player::new(format!("P{i}"))).collect();
+    let mut t = Tournament::with_players(players, 1, TournamentMode::TwoVTwo);
+    t.state = TournamentState::GroupPlay;
+    t.set_auto_start_semis(auto_start_semis).unwrap();
+    generate_group_play_matches(&mut t).unwrap();
+    for m in &t.matches {
+        t.match_results.insert(m.id, Team::One.into());
+    }
+    process_group_play_results(&mut t).unwrap();
+    t
+}
+
+#[test]
+fn auto_start_semis_skips_final_selection_on_an_exact_field() {
+    let t = sixteen_one_life(true);
+    assert_eq!(t.players.len(), 8);
+    assert_eq!(t.state, TournamentState::SemiFinals);
+}
+
+#[test]
+fn exact_field_waits_in_final_selection_by_default() {
+    let t = sixteen_one_life(false);
+    assert_eq!(t.players.len(), 8);
+    assert_eq!(t.state, TournamentState::FinalSelection);
+}
+
+/// `stuck_tournament(6, 4, 0)` (two slots open) with the given (wins, losses) per candidate.
+fn cutoff_with_records(records: [(u32, u32); 4]) -> Tournament {
+    let mut t = stuck_tournament(6, 4, 0);
+    for (p, (wins, losses)) in t.last_eliminated_players.iter_mut().zip(records) {
+        p.wins = wins;
+        p.losses = losses;
+    }
+    t
+}
+
+#[test]
+fn tie_straddling_the_cutoff_is_reported() {
+    // One clear pick, then three with the same record for the last spot.
+    let t = cutoff_with_records([(1, 2), (3, 2), (1, 2), (1, 2)]);
+    let ids: Vec<_> = t.last_eliminated_players.iter().map(|p| p.id).collect();
+    let mut tied = t.tied_at_cutoff();
+    tied.sort();
+    let mut expected = vec![ids[0], ids[2], ids[3]];
+    expected.sort();
+    assert_eq!(tied, expected);
+    assert_eq!(t.final_selection_info().unwrap().tied_at_cutoff.len(), 3);
+}
+
+#[test]
+fn no_tie_when_the_cutoff_separates_records() {
+    let t = cutoff_with_records([(1, 2), (3, 2), (3, 2), (1, 2)]);
+    assert!(t.tied_at_cutoff().is_empty());
+
+    // Exactly as many candidates as open slots: everyone rejoins, nothing to decide.
+    let exact = stuck_tournament(6, 2, 0);
+    assert!(exact.tied_at_cutoff().is_empty());
+
+    let mut group_play = cutoff_with_records([(1, 2); 4]);
+    assert_eq!(group_play.tied_at_cutoff().len(), 4);
+    group_play.state = TournamentState::GroupPlay;
+    assert!(group_play.tied_at_cutoff().is_empty());
+}
+
+#[test]
+fn round_dropping_below_the_field_surfaces_the_add_back() {
+    let players: Vec<Player> = (0..9).map(|i| Player::new(format!("P{i}"))).collect();
+    let mut t = Tournament::with_players(players, 2, TournamentMode::TwoVTwo);
+    t.state = TournamentState::GroupPlay;
+    generate_group_play_matches(&mut t).unwrap();
+    // Three of the four losers are on their last life: 9 active drop to 6.
+    let losers: Vec<_> = t.matches.iter().flat_map(|m| m.team_2.clone()).collect();
+    for id in &losers[..3] {
+        t.players.iter_mut().find(|p| p.id == *id).unwrap().losses = 1;
+    }
+    for m in &t.matches {
+        t.match_results.insert(m.id, Team::One.into());
+    }
+
+    let summary = process_group_play_results_summary(&mut t).unwrap();
+
+    assert_eq!(t.players.len(), 6);
+    assert_eq!(t.state, TournamentState::FinalSelection);
+    assert!(summary.needs_add_back);
+    assert_eq!(summary.add_back_needed, 2);
+    let info = t.final_selection_info().unwrap();
+    assert_eq!(info.needed, 2);
+    assert_eq!(info.candidates.len(), 3);
+}
@@ -0,0 +1,839 @@
+//! Integration tests for the final rounds: semi-final bracket shapes, semi-finals and finals.
+
+mod common;
+
+use common::{teams, tournament_in};
+use dart_tournament_web::{
+    bracket_svg, generate_group_play_matches, generate_semi_final_matches, process_finals_results,
+    process_grand_finals_results, process_semi_final_results, record_drawn_final,
+    record_finals_leg, results_html, semi_final_bracket_shape, set_finals_match_winner,
+    set_sudden_death_players, start_grand_finals, start_semi_finals, FinalTieResolution,
+    FinalsFormat, GameMatch, Player, PlayerSummary, RoundType, ScoringMode, SeedingStrategy,
+    SemiFinalShape, Team, Tournament, TournamentError, TournamentMode, TournamentState,
+};
+
+fn final_selection_with(n: usize, mode: TournamentMode, format: FinalsFormat) -> Tournament {
+    let mut t = tournament_in(n, 3, mode, TournamentState::FinalSelection);
+    t.finals_format = format;
+    t
+}
+
+/// Start semis, generate matches, pick team 1 everywhere, and process into the finals.
+fn play_semis(t: &mut Tournament) {
+    start_semi_finals(t).unwrap();
+    generate_semi_final_matches(t).unwrap();
+    let ids: Vec<_> = t.matches.iter().map(|m| m.id).collect();
+    for id in ids {
+        t.final_match_results.insert(id, Team::One);
+    }
+    process_semi_final_results(t).unwrap();
+}
+
+#[test]
+fn bracket_shapes_for_small_2v2_fields() {
+    let shape = |n| semi_final_bracket_shape(TournamentMode::TwoVTwo, n);
+    assert_eq!(
+        shape(8),
+        Some(SemiFinalShape {
+            full_matches: 2,
+            single_matches: 0,
+            byes: 0
+        })
+    );
+    assert_eq!(
+        shape(7),
+        Some(SemiFinalShape {
+            full_matches: 1,
+            single_matches: 1,
+            byes: 1
+        })
+    );
+    assert_eq!(
+        shape(6),
+        Some(SemiFinalShape {
+            full_matches: 1,
+            single_matches: 0,
+            byes: 2
+        })
+    );
+    assert_eq!(
+        shape(5),
+        Some(SemiFinalShape {
+            full_matches: 0,
+            single_matches: 1,
+            byes: 3
+        })
+    );
+    assert_eq!(shape(4), None);
+    assert_eq!(shape(9), None);
+    assert_eq!(
+        semi_final_bracket_shape(TournamentMode::OneVOne, 3),
+        Some(SemiFinalShape {
+            full_matches: 1,
+            single_matches: 0,
+            byes: 1
+        })
+    );
+}
+
+#[test]
+fn standard_format_rejects_small_field() {
+    let mut t = final_selection_with(6, TournamentMode::TwoVTwo, FinalsFormat::Standard);
+    assert_eq!(
+        start_semi_finals(&mut t),
+        Err(TournamentError::InvalidState)
+    );
+}
+
+#[test]
+fn small_field_of_seven_reaches_four_finalists() {
+    let mut t = final_selection_with(7, TournamentMode::TwoVTwo, FinalsFormat::SmallField);
+    start_semi_finals(&mut t).unwrap();
+    generate_semi_final_matches(&mut t).unwrap();
+    assert_eq!(t.matches.len(), 2);
+    assert_eq!(t.matches[0].team_1.len(), 2);
+    assert_eq!(t.matches[1].team_1.len(), 1);
+    assert_eq!(t.semi_final_byes.len(), 1);
+    let bye = t.semi_final_byes[0];
+
+    let ids: Vec<_> = t.matches.iter().map(|m| m.id).collect();
+    for id in ids {
+        t.final_match_results.insert(id, Team::One);
+    }
+    process_semi_final_results(&mut t).unwrap();
+    assert_eq!(t.state, TournamentState::Finals);
+    assert_eq!(t.players.len(), 4);
+    assert!(t.players.iter().any(|p| p.id == bye));
+    assert!(t.semi_final_byes.is_empty());
+    assert!(t.check_invariants().is_empty());
+    let name = t.player_name(bye);
+    assert!(bracket_svg(&t).contains(&format!("{name} (bye)")));
+}
+
+#[test]
+fn small_field_of_six_plays_one_semi_with_two_byes() {
+    let mut t = final_selection_with(6, TournamentMode::TwoVTwo, FinalsFormat::SmallField);
+    start_semi_finals(&mut t).unwrap();
+    generate_semi_final_matches(&mut t).unwrap();
+    assert_eq!(t.matches.len(), 1);
+    assert_eq!(t.semi_final_byes.len(), 2);
+}
+
+#[test]
+fn small_field_of_five_completes_tournament() {
+    let mut t = final_selection_with(5, TournamentMode::TwoVTwo, FinalsFormat::SmallField);
+    play_semis(&mut t);
+    assert_eq!(t.state, TournamentState::Finals);
+    assert_eq!(t.players.len(), 4);
+    assert_eq!(t.matches[0].team_1.len(), 2);
+
+    let finals_id = t.matches[0].id;
+    t.final_match_results.insert(finals_id, Team::Two);
+    process_finals_results(&mut t).unwrap();
+    assert_eq!(t.state, TournamentState::Completed);
+}
+
+#[test]
+fn small_field_requires_more_than_finals_size() {
+    let mut t = final_selection_with(4, TournamentMode::TwoVTwo, FinalsFormat::SmallField);
+    assert_eq!(
+        start_semi_finals(&mut t),
+        Err(TournamentError::InvalidState)
+    );
+}
+
+#[test]
+fn finals_reject_malformed_match() {
+    let mut t = final_selection_with(8, TournamentMode::TwoVTwo, FinalsFormat::Standard);
+    play_semis(&mut t);
+    t.matches[0].team_1.truncate(1);
+    let id = t.matches[0].id;
+    t.final_match_results.insert(id, Team::One);
+    assert_eq!(
+        process_finals_results(&mut t),
+        Err(TournamentError::InvalidMatchComposition(id))
+    );
+}
+
+#[test]
+fn standard_semis_reject_1v1_match_in_2v2() {
+    let mut t = final_selection_with(8, TournamentMode::TwoVTwo, FinalsFormat::Standard);
+    start_semi_finals(&mut t).unwrap();
+    generate_semi_final_matches(&mut t).unwrap();
+    t.matches[0].team_1.truncate(1);
+    t.matches[0].team_2.truncate(1);
+    let ids: Vec<_> = t.matches.iter().map(|m| m.id).collect();
+    for id in &ids {
+        t.final_match_results.insert(*id, Team::One);
+    }
+    assert_eq!(
+        process_semi_final_results(&mut t),
+        Err(TournamentError::InvalidMatchComposition(ids[0]))
+    );
+}
+
+/// Names in each semi-final match: (team 1, team 2).
+fn semi_pairings(t: &Tournament) -> Vec<(Vec<String>, Vec<String>)> {
+    let name = |id: &_| t.players.iter().find(|p| p.id == *id).unwrap().name.clone();
+    t.matches
+        .iter()
+        .map(|m| {
+            (
+                m.team_1.iter().map(name).collect(),
+                m.team_2.iter().map(name).collect(),
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn snake_by_rating_pairs_best_against_worst() {
+    let mut t = final_selection_with(4, TournamentMode::OneVOne, FinalsFormat::Standard);
+    t.seeding = SeedingStrategy::SnakeByRating;
+    for (p, rating) in t.players.iter_mut().zip([1500, 1800, 1200, 1650]) {
+        p.rating = Some(rating);
+    }
+    start_semi_finals(&mut t).unwrap();
+    generate_semi_final_matches(&mut t).unwrap();
+
+    let pairings = semi_pairings(&t);
+    let s = |n: &str| vec![n.to_string()];
+    assert_eq!(pairings, vec![(s("P1"), s("P2")), (s("P3"), s("P0"))]);
+}
+
+#[test]
+fn snake_by_rating_builds_2v2_teams_and_falls_back_to_wins() {
+    let mut t = final_selection_with(8, TournamentMode::TwoVTwo, FinalsFormat::Standard);
+    t.seeding = SeedingStrategy::SnakeByRating;
+    // P0..P5 rated high to low; P6 and P7 unrated, P7 has more wins.
+    for (i, p) in t.players.iter_mut().enumerate().take(6) {
+        p.rating = Some(2000 - i as i32 * 100);
+    }
+    t.players[7].wins = 3;
+    start_semi_finals(&mut t).unwrap();
+    generate_semi_final_matches(&mut t).unwrap();
+
+    let sorted = |mut v: Vec<String>| {
+        v.sort();
+        v
+    };
+    let pairings: Vec<_> = semi_pairings(&t)
+        .into_iter()
+        .map(|(a, b)| (sorted(a), sorted(b)))
+        .collect();
+    let team = |a: &str, b: &str| sorted(vec![a.to_string(), b.to_string()]);
+    // Seeds: P0 P1 P2 P3 P4 P5 P7 P6 -> teams {P0,P6} {P1,P7} {P2,P5} {P3,P4}.
+    assert_eq!(
+        pairings,
+        vec![
+            (team("P0", "P6"), team("P3", "P4")),
+            (team("P1", "P7"), team("P2", "P5")),
+        ]
+    );
+}
+
+#[test]
+fn snake_seeded_2v2_semi_winners_stay_together_in_the_finals() {
+    let mut t = final_selection_with(8, TournamentMode::TwoVTwo, FinalsFormat::Standard);
+    t.seeding = SeedingStrategy::SnakeByWins;
+    for (i, p) in t.players.iter_mut().enumerate() {
+        p.wins = 8 - i as u32;
+    }
+    start_semi_finals(&mut t).unwrap();
+    generate_semi_final_matches(&mut t).unwrap();
+    let semis = t.matches.clone();
+    set_finals_match_winner(&mut t, semis[0].id, Team::Two).unwrap();
+    set_finals_match_winner(&mut t, semis[1].id, Team::One).unwrap();
+
+    let projected = t.projected_bracket();
+    assert_eq!(projected.finals.len(), 1);
+    assert_eq!(projected.finals[0].team_1, semis[0].team_2);
+    assert_eq!(projected.finals[0].team_2, semis[1].team_1);
+
+    process_semi_final_results(&mut t).unwrap();
+    assert_eq!(t.matches[0].team_1, semis[0].team_2);
+    assert_eq!(t.matches[0].team_2, semis[1].team_1);
+}
+
+#[test]
+fn snake_seeding_gives_byes_to_top_seeds() {
+    let mut t = final_selection_with(3, TournamentMode::OneVOne, FinalsFormat::SmallField);
+    t.seeding = SeedingStrategy::SnakeByRating;
+    t.players[2].rating = Some(1900);
+    start_semi_finals(&mut t).unwrap();
+    generate_semi_final_matches(&mut t).unwrap();
+    assert_eq!(
+        t.semi_final_byes,
+        vec![t.players.iter().find(|p| p.name == "P2").unwrap().id]
+    );
+}
+
+#[test]
+fn finals_seeding_lists_every_semi_finalist_in_draw_order() {
+    let mut t = final_selection_with(8, TournamentMode::TwoVTwo, FinalsFormat::Standard);
+    assert_eq!(t.finals_seeding_view(), Err(TournamentError::InvalidState));
+    start_semi_finals(&mut t).unwrap();
+    generate_semi_final_matches(&mut t).unwrap();
+
+    let seeding = t.finals_seeding_view().unwrap();
+    assert_eq!(seeding.len(), 8);
+    let first_match: Vec<_> = t.matches[0]
+        .team_1
+        .iter()
+        .chain(&t.matches[0].team_2)
+        .collect();
+    let drawn: Vec<_> = seeding.iter().map(|p| &p.id).take(4).collect();
+    assert_eq!(drawn, first_match);
+    assert!(seeding.iter().all(|p| p.name.starts_with('P')));
+
+    // Semi-final losers are out, but the draw still names them.
+    let ids: Vec<_> = t.matches.iter().map(|m| m.id).collect();
+    for id in ids {
+        t.final_match_results.insert(id, Team::One);
+    }
+    process_semi_final_results(&mut t).unwrap();
+    assert_eq!(t.finals_seeding_view().unwrap().len(), 8);
+}
+
+#[test]
+fn bracket_view_names_semi_finalists_and_finalists() {
+    let mut t = final_selection_with(4, TournamentMode::OneVOne, FinalsFormat::Standard);
+    assert!(t.bracket_view().is_empty());
+    play_semis(&mut t);
+
+    let bracket = t.bracket_view();
+    assert_eq!(bracket.len(), 3);
+    assert_eq!(bracket[0].round, RoundType::SemiFinals);
+    assert_eq!(bracket[0].winner, Some(Team::One));
+    assert_eq!(bracket[2].round, RoundType::Finals);
+    assert_eq!(bracket[2].winner, None);
+    let mut names: Vec<_> = bracket[..2]
+        .iter()
+        .flat_map(|m| m.team_1.iter().chain(&m.team_2))
+        .map(|p| p.name.clone())
+        .collect();
+    names.sort();
+    assert_eq!(names, ["P0", "P1", "P2", "P3"]);
+}
+
+#[test]
+fn bracket_svg_placeholder_before_semis() {
+    let t = final_selection_with(8, TournamentMode::TwoVTwo, FinalsFormat::Standard);
+    let svg = bracket_svg(&t);
+    assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\""));
+    assert!(svg.trim_end().ends_with("</svg>"));
+    assert!(svg.contains("once the semi-finals are played"));
+}
+
+#[test]
+fn bracket_svg_shows_names_and_escapes_them() {
+    let mut t = final_selection_with(4, TournamentMode::OneVOne, FinalsFormat::Standard);
+    t.players[0].name = "Tom & <Jerry>".to_string();
+    play_semis(&mut t);
+    let finals_id = t.matches[0].id;
+    t.final_match_results.insert(finals_id, Team::One);
+    process_finals_results(&mut t).unwrap();
+
+    let svg = bracket_svg(&t);
+    assert!(svg.contains("Tom &amp; &lt;Jerry&gt;"));
+    assert!(!svg.contains("<Jerry>"));
+    for name in ["P1", "P2", "P3"] {
+        assert!(svg.contains(name));
+    }
+    assert!(svg.contains("Winners"));
+    assert_eq!(svg.matches("<svg").count(), 1);
+    assert!(svg.trim_end().ends_with("</svg>"));
+}
+
+#[test]
+fn best_of_three_semi_advances_the_series_winner() {
+    let mut t = final_selection_with(4, TournamentMode::OneVOne, FinalsFormat::Standard);
+    t.set_legs_per_final_match(3).unwrap();
+    start_semi_finals(&mut t).unwrap();
+    generate_semi_final_matches(&mut t).unwrap();
+    let (a, b) = (t.matches[0].clone(), t.matches[1].clone());
+
+    assert_eq!(
+        set_finals_match_winner(&mut t, a.id, Team::One),
+        Err(TournamentError::SeriesMatch(a.id))
+    );
+    // Match a: team 2 wins 2-1. Match b: team 1 wins 2-0.
+    record_finals_leg(&mut t, a.id, Team::One).unwrap();
+    record_finals_leg(&mut t, a.id, Team::Two).unwrap();
+    record_finals_leg(&mut t, b.id, Team::One).unwrap();
+    assert_eq!(
+        process_semi_final_results(&mut t),
+        Err(TournamentError::IncompleteResults)
+    );
+    record_finals_leg(&mut t, a.id, Team::Two).unwrap();
+    record_finals_leg(&mut t, b.id, Team::One).unwrap();
+    // Series decided: no more legs.
+    assert_eq!(
+        record_finals_leg(&mut t, b.id, Team::Two),
+        Err(TournamentError::InvalidState)
+    );
+
+    process_semi_final_results(&mut t).unwrap();
+
+    assert_eq!(t.state, TournamentState::Finals);
+    let finalists: Vec<_> = t.players.iter().map(|p| p.id).collect();
+    assert!(finalists.contains(&a.team_2[0]) && finalists.contains(&b.team_1[0]));
+    let recorded = t.bracket_semi_final_matches.as_ref().unwrap();
+    let legs_a = &recorded.iter().find(|m| m.id == a.id).unwrap().legs;
+    assert_eq!(legs_a, &[Team::One, Team::Two, Team::Two]);
+}
+
+#[test]
+fn scoring_mode_takes_bare_group_winners_but_finals_legs() {
+    let players: Vec<Player> = (0..6).map(|i| Player::new(format!("P{i}"))).collect();
+    let mut t = Tournament::with_players(players, 3, TournamentMode::OneVOne);
+    t.state = TournamentState::GroupPlay;
+    t.set_legs_per_final_match(3).unwrap();
+    assert!(matches!(
+        t.set_scoring_mode(RoundType::GroupPlay, ScoringMode::Legs),
+        Err(TournamentError::InvalidConfig(_))
+    ));
+    assert_eq!(t.scoring_mode(RoundType::GroupPlay), ScoringMode::Winner);
+    assert_eq!(t.scoring_mode(RoundType::Finals), ScoringMode::Legs);
+
+    generate_group_play_matches(&mut t).unwrap();
+    let group_id = t.matches[0].id;
+    t.set_match_outcome(group_id, Team::One.into()).unwrap();
+
+    t.players.truncate(4);
+    t.matches.clear();
+    t.match_results.clear();
+    t.state = TournamentState::FinalSelection;
+    play_semis(&mut t);
+    let finals_id = t.matches[0].id;
+    assert_eq!(
+        set_finals_match_winner(&mut t, finals_id, Team::One),
+        Err(TournamentError::SeriesMatch(finals_id))
+    );
+    record_finals_leg(&mut t, finals_id, Team::One).unwrap();
+    record_finals_leg(&mut t, finals_id, Team::One).unwrap();
+    process_finals_results(&mut t).unwrap();
+    assert_eq!(t.state, TournamentState::Completed);
+}
+
+#[test]
+fn even_leg_counts_are_rejected() {
+    let mut t = final_selection_with(4, TournamentMode::OneVOne, FinalsFormat::Standard);
+    assert_eq!(
+        t.set_legs_per_final_match(2),
+        Err(TournamentError::InvalidLegCount(2))
+    );
+    assert_eq!(
+        t.set_legs_per_final_match(0),
+        Err(TournamentError::InvalidLegCount(0))
+    );
+    for legs in [1, 3, 5, 7] {
+        t.set_legs_per_final_match(legs).unwrap();
+        // An odd series always has a strict majority: 2 * legs_to_win > legs.
+        assert!(2 * t.legs_to_win() > legs);
+        assert!(t.legs_to_win() <= legs);
+    }
+}
+
+#[test]
+fn deterministic_semi_finals_repeat_exactly() {
+    let mut t = final_selection_with(8, TournamentMode::TwoVTwo, FinalsFormat::Standard);
+    t.deterministic = true;
+    let mut again = t.clone();
+    for t in [&mut t, &mut again] {
+        start_semi_finals(t).unwrap();
+        generate_semi_final_matches(t).unwrap();
+    }
+    assert_eq!(teams(&t), teams(&again));
+    let first: Vec<_> = t.players.iter().take(2).map(|p| p.id).collect();
+    assert_eq!(t.matches[0].team_1, first);
+}
+
+#[test]
+fn projection_lists_every_finals_pairing_when_undecided() {
+    let mut t = final_selection_with(4, TournamentMode::OneVOne, FinalsFormat::Standard);
+    start_semi_finals(&mut t).unwrap();
+    generate_semi_final_matches(&mut t).unwrap();
+
+    let projection = t.projected_bracket();
+    assert_eq!(projection.undecided.len(), 2);
+    assert_eq!(projection.finals.len(), 4);
+    let mut pairings: Vec<_> = projection
+        .finals
+        .iter()
+        .map(|f| {
+            let mut ids = vec![f.team_1[0], f.team_2[0]];
+            ids.sort();
+            ids
+        })
+        .collect();
+    pairings.sort();
+    pairings.dedup();
+    assert_eq!(pairings.len(), 4);
+    assert_eq!(projection.player_names.len(), 4);
+}
+
+#[test]
+fn projection_collapses_decided_semis() {
+    let mut t = final_selection_with(8, TournamentMode::TwoVTwo, FinalsFormat::Standard);
+    start_semi_finals(&mut t).unwrap();
+    generate_semi_final_matches(&mut t).unwrap();
+    let decided = t.matches[0].clone();
+    t.final_match_results.insert(decided.id, Team::Two);
+
+    let projection = t.projected_bracket();
+    assert_eq!(projection.undecided, vec![t.matches[1].id]);
+    assert_eq!(projection.finals.len(), 2);
+    for f in &projection.finals {
+        assert_eq!(f.semi_winners[&decided.id], Team::Two);
+        let finalists: Vec<_> = f.team_1.iter().chain(&f.team_2).collect();
+        assert_eq!(finalists.len(), 4);
+        assert!(decided.team_2.iter().all(|id| finalists.contains(&id)));
+        assert!(decided.team_1.iter().all(|id| !finalists.contains(&id)));
+    }
+
+    // Once the semis are processed the real finals match is the only projection.
+    t.final_match_results.insert(t.matches[1].id, Team::One);
+    process_semi_final_results(&mut t).unwrap();
+    let projection = t.projected_bracket();
+    assert_eq!(projection.finals.len(), 1);
+    assert_eq!(projection.finals[0].team_1, t.matches[0].team_1);
+}
+
+/// Play the semis and the finals of an 8-player 2v2 bracket, team 1 winning everything.
+fn play_to_end_of_finals(t: &mut Tournament) {
+    play_semis(t);
+    let finals_id = t.matches[0].id;
+    set_finals_match_winner(t, finals_id, Team::One).unwrap();
+    process_finals_results(t).unwrap();
+}
+
+#[test]
+fn finals_complete_with_two_winners_by_default() {
+    let mut t = final_selection_with(8, TournamentMode::TwoVTwo, FinalsFormat::Standard);
+    play_to_end_of_finals(&mut t);
+    assert_eq!(t.state, TournamentState::Completed);
+
+    let finals = t.bracket_finals_match.clone().unwrap();
+    let placements = t.final_placements();
+    assert_eq!(placements.len(), 3);
+    assert_eq!(placements[0].place, 1);
+    assert_eq!(placements[0].players, finals.team_1);
+    assert_eq!(placements[1].players, finals.team_2);
+    assert_eq!(placements[2].place, 3);
+    assert_eq!(placements[2].players.len(), 4);
+}
+
+#[test]
+fn single_champion_plays_a_grand_final() {
+    let mut t = final_selection_with(8, TournamentMode::TwoVTwo, FinalsFormat::Standard);
+    t.set_single_champion(true).unwrap();
+    play_to_end_of_finals(&mut t);
+
+    let winners = t.bracket_finals_match.clone().unwrap().team_1;
+    assert_eq!(t.state, TournamentState::GrandFinals);
+    assert!(t.final_placements().is_empty());
+    let grand = t.matches[0].clone();
+    assert_eq!(grand.round, RoundType::GrandFinals);
+    assert_eq!(grand.team_1, vec![winners[0]]);
+    assert_eq!(grand.team_2, vec![winners[1]]);
+    assert_eq!(
+        process_grand_finals_results(&mut t),
+        Err(TournamentError::IncompleteResults)
+    );
+
+    set_finals_match_winner(&mut t, grand.id, Team::Two).unwrap();
+    process_grand_finals_results(&mut t).unwrap();
+    assert_eq!(t.state, TournamentState::Completed);
+    assert_eq!(
+        t.set_single_champion(false),
+        Err(TournamentError::InvalidState)
+    );
+
+    let placements = t.final_placements();
+    let places: Vec<(u32, usize)> = placements
+        .iter()
+        .map(|p| (p.place, p.players.len()))
+        .collect();
+    assert_eq!(places, vec![(1, 1), (2, 1), (3, 2), (4, 4)]);
+    assert_eq!(placements[0].players, vec![winners[1]]);
+    assert_eq!(placements[1].players, vec![winners[0]]);
+}
+
+#[test]
+fn champion_team_name_only_once_the_finals_are_reached() {
+    let mut t = final_selection_with(8, TournamentMode::TwoVTwo, FinalsFormat::Standard);
+    t.state = TournamentState::GroupPlay;
+    assert_eq!(
+        t.set_champion_team_name(Some("The Bullseyes")),
+        Err(TournamentError::InvalidState)
+    );
+    assert_eq!(t.champion_team_name, None);
+
+    t.state = TournamentState::FinalSelection;
+    play_semis(&mut t);
+    assert_eq!(t.state, TournamentState::Finals);
+    t.set_champion_team_name(Some("  The Bullseyes ")).unwrap();
+    assert_eq!(t.champion_team_name.as_deref(), Some("The Bullseyes"));
+
+    let too_long = "x".repeat(t.limits.max_name_len + 1);
+    assert_eq!(
+        t.set_champion_team_name(Some(&too_long)),
+        Err(TournamentError::NameTooLong {
+            max: t.limits.max_name_len
+        })
+    );
+    t.set_champion_team_name(Some("   ")).unwrap();
+    assert_eq!(t.champion_team_name, None);
+}
+
+#[test]
+fn finals_completion_records_completed_at_and_duration() {
+    let mut t = final_selection_with(8, TournamentMode::TwoVTwo, FinalsFormat::Standard);
+    t.started_at = Some(1_000);
+    play_semis(&mut t);
+    assert_eq!(t.completed_at, None);
+    assert_eq!(t.duration(), None);
+
+    let finals_id = t.matches[0].id;
+    set_finals_match_winner(&mut t, finals_id, Team::One).unwrap();
+    process_finals_results(&mut t).unwrap();
+    let completed_at = t.completed_at.expect("completed_at set on completion");
+    assert!(completed_at > 1_000);
+    assert_eq!(
+        t.duration(),
+        Some(std::time::Duration::from_millis(completed_at - 1_000))
+    );
+}
+
+#[test]
+fn final_result_lists_winners_and_runners_up_once_completed() {
+    let mut t = final_selection_with(8, TournamentMode::TwoVTwo, FinalsFormat::Standard);
+    assert_eq!(t.final_result(), None);
+    play_semis(&mut t);
+    assert_eq!(t.final_result(), None);
+
+    let finals = t.matches[0].clone();
+    set_finals_match_winner(&mut t, finals.id, Team::Two).unwrap();
+    process_finals_results(&mut t).unwrap();
+
+    let result = t.final_result().unwrap();
+    assert!(result.completed);
+    let ids = |players: &[PlayerSummary]| players.iter().map(|p| p.id).collect::<Vec<_>>();
+    assert_eq!(ids(&result.winners), finals.team_2);
+    assert_eq!(ids(&result.runners_up), finals.team_1);
+    assert!(result.winners.iter().all(|p| p.stats.wins > 0));
+    assert!(!result.winners[0].name.is_empty());
+}
+
+/// Play the semis of an 8-player 2v2 bracket with `resolution`, then draw the final and submit.
+/// Returns the drawn finals match.
+fn draw_the_final(t: &mut Tournament, resolution: FinalTieResolution) -> GameMatch {
+    t.set_final_tie_resolution(resolution).unwrap();
+    play_semis(t);
+    let finals = t.matches[0].clone();
+    set_finals_match_winner(t, finals.id, Team::One).unwrap();
+    record_drawn_final(t, finals.id).unwrap();
+    assert!(t.final_match_results.is_empty());
+    assert!(t.all_results_in());
+    process_finals_results(t).unwrap();
+    let last = t.match_history.last().unwrap();
+    assert_eq!(last.id, finals.id);
+    assert!(last.draw);
+    for id in finals.team_1.iter().chain(&finals.team_2) {
+        assert_eq!(t.players.iter().find(|p| p.id == *id).unwrap().draws, 1);
+    }
+    finals
+}
+
+#[test]
+fn drawn_final_is_replayed_by_default() {
+    let mut t = final_selection_with(8, TournamentMode::TwoVTwo, FinalsFormat::Standard);
+    assert_eq!(t.final_tie_resolution, FinalTieResolution::Replay);
+    let finals = draw_the_final(&mut t, FinalTieResolution::Replay);
+
+    assert_eq!(t.state, TournamentState::Finals);
+    let replay = t.matches[0].clone();
+    assert_ne!(replay.id, finals.id);
+    assert_eq!(replay.round, RoundType::Finals);
+    assert_eq!(
+        (&replay.team_1, &replay.team_2),
+        (&finals.team_1, &finals.team_2)
+    );
+    assert!(replay.match_number > finals.match_number);
+
+    set_finals_match_winner(&mut t, replay.id, Team::Two).unwrap();
+    process_finals_results(&mut t).unwrap();
+    assert_eq!(t.state, TournamentState::Completed);
+    assert_eq!(t.final_placements()[0].players, finals.team_2);
+    assert_eq!(
+        set_sudden_death_players(&mut t, finals.team_1[0], finals.team_2[0]),
+        Err(TournamentError::InvalidState)
+    );
+}
+
+#[test]
+fn sudden_death_decider_crowns_the_whole_team() {
+    let mut t = final_selection_with(8, TournamentMode::TwoVTwo, FinalsFormat::Standard);
+    let finals = draw_the_final(&mut t, FinalTieResolution::SuddenDeath);
+
+    assert_eq!(t.state, TournamentState::Finals);
+    let decider = t.matches[0].clone();
+    assert_eq!(decider.team_1, vec![finals.team_1[0]]);
+    assert_eq!(decider.team_2, vec![finals.team_2[0]]);
+
+    // A drawn decider is followed by another one, whose players the organizer may pick.
+    record_drawn_final(&mut t, decider.id).unwrap();
+    process_finals_results(&mut t).unwrap();
+    assert_eq!(t.matches[0].team_1, vec![finals.team_1[0]]);
+    assert_eq!(
+        set_sudden_death_players(&mut t, finals.team_2[1], finals.team_2[0]),
+        Err(TournamentError::PlayerNotFound(finals.team_2[1]))
+    );
+    set_sudden_death_players(&mut t, finals.team_1[1], finals.team_2[1]).unwrap();
+    let decider = t.matches[0].clone();
+    assert_eq!(decider.team_1, vec![finals.team_1[1]]);
+    assert_eq!(decider.team_2, vec![finals.team_2[1]]);
+
+    set_finals_match_winner(&mut t, decider.id, Team::Two).unwrap();
+    process_finals_results(&mut t).unwrap();
+    assert_eq!(t.state, TournamentState::Completed);
+    assert_eq!(t.bracket_finals_match.as_ref().unwrap().id, finals.id);
+    assert_eq!(t.bracket_finals_result, Some(Team::Two));
+    let result = t.final_result().unwrap();
+    let ids: Vec<_> = result.winners.iter().map(|p| p.id).collect();
+    assert_eq!(ids, finals.team_2);
+}
+
+#[test]
+fn shared_title_makes_both_finals_teams_champions() {
+    let mut t = final_selection_with(8, TournamentMode::TwoVTwo, FinalsFormat::Standard);
+    let finals = draw_the_final(&mut t, FinalTieResolution::SharedTitle);
+
+    assert_eq!(t.state, TournamentState::Completed);
+    assert!(t.shared_title);
+    assert!(t.matches.is_empty());
+    let placements = t.final_placements();
+    let places: Vec<(u32, usize)> = placements
+        .iter()
+        .map(|p| (p.place, p.players.len()))
+        .collect();
+    assert_eq!(places, vec![(1, 4), (2, 4)]);
+    for id in finals.team_1.iter().chain(&finals.team_2) {
+        assert!(placements[0].players.contains(id));
+    }
+    let result = t.final_result().unwrap();
+    assert_eq!(result.winners.len(), 4);
+    assert!(result.runners_up.is_empty());
+    assert_eq!(
+        start_grand_finals(&mut t),
+        Err(TournamentError::InvalidState)
+    );
+    assert_eq!(
+        t.set_final_tie_resolution(FinalTieResolution::Replay),
+        Err(TournamentError::InvalidState)
+    );
+}
+
+#[test]
+fn only_a_single_finals_match_can_be_drawn() {
+    let mut t = final_selection_with(8, TournamentMode::TwoVTwo, FinalsFormat::Standard);
+    start_semi_finals(&mut t).unwrap();
+    generate_semi_final_matches(&mut t).unwrap();
+    let semi = t.matches[0].id;
+    assert_eq!(
+        record_drawn_final(&mut t, semi),
+        Err(TournamentError::InvalidState)
+    );
+
+    let mut t = final_selection_with(8, TournamentMode::TwoVTwo, FinalsFormat::Standard);
+    t.set_legs_per_final_match(3).unwrap();
+    t.set_scoring_mode(RoundType::SemiFinals, ScoringMode::Winner)
+        .unwrap();
+    play_semis(&mut t);
+    let finals = t.matches[0].id;
+    assert_eq!(
+        record_drawn_final(&mut t, finals),
+        Err(TournamentError::SeriesMatch(finals))
+    );
+}
+
+#[test]
+fn start_grand_finals_only_right_after_2v2_finals() {
+    let mut t = final_selection_with(8, TournamentMode::TwoVTwo, FinalsFormat::Standard);
+    assert_eq!(
+        start_grand_finals(&mut t),
+        Err(TournamentError::InvalidState)
+    );
+    play_semis(&mut t);
+    assert_eq!(
+        start_grand_finals(&mut t),
+        Err(TournamentError::InvalidState)
+    );
+    let finals_id = t.matches[0].id;
+    set_finals_match_winner(&mut t, finals_id, Team::One).unwrap();
+    process_finals_results(&mut t).unwrap();
+    assert_eq!(t.state, TournamentState::Completed);
+
+    start_grand_finals(&mut t).unwrap();
+    let winners = t.bracket_finals_match.clone().unwrap().team_1;
+    assert_eq!(t.state, TournamentState::GrandFinals);
+    assert!(t.single_champion);
+    assert_eq!(t.completed_at, None);
+    assert_eq!(t.matches[0].round, RoundType::GrandFinals);
+    assert_eq!(t.matches[0].team_1, vec![winners[0]]);
+    assert_eq!(t.matches[0].team_2, vec![winners[1]]);
+
+    let grand_id = t.matches[0].id;
+    set_finals_match_winner(&mut t, grand_id, Team::One).unwrap();
+    process_grand_finals_results(&mut t).unwrap();
+    assert_eq!(t.final_placements()[0].players, vec![winners[0]]);
+    assert_eq!(
+        start_grand_finals(&mut t),
+        Err(TournamentError::InvalidState),
+        "only one grand final"
+    );
+
+    let mut one_v_one = final_selection_with(4, TournamentMode::OneVOne, FinalsFormat::Standard);
+    play_semis(&mut one_v_one);
+    let finals_id = one_v_one.matches[0].id;
+    set_finals_match_winner(&mut one_v_one, finals_id, Team::One).unwrap();
+    process_finals_results(&mut one_v_one).unwrap();
+    assert_eq!(
+        start_grand_finals(&mut one_v_one),
+        Err(TournamentError::InvalidState)
+    );
+}
+
+#[test]
+fn results_html_lists_everyone_with_the_champion_and_escapes_names() {
+    let mut t = final_selection_with(4, TournamentMode::OneVOne, FinalsFormat::Standard);
+    t.players[0].name = "Tom & <Jerry>".to_string();
+    play_semis(&mut t);
+    let finals_id = t.matches[0].id;
+    t.final_match_results.insert(finals_id, Team::One);
+    process_finals_results(&mut t).unwrap();
+
+    let html = results_html(&t);
+    assert!(html.starts_with(r#"<section class="results">"#));
+    assert!(html.trim_end().ends_with("</section>"));
+    assert!(html.contains("<h2>Results</h2>"));
+    assert!(html.contains("Champion: "));
+    assert!(html.contains("Tom &amp; &lt;Jerry&gt;"));
+    assert!(!html.contains("<Jerry>"));
+    for name in ["P1", "P2", "P3"] {
+        assert!(html.contains(&format!("<td>{name}</td>")));
+    }
+    assert!(html.contains("Semi-final: "));
+    assert!(html.contains("Final: "));
+}
+
+#[test]
+fn results_html_shows_current_standings_before_completion() {
+    let t = final_selection_with(4, TournamentMode::OneVOne, FinalsFormat::Standard);
+    let html = results_html(&t);
+    assert!(html.contains("Standings (in progress)"));
+    assert!(!html.contains("Champion"));
+    assert!(!html.contains("<h3>Bracket</h3>"));
+    assert_eq!(html.matches("<tr><td>").count(), 4);
+}
@@ -1,15 +1,19 @@
 //! Integration tests for group play: match generation and result processing.
 
+mod common;
+
+use common::{submit_team_one_wins, teams, tournament_in};
 use dart_tournament_web::{
-    generate_group_play_matches, process_group_play_results, Player, RoundType, Team, Tournament,
-    TournamentError, TournamentMode, TournamentState,
+    generate_group_play_matches, generate_group_play_matches_with_rng, generate_practice_round,
+    process_group_play_results, reproduce_round, GameMatch, MatchOutcome, Player, PlayerId,
+    RoundType, Team, Tournament, TournamentError, TournamentFormat, TournamentMode,
+    TournamentState,
 };
+use rand::rngs::StdRng;
+use rand::SeedableRng;
 
 fn tournament_with_players(n: usize) -> Tournament {
-    let players: Vec<Player> = (0..n).map(|i| Player::new(format!("P{i}"))).collect();
-    let mut t = Tournament::with_players(players, 2, TournamentMode::TwoVTwo);
-    t.state = TournamentState::GroupPlay;
-    t
+    tournament_in(n, 2, TournamentMode::TwoVTwo, TournamentState::GroupPlay)
 }
 
 #[test]
@@ -41,7 +45,7 @@ fn process_results_updates_wins_losses_and_eliminates() {
     let m = &t.matches[0];
     let team1_ids = m.team_1.clone();
     let team2_ids = m.team_2.clone();
-    t.match_results.insert(m.id, Team::One);
+    t.match_results.insert(m.id, Team::One.into());
 
     process_group_play_results(&mut t).unwrap();
 
@@ -59,3 +63,1029 @@ fn process_results_updates_wins_losses_and_eliminates() {
     // 4 players <= 8, so state moves to FinalSelection
     assert_eq!(t.state, TournamentState::FinalSelection);
 }
+
+/// Sum over matches of |team 1 wins - team 2 wins|.
+fn team_win_imbalance(t: &Tournament) -> u32 {
+    let wins = |ids: &[PlayerId]| -> u32 {
+        ids.iter()
+            .map(|id| t.players.iter().find(|p| p.id == *id).unwrap().wins)
+            .sum()
+    };
+    t.matches
+        .iter()
+        .map(|m| wins(&m.team_1).abs_diff(wins(&m.team_2)))
+        .sum()
+}
+
+#[test]
+fn balance_teams_pairs_strongest_with_weakest() {
+    let build = |balance_teams: bool| {
+        let mut t = tournament_with_players(8);
+        for (i, p) in t.players.iter_mut().enumerate() {
+            p.wins = (i as u32) * 3;
+        }
+        t.balance_teams = balance_teams;
+        generate_group_play_matches_with_rng(&mut t, &mut StdRng::seed_from_u64(7)).unwrap();
+        t
+    };
+    let random = build(false);
+    let balanced = build(true);
+
+    assert!(team_win_imbalance(&balanced) <= team_win_imbalance(&random));
+    for m in &balanced.matches {
+        let wins_of = |id: &PlayerId| balanced.players.iter().find(|p| p.id == *id).unwrap().wins;
+        let mut all: Vec<u32> = m.team_1.iter().chain(&m.team_2).map(wins_of).collect();
+        all.sort();
+        let team_1: Vec<u32> = m.team_1.iter().map(wins_of).collect();
+        assert!(team_1.contains(&all[0]) && team_1.contains(&all[3]));
+    }
+}
+
+#[test]
+fn matches_played_counts_matches_but_not_byes() {
+    let mut t = tournament_with_players(6); // 6 % 4 = 2 sit out, 4 play
+    generate_group_play_matches(&mut t).unwrap();
+    let sat_out: Vec<PlayerId> = t.unused_players.iter().map(|p| p.id).collect();
+    let m = &t.matches[0];
+    t.match_results.insert(m.id, Team::Two.into());
+
+    process_group_play_results(&mut t).unwrap();
+
+    for p in &t.players {
+        if sat_out.contains(&p.id) {
+            assert_eq!(p.stats(t.max_losses).matches_played, 0);
+            assert_eq!(p.times_sat_out, 1);
+        } else {
+            assert_eq!(p.stats(t.max_losses).matches_played, 1);
+        }
+    }
+}
+
+#[test]
+fn void_match_result_clears_unsubmitted_result() {
+    let mut t = tournament_with_players(8);
+    generate_group_play_matches(&mut t).unwrap();
+    let id = t.matches[0].id;
+    assert_eq!(
+        t.void_match_result(id),
+        Err(TournamentError::NoResultRecorded(id))
+    );
+
+    t.match_results.insert(id, Team::One.into());
+    t.void_match_result(id).unwrap();
+    assert!(!t.match_results.contains_key(&id));
+    assert!(t.matches[0].winner.is_none());
+    assert_eq!(
+        process_group_play_results(&mut t),
+        Err(TournamentError::IncompleteResults)
+    );
+}
+
+#[test]
+fn process_rejects_malformed_2v2_match() {
+    let mut t = tournament_with_players(8);
+    generate_group_play_matches(&mut t).unwrap();
+    let removed = t.matches[1].team_2.pop().unwrap();
+    let id = t.matches[1].id;
+    for m in &t.matches {
+        t.match_results.insert(m.id, Team::One.into());
+    }
+
+    assert_eq!(
+        process_group_play_results(&mut t),
+        Err(TournamentError::InvalidMatchComposition(id))
+    );
+    // Nothing applied: the dropped player and everyone else still have no results.
+    assert!(t.players.iter().all(|p| p.wins == 0 && p.losses == 0));
+    assert!(t.players.iter().any(|p| p.id == removed));
+}
+
+#[test]
+fn history_records_rounds_and_paginates() {
+    let mut t = tournament_with_players(16);
+    t.max_losses = 5;
+    for _ in 0..3 {
+        generate_group_play_matches(&mut t).unwrap();
+        for m in &t.matches {
+            t.match_results.insert(m.id, Team::Two.into());
+        }
+        process_group_play_results(&mut t).unwrap();
+    }
+    assert_eq!(t.match_history.len(), 12);
+    assert!(t.match_history.iter().all(|m| m.winner == Some(Team::Two)));
+
+    let (page, total) = t.history_page(10, 5);
+    assert_eq!(total, 12);
+    assert_eq!(page.len(), 2);
+    assert_eq!(page[0].id, t.match_history[10].id);
+    let (page, _) = t.history_page(40, 5);
+    assert!(page.is_empty());
+}
+
+#[test]
+fn practice_round_does_not_affect_stats_or_state() {
+    let mut t = tournament_with_players(10);
+    t.max_losses = 1;
+    generate_practice_round(&mut t).unwrap();
+    assert!(t.matches.iter().all(|m| m.practice));
+    for m in &t.matches {
+        t.match_results.insert(m.id, Team::One.into());
+    }
+
+    process_group_play_results(&mut t).unwrap();
+
+    assert_eq!(t.state, TournamentState::GroupPlay);
+    assert_eq!(t.players.len(), 10);
+    assert!(t.eliminated_players.is_empty());
+    for p in &t.players {
+        assert_eq!(
+            (p.wins, p.losses, p.matches_played, p.times_sat_out),
+            (0, 0, 0, 0)
+        );
+    }
+    assert_eq!(t.match_history.len(), 2);
+    assert!(t.match_history.iter().all(|m| m.practice));
+    assert!(t.matches.is_empty());
+}
+
+#[test]
+fn public_view_order_is_stable_across_rounds() {
+    let mut t = Tournament::new(3, TournamentMode::TwoVTwo);
+    for name in [
+        "Zoe", "Adam", "Mia", "Bo", "Eve", "Li", "Kai", "Ida", "Ola", "Pia",
+    ] {
+        t.add_player(name).unwrap();
+    }
+    t.state = TournamentState::GroupPlay;
+    let names = |t: &Tournament| -> Vec<String> {
+        t.public_view()
+            .players
+            .iter()
+            .map(|p| p.name.clone())
+            .collect()
+    };
+    let before = names(&t);
+
+    generate_group_play_matches(&mut t).unwrap();
+    assert_eq!(names(&t), before);
+    for m in &t.matches {
+        t.match_results.insert(m.id, Team::One.into());
+    }
+    process_group_play_results(&mut t).unwrap();
+
+    assert_eq!(names(&t), before);
+    assert_eq!(before[0], "Zoe");
+
+    // Eliminate in reverse join order: the view still lists them by join order.
+    let mut ids: Vec<_> = t.players.iter().map(|p| (p.join_order, p.id)).collect();
+    ids.sort();
+    for &(_, id) in ids.iter().rev().take(3) {
+        t.eliminate_player(id).unwrap();
+    }
+    let view = t.public_view();
+    assert_eq!(view.eliminated_players.len(), 3);
+    assert!(view
+        .eliminated_players
+        .windows(2)
+        .all(|w| w[0].join_order < w[1].join_order));
+}
+
+#[test]
+fn shuffle_match_order_keeps_the_same_matches() {
+    let build = |shuffle: bool, seed: u64| {
+        let mut t = tournament_with_players(16);
+        t.shuffle_match_order = shuffle;
+        generate_group_play_matches_with_rng(&mut t, &mut StdRng::seed_from_u64(seed)).unwrap();
+        let names = |ids: &[PlayerId]| -> Vec<String> {
+            let mut n: Vec<String> = ids
+                .iter()
+                .map(|id| t.players.iter().find(|p| p.id == *id).unwrap().name.clone())
+                .collect();
+            n.sort();
+            n
+        };
+        t.matches
+            .iter()
+            .map(|m| (names(&m.team_1), names(&m.team_2)))
+            .collect::<Vec<_>>()
+    };
+
+    let mut any_reordered = false;
+    for seed in 0..8 {
+        let fixed = build(false, seed);
+        let shuffled = build(true, seed);
+        let mut fixed_sorted = fixed.clone();
+        fixed_sorted.sort();
+        let mut shuffled_sorted = shuffled.clone();
+        shuffled_sorted.sort();
+        assert_eq!(fixed_sorted, shuffled_sorted);
+        any_reordered |= fixed != shuffled;
+    }
+    assert!(any_reordered);
+}
+
+#[test]
+fn draw_rejected_unless_allowed() {
+    let mut t = tournament_with_players(8);
+    generate_group_play_matches(&mut t).unwrap();
+    let id = t.matches[0].id;
+    assert_eq!(
+        t.set_match_outcome(id, MatchOutcome::Draw),
+        Err(TournamentError::DrawsNotAllowed)
+    );
+    t.set_match_outcome(id, Team::Two.into()).unwrap();
+}
+
+#[test]
+fn draw_gives_no_losses_and_is_recorded() {
+    let mut t = tournament_with_players(8);
+    t.draws_allowed = true;
+    generate_group_play_matches(&mut t).unwrap();
+    let drawn = t.matches[0].clone();
+    t.set_match_outcome(drawn.id, MatchOutcome::Draw).unwrap();
+    t.set_match_outcome(t.matches[1].id, Team::One.into())
+        .unwrap();
+
+    process_group_play_results(&mut t).unwrap();
+
+    for id in drawn.team_1.iter().chain(&drawn.team_2) {
+        let p = t.players.iter().find(|p| p.id == *id).unwrap();
+        assert_eq!((p.wins, p.losses, p.draws, p.matches_played), (0, 0, 1, 1));
+    }
+    let recorded = t.match_history.iter().find(|m| m.id == drawn.id).unwrap();
+    assert!(recorded.draw);
+    assert_eq!(recorded.winner, None);
+}
+
+#[test]
+fn match_outcome_serializes_like_team() {
+    assert_eq!(
+        serde_json::to_string(&MatchOutcome::Win(Team::One)).unwrap(),
+        "\"one\""
+    );
+    assert_eq!(
+        serde_json::to_string(&MatchOutcome::Draw).unwrap(),
+        "\"draw\""
+    );
+    let parsed: MatchOutcome = serde_json::from_str("\"two\"").unwrap();
+    assert_eq!(parsed, MatchOutcome::Win(Team::Two));
+}
+
+fn one_v_one(n: usize) -> Tournament {
+    tournament_in(n, 3, TournamentMode::OneVOne, TournamentState::GroupPlay)
+}
+
+#[test]
+fn prefers_resting_players_who_played_last_round() {
+    for seed in 0..16 {
+        let mut t = one_v_one(5);
+        for p in &mut t.players {
+            p.played_last_round = true;
+        }
+        t.players[4].played_last_round = false;
+        generate_group_play_matches_with_rng(&mut t, &mut StdRng::seed_from_u64(seed)).unwrap();
+        assert_ne!(t.unused_players[0].id, t.players[4].id);
+    }
+}
+
+#[test]
+fn rest_preference_never_breaks_sit_out_fairness() {
+    for seed in 0..16 {
+        let mut t = one_v_one(5);
+        // P0 played last round but already sat out more than anyone else: it must play.
+        t.players[0].played_last_round = true;
+        t.players[0].internal_times_sat_out = 1;
+        generate_group_play_matches_with_rng(&mut t, &mut StdRng::seed_from_u64(seed)).unwrap();
+        assert_ne!(t.unused_players[0].id, t.players[0].id);
+    }
+}
+
+#[test]
+fn regenerating_a_round_does_not_count_its_byes_twice() {
+    let mut t = one_v_one(9);
+    for _ in 0..3 {
+        generate_group_play_matches(&mut t).unwrap();
+    }
+    let sat_out: u32 = t.players.iter().map(|p| p.times_sat_out).sum();
+    let internal: i32 = t.players.iter().map(|p| p.internal_times_sat_out).sum();
+    assert_eq!((sat_out, internal), (1, 1));
+    let bye = t.unused_players[0].id;
+    assert_eq!(
+        t.players
+            .iter()
+            .find(|p| p.id == bye)
+            .unwrap()
+            .times_sat_out,
+        1
+    );
+
+    t.undo_last_bye_assignment();
+    assert!(t.unused_players.is_empty());
+    assert!(t.players.iter().all(|p| p.times_sat_out == 0));
+}
+
+#[test]
+fn processing_marks_who_played_last_round() {
+    let mut t = one_v_one(5);
+    generate_group_play_matches(&mut t).unwrap();
+    let rested = t.unused_players[0].id;
+    for m in &t.matches {
+        t.match_results.insert(m.id, Team::One.into());
+    }
+    process_group_play_results(&mut t).unwrap();
+    for p in &t.players {
+        assert_eq!(p.played_last_round, p.id != rested);
+    }
+}
+
+#[test]
+fn match_handicap_is_the_teams_combined_spot() {
+    let mut t = tournament_with_players(8);
+    for (i, p) in t.players.iter_mut().enumerate() {
+        p.handicap_spot = i as i32 * 10;
+    }
+    generate_group_play_matches(&mut t).unwrap();
+    for m in &t.matches {
+        let spot = |ids: &[PlayerId]| -> i32 {
+            ids.iter()
+                .map(|id| {
+                    t.players
+                        .iter()
+                        .find(|p| p.id == *id)
+                        .unwrap()
+                        .handicap_spot
+                })
+                .sum()
+        };
+        assert_eq!(m.team_1_handicap, spot(&m.team_1));
+        assert_eq!(m.team_2_handicap, spot(&m.team_2));
+    }
+    let total: i32 = t
+        .matches
+        .iter()
+        .map(|m| m.team_1_handicap + m.team_2_handicap)
+        .sum();
+    assert_eq!(total, (0..8).map(|i| i * 10).sum::<i32>());
+
+    // Changing a spot mid-round updates the displayed match handicap.
+    let id = t.matches[0].team_1[0];
+    let before = t.matches[0].team_1_handicap;
+    let old = t.players.iter().find(|p| p.id == id).unwrap().handicap_spot;
+    t.set_player_handicap_spot(id, old + 40).unwrap();
+    assert_eq!(t.matches[0].team_1_handicap, before + 40);
+}
+
+#[test]
+fn team_handicap_saturates_and_survives_a_restart() {
+    let mut t = tournament_with_players(8);
+    let ids: Vec<PlayerId> = t.players.iter().map(|p| p.id).collect();
+    t.set_player_handicap_spot(ids[0], i32::MAX).unwrap();
+    t.set_player_handicap_spot(ids[1], 10).unwrap();
+    t.set_player_handicap_spot(ids[2], i32::MIN).unwrap();
+    t.set_player_handicap_spot(ids[3], -10).unwrap();
+    assert_eq!(t.team_handicap(&ids[..2]), i32::MAX);
+    assert_eq!(t.team_handicap(&ids[2..4]), i32::MIN);
+
+    t.restart_tournament().unwrap();
+    let spots: Vec<i32> = t.players.iter().map(|p| p.handicap_spot).collect();
+    assert_eq!(spots[..4], [i32::MAX, 10, i32::MIN, -10]);
+}
+
+#[test]
+fn current_round_view_has_matches_results_and_names() {
+    let mut t = tournament_with_players(10);
+    let view = t.current_round_view();
+    assert!(view.matches.is_empty() && view.results.is_empty() && view.player_names.is_empty());
+
+    generate_group_play_matches(&mut t).unwrap();
+    let id = t.matches[0].id;
+    t.set_match_outcome(id, Team::Two.into()).unwrap();
+    let view = t.current_round_view();
+
+    assert_eq!(view.matches, t.matches);
+    assert_eq!(view.results.len(), 1);
+    assert_eq!(view.results[&id], MatchOutcome::Win(Team::Two));
+    // 8 players in matches; the 2 sitting out are not listed.
+    assert_eq!(view.player_names.len(), 8);
+    for m in &t.matches {
+        for pid in m.team_1.iter().chain(&m.team_2) {
+            let name = &t.players.iter().find(|p| p.id == *pid).unwrap().name;
+            assert_eq!(&view.player_names[pid], name);
+        }
+    }
+}
+
+#[test]
+fn current_round_view_with_names_inlines_every_player() {
+    let mut t = tournament_with_players(10);
+    assert_eq!(t.current_round_view().match_views, None);
+    generate_group_play_matches(&mut t).unwrap();
+    let views = t.current_round_view_with_names().match_views.unwrap();
+
+    assert_eq!(views.len(), t.matches.len());
+    for (view, m) in views.iter().zip(&t.matches) {
+        assert_eq!((view.id, view.match_number), (m.id, m.match_number));
+        for (members, ids) in [(&view.team_1, &m.team_1), (&view.team_2, &m.team_2)] {
+            assert_eq!(members.len(), ids.len());
+            for (member, id) in members.iter().zip(ids) {
+                let name = &t.players.iter().find(|p| p.id == *id).unwrap().name;
+                assert_eq!((&member.id, &member.name), (id, name));
+            }
+        }
+    }
+}
+
+#[test]
+fn matches_spread_evenly_across_boards() {
+    for (players, boards) in [(28, 3), (16, 4), (12, 5), (8, 1)] {
+        let mut t = tournament_with_players(players);
+        t.set_num_boards(Some(boards)).unwrap();
+        generate_group_play_matches(&mut t).unwrap();
+
+        let by_board = t.matches_by_board();
+        let counts: Vec<usize> = by_board.values().map(|m| m.len()).collect();
+        let total: usize = counts.iter().sum();
+        assert_eq!(total, t.matches.len());
+        assert_eq!(by_board.len(), (boards as usize).min(t.matches.len()));
+        assert!(counts.iter().max().unwrap() - counts.iter().min().unwrap() <= 1);
+        assert!(by_board.keys().all(|&b| (1..=boards).contains(&b)));
+    }
+}
+
+#[test]
+fn no_boards_means_no_assignment() {
+    let mut t = tournament_with_players(8);
+    generate_group_play_matches(&mut t).unwrap();
+    assert!(t.matches.iter().all(|m| m.board.is_none()));
+    assert!(t.matches_by_board().is_empty());
+    assert_eq!(
+        t.set_num_boards(Some(0)),
+        Err(TournamentError::InvalidConfig(
+            "num_boards must be at least 1"
+        ))
+    );
+}
+
+#[test]
+fn deterministic_mode_pairs_in_list_order() {
+    let mut t = tournament_with_players(10);
+    t.deterministic = true;
+    let mut again = t.clone();
+    generate_group_play_matches(&mut t).unwrap();
+    generate_group_play_matches(&mut again).unwrap();
+    assert_eq!(teams(&t), teams(&again));
+    let ids: Vec<&PlayerId> = t.unused_players.iter().map(|p| &p.id).collect();
+    let again_ids: Vec<&PlayerId> = again.unused_players.iter().map(|p| &p.id).collect();
+    assert_eq!(ids, again_ids);
+
+    // Nobody has sat out yet, so the first two in list order sit out and the rest pair up in order.
+    let order: Vec<PlayerId> = t.players.iter().map(|p| p.id).collect();
+    assert_eq!(ids, vec![&order[0], &order[1]]);
+    assert_eq!(
+        teams(&t),
+        vec![
+            (vec![order[2], order[3]], vec![order[4], order[5]]),
+            (vec![order[6], order[7]], vec![order[8], order[9]]),
+        ]
+    );
+}
+
+#[test]
+fn swap_players_moves_them_between_matches() {
+    let mut t = tournament_with_players(8);
+    generate_group_play_matches(&mut t).unwrap();
+    let a = t.matches[0].team_1[0];
+    let partner = t.matches[0].team_1[1];
+    let b = t.matches[1].team_2[1];
+
+    t.swap_players_in_matches(a, b).unwrap();
+
+    assert_eq!(t.matches[0].team_1, vec![b, partner]);
+    assert_eq!(t.matches[1].team_2[1], a);
+    assert_eq!(
+        t.swap_players_in_matches(a, a),
+        Err(TournamentError::InvalidState)
+    );
+}
+
+#[test]
+fn swap_players_rejected_after_a_result_or_for_sitting_out_players() {
+    let mut t = tournament_with_players(9);
+    generate_group_play_matches(&mut t).unwrap();
+    let a = t.matches[0].team_1[0];
+    let b = t.matches[1].team_1[0];
+    let sitting_out = t.unused_players[0].id;
+    assert_eq!(
+        t.swap_players_in_matches(a, sitting_out),
+        Err(TournamentError::PlayerNotFound(sitting_out))
+    );
+
+    let match_id = t.matches[0].id;
+    t.set_match_outcome(match_id, Team::One.into()).unwrap();
+    let before = t.matches.clone();
+    assert_eq!(
+        t.swap_players_in_matches(a, b),
+        Err(TournamentError::InvalidState)
+    );
+    assert_eq!(t.matches, before);
+}
+
+#[test]
+fn max_concurrent_matches_caps_round_and_rotates_sitters_fairly() {
+    let players: Vec<Player> = (0..10).map(|i| Player::new(format!("P{i}"))).collect();
+    let mut t = Tournament::with_players(players, 100, TournamentMode::OneVOne);
+    t.state = TournamentState::GroupPlay;
+    assert_eq!(
+        t.set_max_concurrent_matches(Some(0)),
+        Err(TournamentError::InvalidConfig(
+            "max_concurrent_matches must be at least 1"
+        ))
+    );
+    t.set_max_concurrent_matches(Some(2)).unwrap();
+
+    for _ in 0..5 {
+        generate_group_play_matches(&mut t).unwrap();
+        assert_eq!(t.matches.len(), 2);
+        assert_eq!(t.unused_players.len(), 6);
+        let ids: Vec<_> = t.matches.iter().map(|m| m.id).collect();
+        for id in ids {
+            t.set_match_outcome(id, Team::One.into()).unwrap();
+        }
+        process_group_play_results(&mut t).unwrap();
+
+        let sat_out: Vec<u32> = t.players.iter().map(|p| p.times_sat_out).collect();
+        let (min, max) = (sat_out.iter().min(), sat_out.iter().max());
+        assert!(max.unwrap() - min.unwrap() <= 1, "unbalanced: {sat_out:?}");
+    }
+    // 5 rounds x 6 sitters spread over 10 players.
+    assert!(t.players.iter().all(|p| p.times_sat_out == 3));
+}
+
+#[test]
+fn projected_finalists_are_the_best_records_up_to_semi_size() {
+    let mut t = tournament_with_players(10);
+    for (i, p) in t.players.iter_mut().enumerate() {
+        p.wins = i as u32 % 5;
+        p.losses = 1;
+        p.join_order = i as u32;
+    }
+    let projected: Vec<String> = t
+        .projected_finalists()
+        .into_iter()
+        .map(|p| p.name)
+        .collect();
+    assert_eq!(projected, ["P4", "P9", "P3", "P8", "P2", "P7", "P1", "P6"]);
+
+    t.players.truncate(5);
+    assert_eq!(t.projected_finalists().len(), 5);
+    t.state = TournamentState::FinalSelection;
+    assert!(t.projected_finalists().is_empty());
+}
+
+#[test]
+fn group_only_plays_down_to_one_and_ranks_everyone() {
+    let mut t = one_v_one(6);
+    t.format = TournamentFormat::GroupOnly;
+    let mut rng = StdRng::seed_from_u64(7);
+    while t.state == TournamentState::GroupPlay {
+        generate_group_play_matches_with_rng(&mut t, &mut rng).unwrap();
+        for m in &t.matches {
+            t.match_results.insert(m.id, Team::One.into());
+        }
+        process_group_play_results(&mut t).unwrap();
+    }
+    assert_eq!(t.state, TournamentState::Completed);
+    assert_eq!(t.players.len(), 1);
+
+    let placements = t.final_placements();
+    assert_eq!(placements[0].place, 1);
+    assert_eq!(placements[0].players, vec![t.players[0].id]);
+    let ranked: usize = placements.iter().map(|p| p.players.len()).sum();
+    assert_eq!(ranked, 6);
+    for pair in placements.windows(2) {
+        assert_eq!(pair[1].place, pair[0].place + 1);
+    }
+}
+
+#[test]
+fn recorded_seed_reproduces_the_round() {
+    let mut t = tournament_with_players(11);
+    let before = t.clone();
+    generate_group_play_matches(&mut t).unwrap();
+    let seed = t.last_round_seed.expect("seed recorded");
+    assert_eq!(t.current_round_view().seed, Some(seed));
+
+    let mut replay = before.clone();
+    replay.matches = reproduce_round(&before, seed).unwrap();
+    assert_eq!(teams(&replay), teams(&t));
+    assert!(
+        before.matches.is_empty(),
+        "reproducing does not modify the tournament"
+    );
+}
+
+#[test]
+fn team_standings_aggregate_each_pairing() {
+    let mut t = tournament_with_players(4);
+    let ids: Vec<PlayerId> = t.players.iter().map(|p| p.id).collect();
+    for winner in [Team::One, Team::One, Team::Two] {
+        let mut m = GameMatch::new(
+            vec![ids[0], ids[1]],
+            vec![ids[3], ids[2]],
+            RoundType::GroupPlay,
+        );
+        m.winner = Some(winner);
+        t.match_history.push(m);
+    }
+    let mut practice = GameMatch::new(
+        vec![ids[0], ids[1]],
+        vec![ids[2], ids[3]],
+        RoundType::GroupPlay,
+    );
+    practice.practice = true;
+    practice.winner = Some(Team::Two);
+    t.match_history.push(practice);
+
+    let standings = t.team_standings();
+    assert_eq!(standings.len(), 2);
+    let mut names = standings[0].names.clone();
+    names.sort();
+    assert_eq!(names, vec!["P0", "P1"]);
+    assert_eq!((standings[0].wins, standings[0].losses), (2, 1));
+    assert_eq!(standings[0].matches_played, 3);
+    let mut second = vec![ids[2], ids[3]];
+    second.sort();
+    assert_eq!(standings[1].players, second);
+    assert_eq!((standings[1].wins, standings[1].losses), (1, 2));
+
+    assert!(one_v_one(4).team_standings().is_empty());
+}
+
+fn team_set(t: &Tournament) -> Vec<Vec<PlayerId>> {
+    let mut teams: Vec<Vec<PlayerId>> = t
+        .matches
+        .iter()
+        .flat_map(|m| [m.team_1.clone(), m.team_2.clone()])
+        .map(|mut team| {
+            team.sort();
+            team
+        })
+        .collect();
+    teams.sort();
+    teams
+}
+
+#[test]
+fn reshuffle_every_keeps_teams_for_that_many_rounds() {
+    let players: Vec<Player> = (0..12).map(|i| Player::new(format!("P{i}"))).collect();
+    let mut t = Tournament::with_players(players, 10, TournamentMode::TwoVTwo);
+    t.state = TournamentState::GroupPlay;
+    t.set_reshuffle_every(3).unwrap();
+    let mut rng = StdRng::seed_from_u64(3);
+
+    let mut rounds = Vec::new();
+    for _ in 0..4 {
+        generate_group_play_matches_with_rng(&mut t, &mut rng).unwrap();
+        rounds.push(team_set(&t));
+        for m in &t.matches {
+            t.match_results.insert(m.id, Team::One.into());
+        }
+        process_group_play_results(&mut t).unwrap();
+    }
+    assert_eq!(rounds[0], rounds[1]);
+    assert_eq!(rounds[1], rounds[2]);
+    assert_ne!(rounds[2], rounds[3], "round 4 draws new teams");
+    assert_eq!(
+        t.set_reshuffle_every(0),
+        Err(TournamentError::InvalidConfig(
+            "reshuffle_every must be at least 1"
+        ))
+    );
+}
+
+#[test]
+fn sit_out_volunteer_takes_the_bye() {
+    for seed in 0..8 {
+        let mut t = one_v_one(5);
+        let volunteer = t.players[3].id;
+        t.request_sit_out(volunteer).unwrap();
+        let mut rng = StdRng::seed_from_u64(seed);
+        generate_group_play_matches_with_rng(&mut t, &mut rng).unwrap();
+        assert_eq!(t.unused_players.len(), 1);
+        assert_eq!(t.unused_players[0].id, volunteer);
+        assert!(t.sit_out_requests.is_empty(), "the request is used up");
+    }
+    let mut t = one_v_one(5);
+    assert!(matches!(
+        t.request_sit_out(uuid::Uuid::new_v4()),
+        Err(TournamentError::PlayerNotFound(_))
+    ));
+}
+
+#[test]
+fn sit_out_request_holds_when_the_round_is_regenerated() {
+    let mut t = one_v_one(5);
+    let volunteer = t.players[3].id;
+    t.request_sit_out(volunteer).unwrap();
+    let mut rng = StdRng::seed_from_u64(1);
+    generate_group_play_matches_with_rng(&mut t, &mut rng).unwrap();
+    for seed in 2..6 {
+        let mut rng = StdRng::seed_from_u64(seed);
+        generate_group_play_matches_with_rng(&mut t, &mut rng).unwrap();
+        assert_eq!(t.unused_players.len(), 1);
+        assert_eq!(t.unused_players[0].id, volunteer);
+    }
+    let sat_out = t.players.iter().find(|p| p.id == volunteer).unwrap();
+    assert_eq!(sat_out.times_sat_out, 1);
+
+    let ids: Vec<_> = t.matches.iter().map(|m| m.id).collect();
+    for id in ids {
+        t.match_results.insert(id, Team::One.into());
+    }
+    process_group_play_results(&mut t).unwrap();
+    assert!(t.sit_out_requests.is_empty());
+    assert!(t.round_sit_out_requests.is_empty());
+}
+
+#[test]
+fn mid_round_tournament_survives_a_json_round_trip() {
+    let mut t = tournament_with_players(10);
+    t.set_reshuffle_every(2).unwrap();
+    generate_group_play_matches(&mut t).unwrap();
+    let first = t.matches[0].id;
+    t.match_results.insert(first, Team::Two.into());
+
+    let json = serde_json::to_value(&t).unwrap();
+    let mut restored: Tournament = serde_json::from_value(json.clone()).unwrap();
+    assert_eq!(serde_json::to_value(&restored).unwrap(), json);
+    assert_eq!(restored.match_results, t.match_results);
+    assert_eq!(restored.unused_players.len(), 2);
+
+    assert!(matches!(
+        process_group_play_results(&mut restored),
+        Err(TournamentError::IncompleteResults)
+    ));
+    let second = restored.matches[1].id;
+    restored.match_results.insert(second, Team::One.into());
+    process_group_play_results(&mut restored).unwrap();
+    assert_eq!(restored.rounds_completed, 1);
+}
+
+#[test]
+fn auto_generate_next_starts_the_next_round_on_submit() {
+    let mut t = one_v_one(8);
+    t.set_auto_generate_next(true).unwrap();
+    generate_group_play_matches(&mut t).unwrap();
+    let first_round: Vec<_> = t.matches.iter().map(|m| m.id).collect();
+    submit_team_one_wins(&mut t);
+    assert_eq!(t.state, TournamentState::GroupPlay);
+    assert_eq!(t.matches.len(), 4);
+    assert!(t.matches.iter().all(|m| !first_round.contains(&m.id)));
+    assert!(t.match_results.is_empty());
+
+    let mut manual = one_v_one(8);
+    generate_group_play_matches(&mut manual).unwrap();
+    submit_team_one_wins(&mut manual);
+    assert!(manual.matches.is_empty(), "off by default");
+}
+
+#[test]
+fn auto_generate_next_skips_generation_after_leaving_group_play() {
+    let players: Vec<Player> = (0..5).map(|i| Player::new(format!("P{i}"))).collect();
+    let mut t = Tournament::with_players(players, 1, TournamentMode::OneVOne);
+    t.state = TournamentState::GroupPlay;
+    t.auto_generate_next = true;
+    generate_group_play_matches(&mut t).unwrap();
+    submit_team_one_wins(&mut t);
+    assert_eq!(t.state, TournamentState::FinalSelection);
+    assert!(t.matches.is_empty());
+}
+
+#[test]
+fn counts_match_the_player_lists() {
+    let check = |t: &Tournament| {
+        let c = t.counts();
+        assert_eq!(c.active, t.players.len());
+        assert_eq!(c.unused, t.unused_players.len());
+        assert_eq!(c.eliminated, t.eliminated_players.len());
+        assert_eq!(c.last_eliminated, t.last_eliminated_players.len());
+        assert_eq!(c.max_losses, t.max_losses);
+        assert_eq!(c.state, t.state);
+    };
+    let players: Vec<Player> = (0..7).map(|i| Player::new(format!("P{i}"))).collect();
+    let mut t = Tournament::with_players(players, 1, TournamentMode::OneVOne);
+    check(&t);
+    t.state = TournamentState::GroupPlay;
+    generate_group_play_matches(&mut t).unwrap();
+    check(&t);
+    assert_eq!(t.counts().unused, 1);
+    submit_team_one_wins(&mut t);
+    check(&t);
+    assert_eq!(t.counts().state, TournamentState::FinalSelection);
+    assert_eq!(t.counts().eliminated, 3);
+}
+
+#[test]
+fn lives_remaining_counts_down_to_zero() {
+    let mut p = Player::new("Dave".to_string());
+    p.losses = 1;
+    assert_eq!(p.stats(3).lives_remaining, 2);
+    p.losses = 5;
+    assert_eq!(p.stats(3).lives_remaining, 0, "saturates");
+
+    let mut t = one_v_one(4);
+    let mut out = t.players.remove(0);
+    out.losses = 1;
+    out.eliminated = true;
+    t.eliminated_players.push(out.clone());
+    assert_eq!(t.player_summary(&out).stats.lives_remaining, 0);
+    assert_eq!(t.player_summary(&t.players[0]).stats.lives_remaining, 3);
+}
+
+#[test]
+fn streaks_follow_consecutive_results() {
+    let mut p = Player::new("Erin".to_string());
+    p.add_win();
+    p.add_win();
+    assert_eq!((p.current_streak, p.longest_streak), (2, 2));
+    p.add_loss();
+    assert_eq!((p.current_streak, p.longest_streak), (-1, 2));
+    p.add_loss();
+    assert_eq!(p.current_streak, -2);
+
+    // A sit-out keeps the streak; a draw ends it.
+    p.add_win();
+    p.record_sat_out();
+    assert_eq!(p.stats(3).current_streak, 1);
+    p.add_draw();
+    assert_eq!(p.stats(3).current_streak, 0);
+    assert_eq!(p.stats(3).longest_streak, 2);
+}
+
+#[test]
+fn simulate_winner_previews_without_changing_anything() {
+    let players: Vec<Player> = (0..5).map(|i| Player::new(format!("P{i}"))).collect();
+    let mut t = Tournament::with_players(players, 1, TournamentMode::OneVOne);
+    t.state = TournamentState::GroupPlay;
+    generate_group_play_matches(&mut t).unwrap();
+    let (first, second) = (t.matches[0].clone(), t.matches[1].clone());
+    let before = serde_json::to_value(&t).unwrap();
+
+    let sim = t.simulate_winner(first.id, Team::One.into()).unwrap();
+    assert_eq!(serde_json::to_value(&t).unwrap(), before);
+    assert!(!sim.completes_round);
+    assert_eq!(sim.state, TournamentState::GroupPlay);
+    let out: Vec<PlayerId> = sim.eliminated.iter().map(|p| p.id).collect();
+    assert_eq!(out, first.team_2);
+    assert_eq!(sim.eliminated[0].stats.lives_remaining, 0);
+
+    t.set_match_outcome(first.id, Team::One.into()).unwrap();
+    let before = serde_json::to_value(&t).unwrap();
+    let sim = t.simulate_winner(second.id, Team::Two.into()).unwrap();
+    assert_eq!(serde_json::to_value(&t).unwrap(), before);
+    assert!(sim.completes_round);
+    assert_eq!(sim.state, TournamentState::FinalSelection);
+    let out: Vec<PlayerId> = sim.eliminated.iter().map(|p| p.id).collect();
+    assert_eq!(out, second.team_1, "only this match's eliminations");
+
+    assert!(matches!(
+        t.simulate_winner(uuid::Uuid::new_v4(), Team::One.into()),
+        Err(TournamentError::MatchNotFound(_))
+    ));
+}
+
+#[test]
+fn strict_bye_rotation_never_repeats_a_bye_early() {
+    for seed in 0..8 {
+        let mut t = one_v_one(9);
+        t.max_losses = 100;
+        t.strict_bye_rotation = true;
+        t.max_concurrent_matches = Some(3);
+        let mut rng = StdRng::seed_from_u64(seed);
+        for round in 0..15 {
+            if round % 4 == 1 {
+                // A volunteer must not override the rotation.
+                let most_rested = t.players.iter().max_by_key(|p| p.times_sat_out).unwrap().id;
+                t.request_sit_out(most_rested).unwrap();
+            }
+            generate_group_play_matches_with_rng(&mut t, &mut rng).unwrap();
+            let counts: Vec<u32> = t.players.iter().map(|p| p.times_sat_out).collect();
+            let (min, max) = (counts.iter().min().unwrap(), counts.iter().max().unwrap());
+            assert!(max - min <= 1, "seed {seed}, round {round}: {counts:?}");
+            submit_team_one_wins(&mut t);
+        }
+    }
+}
+
+/// 2v2 with seven players and `allow_ghost`: one round, the ghost's id and their two matches.
+fn ghost_round() -> (Tournament, PlayerId) {
+    let mut t = tournament_with_players(7);
+    t.set_allow_ghost(true).unwrap();
+    generate_group_play_matches(&mut t).unwrap();
+    let ghost = t.ghost_player.expect("a ghost fills the round");
+    (t, ghost)
+}
+
+#[test]
+fn ghost_plays_twice_instead_of_three_sitting_out() {
+    let mut plain = tournament_with_players(7);
+    generate_group_play_matches(&mut plain).unwrap();
+    assert_eq!(plain.matches.len(), 1);
+    assert_eq!(plain.unused_players.len(), 3);
+    assert_eq!(plain.ghost_player, None);
+
+    let (t, ghost) = ghost_round();
+    assert_eq!(t.matches.len(), 2);
+    assert!(t.unused_players.is_empty());
+    for p in &t.players {
+        let appearances = t
+            .matches
+            .iter()
+            .filter(|m| m.team_1.contains(&p.id) || m.team_2.contains(&p.id))
+            .count();
+        assert_eq!(appearances, if p.id == ghost { 2 } else { 1 });
+    }
+    for m in &t.matches {
+        assert_eq!(m.team_1.len() + m.team_2.len(), 4);
+    }
+}
+
+#[test]
+fn ghost_takes_the_result_of_each_match() {
+    let (mut t, ghost) = ghost_round();
+    // The ghost loses both matches, which with two lives puts them out once.
+    for m in &t.matches {
+        let winner = if m.team_1.contains(&ghost) {
+            Team::Two
+        } else {
+            Team::One
+        };
+        t.match_results.insert(m.id, winner.into());
+    }
+    process_group_play_results(&mut t).unwrap();
+
+    assert_eq!(t.ghost_player, None);
+    let out: Vec<&Player> = t
+        .eliminated_players
+        .iter()
+        .filter(|p| p.id == ghost)
+        .collect();
+    assert_eq!(out.len(), 1);
+    assert_eq!((out[0].losses, out[0].matches_played), (2, 2));
+    assert_eq!(t.eliminated_players.len(), 1);
+    for p in &t.players {
+        assert_eq!(p.matches_played, 1);
+        assert_eq!(p.wins + p.losses, 1);
+    }
+    assert!(t.check_invariants().is_empty());
+}
+
+#[test]
+fn match_numbers_count_up_across_rounds() {
+    let mut t = tournament_with_players(12);
+    t.max_losses = 3;
+    generate_group_play_matches(&mut t).unwrap();
+    let first: Vec<u32> = t.matches.iter().map(|m| m.match_number).collect();
+    let mut sorted = first.clone();
+    sorted.sort();
+    assert_eq!(sorted, vec![1, 2, 3]);
+
+    let second_id = t.match_by_number(2).unwrap().id;
+    assert!(t.matches.iter().any(|m| m.id == second_id));
+    for m in &t.matches {
+        t.match_results.insert(m.id, Team::One.into());
+    }
+    process_group_play_results(&mut t).unwrap();
+
+    generate_group_play_matches(&mut t).unwrap();
+    let mut next: Vec<u32> = t.matches.iter().map(|m| m.match_number).collect();
+    next.sort();
+    assert_eq!(next, vec![4, 5, 6]);
+    // Submitted matches keep their number and can still be looked up.
+    assert_eq!(t.match_by_number(2).unwrap().id, second_id);
+    assert!(t.match_by_number(7).is_none());
+    assert!(t.match_by_number(0).is_none());
+}
+
+#[test]
+fn generating_a_round_drops_results_of_old_matches() {
+    let mut t = tournament_with_players(12);
+    t.max_losses = 3;
+    generate_group_play_matches(&mut t).unwrap();
+    let old = t.matches[0].id;
+    t.match_results.insert(old, Team::One.into());
+    // The round is thrown away (e.g. rebuilt by hand) with its result still recorded.
+    t.matches.clear();
+    assert!(!t.check_invariants().is_empty());
+
+    generate_group_play_matches(&mut t).unwrap();
+    assert!(t.match_results.is_empty());
+    assert!(t.check_invariants().is_empty());
+
+    let current = t.matches[0].id;
+    t.match_results.insert(current, Team::Two.into());
+    t.final_match_results.insert(old, Team::One);
+    assert_eq!(t.prune_stale_results(), vec![old]);
+    assert!(t.final_match_results.is_empty());
+    assert_eq!(t.match_results.len(), 1);
+}
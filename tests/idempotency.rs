@@ -0,0 +1,64 @@
+//! Integration tests for the `Idempotency-Key` response cache.
+
+use dart_tournament_web::idempotency::{
+    IdempotencyCache, IdempotencyKey, IDEMPOTENCY_CACHE_CAPACITY,
+};
+
+fn key(method: &str, path: &str, key: &str) -> IdempotencyKey {
+    IdempotencyKey::new(method, path, key).unwrap()
+}
+
+#[test]
+fn blank_keys_are_ignored() {
+    assert!(IdempotencyKey::new("POST", "/api/tournaments", "").is_none());
+    assert!(IdempotencyKey::new("POST", "/api/tournaments", "   ").is_none());
+    assert_eq!(
+        IdempotencyKey::new("POST", "/api/tournaments", " k1 "),
+        Some(key("POST", "/api/tournaments", "k1"))
+    );
+}
+
+#[test]
+fn a_retry_on_the_same_route_is_replayed() {
+    let mut cache = IdempotencyCache::default();
+    let submit = key("POST", "/api/tournaments/t1/matches/submit", "k1");
+    assert_eq!(cache.get(&submit), None);
+    cache.insert(submit.clone(), "round 1 submitted");
+    assert_eq!(cache.get(&submit), Some("round 1 submitted"));
+}
+
+#[test]
+fn the_same_key_on_another_route_or_method_is_not_replayed() {
+    let mut cache = IdempotencyCache::default();
+    cache.insert(key("POST", "/api/tournaments/t1/players", "k1"), "added");
+    assert_eq!(
+        cache.get(&key("POST", "/api/tournaments/t1/matches/submit", "k1")),
+        None
+    );
+    assert_eq!(
+        cache.get(&key("DELETE", "/api/tournaments/t1/players", "k1")),
+        None
+    );
+
+    cache.insert(
+        key("POST", "/api/tournaments/t1/matches/submit", "k1"),
+        "submitted",
+    );
+    assert_eq!(
+        cache.get(&key("POST", "/api/tournaments/t1/players", "k1")),
+        Some("added")
+    );
+}
+
+#[test]
+fn the_least_recently_used_key_is_evicted() {
+    let mut cache = IdempotencyCache::default();
+    for i in 0..IDEMPOTENCY_CACHE_CAPACITY {
+        cache.insert(key("POST", "/p", &format!("k{i}")), i);
+    }
+    // Using k0 keeps it; k1 is now the oldest and goes first.
+    assert_eq!(cache.get(&key("POST", "/p", "k0")), Some(0));
+    cache.insert(key("POST", "/p", "new"), IDEMPOTENCY_CACHE_CAPACITY);
+    assert_eq!(cache.get(&key("POST", "/p", "k1")), None);
+    assert_eq!(cache.get(&key("POST", "/p", "k0")), Some(0));
+}
@@ -0,0 +1,73 @@
+//! Integration tests for the consistency diagnostics (`check_invariants`).
+
+mod common;
+
+use common::tournament_in;
+use dart_tournament_web::{
+    generate_group_play_matches, GameMatch, RoundType, Team, Tournament, TournamentMode,
+    TournamentState,
+};
+use uuid::Uuid;
+
+fn round_in_progress() -> Tournament {
+    let mut t = tournament_in(5, 2, TournamentMode::OneVOne, TournamentState::GroupPlay);
+    generate_group_play_matches(&mut t).unwrap();
+    t
+}
+
+#[test]
+fn healthy_tournament_has_no_violations() {
+    let mut t = round_in_progress();
+    assert!(t.check_invariants().is_empty());
+    let id = t.matches[0].id;
+    t.match_results.insert(id, Team::One.into());
+    assert!(t.check_invariants().is_empty());
+}
+
+#[test]
+fn detects_unknown_player_in_a_match() {
+    let mut t = round_in_progress();
+    t.matches[0].team_1 = vec![Uuid::new_v4()];
+    let problems = t.check_invariants();
+    assert_eq!(problems.len(), 1);
+    assert!(problems[0].contains("unknown player"));
+}
+
+#[test]
+fn detects_duplicate_players() {
+    let mut t = round_in_progress();
+    let p = t.players[0].clone();
+    t.eliminated_players.push(p);
+    let problems = t.check_invariants();
+    assert_eq!(problems.len(), 1);
+    assert!(problems[0].contains("both players and eliminated_players"));
+
+    let mut t = round_in_progress();
+    let p = t.players[0].clone();
+    t.players.push(p);
+    let problems = t.check_invariants();
+    assert_eq!(problems.len(), 1);
+    assert!(problems[0].contains("listed twice"));
+}
+
+#[test]
+fn detects_results_for_matches_not_in_the_round() {
+    let mut t = round_in_progress();
+    let stale = GameMatch::new(vec![], vec![], RoundType::GroupPlay);
+    t.match_results.insert(stale.id, Team::Two.into());
+    t.final_match_results.insert(Uuid::new_v4(), Team::One);
+    let problems = t.check_invariants();
+    assert_eq!(problems.len(), 2);
+    assert!(problems
+        .iter()
+        .all(|p| p.contains("not in the current round")));
+}
+
+#[test]
+fn detects_sitting_out_player_missing_from_players() {
+    let mut t = round_in_progress();
+    let sitter = t.unused_players[0].id;
+    t.players.retain(|p| p.id != sitter);
+    let problems = t.check_invariants();
+    assert!(problems.iter().any(|p| p.contains("Sitting-out player")));
+}
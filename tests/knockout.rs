@@ -0,0 +1,212 @@
+//! Integration tests for the knockout-all format: group play to a round cap, then every survivor
+//! in a single-elimination bracket.
+
+mod common;
+
+use common::{play_round, tournament_in};
+use dart_tournament_web::{
+    advance_tournament, generate_knockout_matches, knockout_round, process_knockout_results,
+    set_finals_match_winner, start_tournament, EliminationReason, Player, PlayerId, RoundType,
+    Team, Tournament, TournamentError, TournamentFormat, TournamentMode, TournamentState,
+};
+use std::collections::HashSet;
+use uuid::Uuid;
+
+/// 1v1 knockout-all tournament in group play, with enough lives that one round eliminates nobody.
+fn knockout_all_with(n: usize) -> Tournament {
+    let mut t = tournament_in(n, 3, TournamentMode::OneVOne, TournamentState::GroupPlay);
+    t.format = TournamentFormat::KnockoutAll;
+    t.knockout_after_rounds = 1;
+    t
+}
+
+/// Players in the current knockout round who have no match.
+fn byes(t: &Tournament) -> HashSet<PlayerId> {
+    let playing: HashSet<PlayerId> = t
+        .matches
+        .iter()
+        .flat_map(|m| m.team_1.iter().chain(&m.team_2))
+        .copied()
+        .collect();
+    t.players
+        .iter()
+        .map(|p| p.id)
+        .filter(|id| !playing.contains(id))
+        .collect()
+}
+
+/// Active players without a group play loss (the round's winners and the sitter).
+fn unbeaten(t: &Tournament) -> HashSet<PlayerId> {
+    t.players
+        .iter()
+        .filter(|p| p.losses == 0)
+        .map(|p| p.id)
+        .collect()
+}
+
+#[test]
+fn zero_rounds_before_the_knockout_is_an_invalid_config() {
+    let mut t = knockout_all_with(8);
+    assert_eq!(
+        t.set_knockout_after_rounds(0),
+        Err(TournamentError::InvalidConfig(
+            "knockout_after_rounds must be at least 1"
+        ))
+    );
+    assert_eq!(t.knockout_after_rounds, 1);
+}
+
+#[test]
+fn knockout_round_pads_to_a_power_of_two_with_byes_for_top_seeds() {
+    let seeds: Vec<PlayerId> = (0..8).map(|_| Uuid::new_v4()).collect();
+
+    let five = knockout_round(&seeds[..5]);
+    assert_eq!(five.byes, seeds[..3]);
+    assert_eq!(five.matches, vec![(seeds[3], seeds[4])]);
+
+    let six = knockout_round(&seeds[..6]);
+    assert_eq!(six.byes, seeds[..2]);
+    assert_eq!(
+        six.matches,
+        vec![(seeds[2], seeds[5]), (seeds[3], seeds[4])]
+    );
+
+    let eight = knockout_round(&seeds);
+    assert!(eight.byes.is_empty());
+    assert_eq!(
+        eight.matches,
+        vec![
+            (seeds[0], seeds[7]),
+            (seeds[1], seeds[6]),
+            (seeds[2], seeds[5]),
+            (seeds[3], seeds[4]),
+        ]
+    );
+}
+
+#[test]
+fn five_survivors_give_the_three_best_records_a_bye() {
+    let mut t = knockout_all_with(5);
+    play_round(&mut t);
+    assert_eq!(t.state, TournamentState::Knockout);
+    assert!(
+        t.last_round_summary
+            .as_ref()
+            .unwrap()
+            .advanced_to_final_selection
+    );
+
+    generate_knockout_matches(&mut t).unwrap();
+    assert_eq!(t.matches.len(), 1);
+    assert_eq!(t.matches[0].round, RoundType::Knockout);
+    // Two winners and the sitter are unbeaten; the two losers play each other.
+    assert_eq!(byes(&t), unbeaten(&t));
+}
+
+#[test]
+fn six_survivors_give_two_byes_to_group_winners() {
+    let mut t = knockout_all_with(6);
+    play_round(&mut t);
+    assert_eq!(t.state, TournamentState::Knockout);
+
+    generate_knockout_matches(&mut t).unwrap();
+    assert_eq!(t.matches.len(), 2);
+    let byes = byes(&t);
+    assert_eq!(byes.len(), 2);
+    assert!(byes.is_subset(&unbeaten(&t)));
+}
+
+#[test]
+fn eight_survivors_play_four_matches_without_byes() {
+    let mut t = knockout_all_with(8);
+    play_round(&mut t);
+    assert_eq!(t.state, TournamentState::Knockout);
+
+    generate_knockout_matches(&mut t).unwrap();
+    assert_eq!(t.matches.len(), 4);
+    assert!(byes(&t).is_empty());
+    // Top half against bottom half: every match has exactly one group play winner.
+    let unbeaten = unbeaten(&t);
+    for m in &t.matches {
+        assert!(unbeaten.contains(&m.team_1[0]));
+        assert!(!unbeaten.contains(&m.team_2[0]));
+    }
+}
+
+#[test]
+fn knockout_runs_to_a_finals_and_ranks_by_knockout_round() {
+    let mut t = knockout_all_with(6);
+    play_round(&mut t);
+
+    // 6 -> 4 (two byes), 4 -> 2, then the finals.
+    generate_knockout_matches(&mut t).unwrap();
+    let ids: Vec<_> = t.matches.iter().map(|m| m.id).collect();
+    for id in ids {
+        set_finals_match_winner(&mut t, id, Team::One).unwrap();
+    }
+    process_knockout_results(&mut t).unwrap();
+    assert_eq!(t.state, TournamentState::Knockout);
+    assert_eq!(t.players.len(), 4);
+    assert!(t
+        .eliminated_players
+        .iter()
+        .all(|p| p.elimination_reason == Some(EliminationReason::Knockout)));
+
+    while t.state == TournamentState::Knockout {
+        if t.matches.is_empty() {
+            advance_tournament(&mut t).unwrap();
+        }
+        let ids: Vec<_> = t.matches.iter().map(|m| m.id).collect();
+        for id in ids {
+            set_finals_match_winner(&mut t, id, Team::One).unwrap();
+        }
+        advance_tournament(&mut t).unwrap();
+    }
+    assert_eq!(t.state, TournamentState::Finals);
+    assert_eq!(t.matches[0].round, RoundType::Finals);
+    let finals = t.matches[0].id;
+    set_finals_match_winner(&mut t, finals, Team::One).unwrap();
+    advance_tournament(&mut t).unwrap();
+
+    assert_eq!(t.state, TournamentState::Completed);
+    let sizes: Vec<usize> = t
+        .final_placements()
+        .iter()
+        .map(|p| p.players.len())
+        .collect();
+    assert_eq!(sizes, vec![1, 1, 2, 2]);
+}
+
+#[test]
+fn reset_keeping_stats_clears_the_knockout_bracket() {
+    let mut t = knockout_all_with(6);
+    play_round(&mut t);
+    generate_knockout_matches(&mut t).unwrap();
+    let ids: Vec<_> = t.matches.iter().map(|m| m.id).collect();
+    for id in ids {
+        set_finals_match_winner(&mut t, id, Team::One).unwrap();
+    }
+    process_knockout_results(&mut t).unwrap();
+    assert!(!t.knockout_seeds.is_empty());
+    assert!(!t.knockout_eliminated.is_empty());
+
+    t.reset_to_group_play_keeping_stats(true).unwrap();
+    assert_eq!(t.state, TournamentState::GroupPlay);
+    assert_eq!(t.players.len(), 6);
+    assert!(t.knockout_seeds.is_empty());
+    assert!(t.knockout_eliminated.is_empty());
+    assert!(t.last_round_teams.is_empty());
+    assert_eq!(t.ghost_player, None);
+    assert_eq!(t.final_placements().len(), 0);
+}
+
+#[test]
+fn knockout_all_needs_one_v_one() {
+    let players: Vec<Player> = (0..8).map(|i| Player::new(format!("P{i}"))).collect();
+    let mut t = Tournament::with_players(players, 3, TournamentMode::TwoVTwo);
+    t.set_format(TournamentFormat::KnockoutAll).unwrap();
+    assert_eq!(
+        start_tournament(&mut t),
+        Err(TournamentError::InvalidConfig("knockout_all needs 1v1"))
+    );
+}
@@ -0,0 +1,71 @@
+//! The OpenAPI document must list exactly the routes the web binary registers.
+
+use dart_tournament_web::openapi::{openapi_document, schemas, ENDPOINTS};
+use std::collections::BTreeSet;
+
+const WEB_SOURCE: &str = include_str!("../src/bin/web.rs");
+
+/// (method, path) for every `#[get("...")]`-style route attribute whose handler is registered
+/// with `.service(...)`.
+fn registered_routes() -> BTreeSet<(String, String)> {
+    let mut routes = BTreeSet::new();
+    let mut lines = WEB_SOURCE.lines();
+    while let Some(line) = lines.next() {
+        let line = line.trim();
+        let Some((method, rest)) = line.strip_prefix("#[").and_then(|l| l.split_once("(\"")) else {
+            continue;
+        };
+        if !["get", "post", "put", "delete"].contains(&method) {
+            continue;
+        }
+        let path = rest.trim_end_matches("\")]").to_string();
+        let handler = lines
+            .by_ref()
+            .find_map(|l| l.trim().strip_prefix("async fn "))
+            .and_then(|l| l.split('(').next())
+            .expect("route attribute is followed by a handler");
+        let registered = format!(".service({handler})");
+        assert!(
+            WEB_SOURCE.contains(&registered),
+            "{handler} is not registered"
+        );
+        routes.insert((method.to_string(), path));
+    }
+    routes
+}
+
+#[test]
+fn documented_routes_match_registered_routes() {
+    let documented: BTreeSet<(String, String)> = ENDPOINTS
+        .iter()
+        .map(|e| (e.method.to_string(), e.path.to_string()))
+        .collect();
+    assert_eq!(documented.len(), ENDPOINTS.len(), "duplicate endpoint");
+    assert_eq!(documented, registered_routes());
+}
+
+#[test]
+fn referenced_schemas_exist() {
+    let schemas = schemas();
+    for e in ENDPOINTS {
+        for name in e.body.into_iter().chain([e.response]) {
+            if !matches!(name, "None" | "Svg" | "Html" | "EventStream") {
+                assert!(schemas.get(name).is_some(), "missing schema {name}");
+            }
+        }
+    }
+}
+
+#[test]
+fn document_has_every_path() {
+    let doc = openapi_document();
+    assert_eq!(doc["openapi"], "3.0.3");
+    let paths = doc["paths"].as_object().unwrap();
+    for e in ENDPOINTS {
+        assert!(paths[e.path].get(e.method).is_some());
+    }
+    let remove = &paths["/api/tournaments/{id}/players/{player_id}"]["delete"];
+    let params = remove["parameters"].as_array().unwrap();
+    assert_eq!(params.len(), 5); // id, player_id, Idempotency-Key, If-Match, expected_version
+    assert!(remove["responses"].get("409").is_some());
+}
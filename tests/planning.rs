@@ -0,0 +1,50 @@
+//! Integration tests for read-only planning helpers.
+
+mod common;
+
+use common::tournament_in;
+use dart_tournament_web::{Tournament, TournamentMode, TournamentState};
+
+fn tournament(n: usize, max_losses: u32, mode: TournamentMode) -> Tournament {
+    tournament_in(n, max_losses, mode, TournamentState::Setup)
+}
+
+#[test]
+fn max_rounds_zero_at_or_below_threshold() {
+    assert_eq!(
+        tournament(0, 3, TournamentMode::TwoVTwo).max_possible_rounds(),
+        0
+    );
+    assert_eq!(
+        tournament(8, 3, TournamentMode::TwoVTwo).max_possible_rounds(),
+        0
+    );
+    assert_eq!(
+        tournament(4, 3, TournamentMode::OneVOne).max_possible_rounds(),
+        0
+    );
+}
+
+#[test]
+fn max_rounds_one_above_threshold() {
+    // 9 players: 8 play, 4 losses per round; 9 players absorb 2 losses each (18) before anyone goes out.
+    assert_eq!(
+        tournament(9, 3, TournamentMode::TwoVTwo).max_possible_rounds(),
+        5
+    );
+    // 5 players 1v1: 4 play, 2 losses per round; with max_losses 1 the first loss eliminates.
+    assert_eq!(
+        tournament(5, 1, TournamentMode::OneVOne).max_possible_rounds(),
+        1
+    );
+}
+
+#[test]
+fn max_rounds_grows_with_field_and_losses() {
+    let small = tournament(16, 3, TournamentMode::TwoVTwo).max_possible_rounds();
+    let large = tournament(1000, 3, TournamentMode::TwoVTwo).max_possible_rounds();
+    let more_losses = tournament(1000, 5, TournamentMode::TwoVTwo).max_possible_rounds();
+    assert!(small > 0);
+    assert!(large > small);
+    assert!(more_losses > large);
+}
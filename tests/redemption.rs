@@ -0,0 +1,103 @@
+//! Integration tests for the opt-in redemption round between group play and the semi-finals.
+
+mod common;
+
+use common::{play_round, tournament_in};
+use dart_tournament_web::{
+    generate_redemption_matches, generate_semi_final_matches, process_redemption_results,
+    set_finals_match_winner, EliminationReason, Player, RoundType, Team, Tournament,
+    TournamentMode, TournamentState,
+};
+
+fn group_play_with(n: usize, mode: TournamentMode, redemption: bool) -> Tournament {
+    let mut t = tournament_in(n, 1, mode, TournamentState::GroupPlay);
+    t.redemption = redemption;
+    t
+}
+
+/// Team 1 wins every current redemption match, then submit.
+fn play_redemption(t: &mut Tournament) {
+    let ids: Vec<_> = t.matches.iter().map(|m| m.id).collect();
+    for id in ids {
+        set_finals_match_winner(t, id, Team::One).unwrap();
+    }
+    process_redemption_results(t).unwrap();
+}
+
+#[test]
+fn without_redemption_group_play_goes_to_final_selection() {
+    let mut t = group_play_with(6, TournamentMode::OneVOne, false);
+    play_round(&mut t);
+    assert_eq!(t.state, TournamentState::FinalSelection);
+}
+
+#[test]
+fn one_v_one_redemption_fills_the_open_slot() {
+    // 6 players, 3 lose their only life: 3 left, 1 slot open, 3 candidates -> one 1v1.
+    let mut t = group_play_with(6, TournamentMode::OneVOne, true);
+    play_round(&mut t);
+    assert_eq!(t.state, TournamentState::Redemption);
+
+    generate_redemption_matches(&mut t).unwrap();
+    assert_eq!(t.matches.len(), 1);
+    assert_eq!(t.matches[0].round, RoundType::Redemption);
+    let winner = t.matches[0].team_1[0];
+    let loser = t.matches[0].team_2[0];
+
+    play_redemption(&mut t);
+
+    assert_eq!(t.state, TournamentState::SemiFinals);
+    assert_eq!(t.players.len(), 4);
+    let w = t.players.iter().find(|p| p.id == winner).unwrap();
+    assert!(!w.eliminated && w.elimination_reason.is_none());
+    assert_eq!((w.wins, w.matches_played), (1, 2));
+    let l = t.eliminated_players.iter().find(|p| p.id == loser).unwrap();
+    assert_eq!(l.losses, 2);
+    assert_eq!(l.elimination_reason, Some(EliminationReason::MaxLosses));
+    assert!(t
+        .match_history
+        .iter()
+        .any(|m| m.round == RoundType::Redemption && m.winner == Some(Team::One)));
+
+    generate_semi_final_matches(&mut t).unwrap();
+    assert_eq!(t.matches.len(), 2);
+}
+
+#[test]
+fn two_v_two_redemption_plays_1v1_matches() {
+    // 10 players: 8 play, 4 lose and are out -> 6 left, 2 slots, 4 candidates -> two 1v1s.
+    let mut t = group_play_with(10, TournamentMode::TwoVTwo, true);
+    play_round(&mut t);
+    assert_eq!(t.state, TournamentState::Redemption);
+
+    generate_redemption_matches(&mut t).unwrap();
+    assert_eq!(t.matches.len(), 2);
+    assert!(t
+        .matches
+        .iter()
+        .all(|m| m.team_1.len() == 1 && m.team_2.len() == 1));
+
+    play_redemption(&mut t);
+    assert_eq!(t.state, TournamentState::SemiFinals);
+    assert_eq!(t.players.len(), 8);
+    generate_semi_final_matches(&mut t).unwrap();
+}
+
+#[test]
+fn too_few_candidates_rejoin_without_playing() {
+    let players: Vec<Player> = (0..3).map(|i| Player::new(format!("P{i}"))).collect();
+    let mut t = Tournament::with_players(players, 1, TournamentMode::OneVOne);
+    t.redemption = true;
+    let mut out = t.players.pop().unwrap();
+    out.eliminate(EliminationReason::MaxLosses);
+    t.eliminated_players.push(out.clone());
+    t.last_eliminated_players.push(out);
+    t.state = TournamentState::Redemption;
+
+    generate_redemption_matches(&mut t).unwrap();
+
+    assert!(t.matches.is_empty());
+    assert_eq!(t.players.len(), 3);
+    assert!(t.eliminated_players.is_empty());
+    assert_eq!(t.state, TournamentState::FinalSelection);
+}
@@ -0,0 +1,588 @@
+//! Integration tests for the Setup phase: roster, mode, and starting the tournament.
+
+use dart_tournament_web::{
+    import_roster_csv, parse_roster_csv, quickstart_tournament, start_tournament, CsvRowError,
+    FeasibilityWarning, InputLimits, Player, RoundType, ScoringMode, Tournament, TournamentError,
+    TournamentMode, TournamentState, DEFAULT_MAX_NAME_LEN, DEFAULT_MAX_PLAYERS,
+};
+use std::time::{Duration, Instant};
+
+fn setup_with(n: usize, mode: TournamentMode) -> Tournament {
+    let mut t = Tournament::new(3, mode);
+    for i in 0..n {
+        t.add_player(format!("P{i}")).unwrap();
+    }
+    t
+}
+
+#[test]
+fn roster_compatible_with_both_modes() {
+    let t = setup_with(8, TournamentMode::OneVOne);
+    assert!(t.can_start_in_mode(TournamentMode::OneVOne));
+    assert!(t.can_start_in_mode(TournamentMode::TwoVTwo));
+}
+
+#[test]
+fn switching_to_2v2_with_small_roster_warns_but_succeeds() {
+    let mut t = setup_with(5, TournamentMode::OneVOne);
+    assert!(t.can_start_in_mode(TournamentMode::OneVOne));
+    assert!(!t.can_start_in_mode(TournamentMode::TwoVTwo));
+
+    t.set_mode(TournamentMode::TwoVTwo).unwrap();
+    assert!(!t.can_start_in_mode(t.mode));
+    assert_eq!(
+        start_tournament(&mut t),
+        Err(TournamentError::NotEnoughPlayersToStart { required: 8 })
+    );
+    assert_eq!(t.state, TournamentState::Setup);
+}
+
+#[test]
+fn pause_gates_mutations_without_changing_state() {
+    let mut t = Tournament::new(3, TournamentMode::OneVOne);
+    t.pause().unwrap();
+    assert!(t.paused);
+    assert_eq!(t.state, TournamentState::Setup);
+    assert_eq!(t.ensure_not_paused(), Err(TournamentError::Paused));
+
+    t.resume().unwrap();
+    assert!(!t.paused);
+    assert_eq!(t.ensure_not_paused(), Ok(()));
+}
+
+#[test]
+fn name_length_limit_boundary() {
+    let mut t = Tournament::new(3, TournamentMode::OneVOne);
+    t.add_player("å".repeat(DEFAULT_MAX_NAME_LEN)).unwrap();
+    assert_eq!(
+        t.add_player("b".repeat(DEFAULT_MAX_NAME_LEN + 1)),
+        Err(TournamentError::NameTooLong {
+            max: DEFAULT_MAX_NAME_LEN
+        })
+    );
+    // Surrounding whitespace is trimmed before the check.
+    t.add_player(format!("  {}  ", "c".repeat(DEFAULT_MAX_NAME_LEN)))
+        .unwrap();
+}
+
+#[test]
+fn roster_size_limit_boundary() {
+    let mut t = Tournament::new(3, TournamentMode::OneVOne);
+    t.limits = InputLimits {
+        max_players: 5,
+        ..InputLimits::default()
+    };
+    for i in 0..5 {
+        t.add_player(format!("P{i}")).unwrap();
+    }
+    assert_eq!(
+        t.add_player("P5"),
+        Err(TournamentError::TooManyPlayers { max: 5 })
+    );
+    assert_eq!(t.players.len(), 5);
+    assert_eq!(InputLimits::default().max_players, DEFAULT_MAX_PLAYERS);
+}
+
+#[test]
+fn stale_expected_version_is_rejected_without_changes() {
+    let mut t = Tournament::new(3, TournamentMode::OneVOne);
+    t.apply_versioned(Some(0), |t| t.add_player("Alice"))
+        .unwrap();
+    t.apply_versioned(None, |t| t.add_player("Bob")).unwrap();
+    assert_eq!(t.version, 2);
+
+    // A second client still at version 1 loses the race.
+    assert_eq!(
+        t.apply_versioned(Some(1), |t| t.add_player("Carol").map(|_| ())),
+        Err(TournamentError::VersionConflict {
+            expected: 1,
+            current: 2
+        })
+    );
+    assert_eq!(t.players.len(), 2);
+    assert_eq!(t.version, 2);
+
+    // Failed actions do not bump the version.
+    assert_eq!(
+        t.apply_versioned(Some(2), |t| t.add_player("alice").map(|_| ())),
+        Err(TournamentError::DuplicatePlayerName)
+    );
+    assert_eq!(t.version, 2);
+}
+
+#[test]
+fn failed_transaction_leaves_the_tournament_exactly_as_before() {
+    let mut t = setup_with(3, TournamentMode::OneVOne);
+    let before = serde_json::to_value(&t).unwrap();
+
+    // Adds a player and starts, then fails on the last step.
+    let result = t.transaction(|t| {
+        t.add_player("Dora")?;
+        start_tournament(t)?;
+        t.add_player("P0")
+    });
+    assert_eq!(result, Err(TournamentError::DuplicatePlayerName));
+    assert_eq!(serde_json::to_value(&t).unwrap(), before);
+
+    t.transaction(|t| t.add_player("Dora")).unwrap();
+    assert_eq!(t.players.len(), 4);
+}
+
+#[test]
+fn failed_versioned_action_rolls_back_earlier_steps() {
+    let mut t = setup_with(3, TournamentMode::OneVOne);
+    let before = serde_json::to_value(&t).unwrap();
+    assert_eq!(
+        t.apply_versioned(None, |t| {
+            t.add_player("Dora")?;
+            t.set_max_losses(0)
+        }),
+        Err(TournamentError::InvalidMaxLosses)
+    );
+    assert_eq!(serde_json::to_value(&t).unwrap(), before);
+}
+
+#[test]
+fn feasibility_flags_sit_outs_and_short_first_round() {
+    // 2v2 with 9 players: one sits out every round, and a single-loss first round leaves 5 of 8.
+    let mut t = setup_with(9, TournamentMode::TwoVTwo);
+    t.max_losses = 1;
+    assert_eq!(
+        t.start_feasibility().warnings,
+        vec![
+            FeasibilityWarning::SittingOutEveryRound { per_round: 1 },
+            FeasibilityWarning::FirstRoundBelowSemiFinalSize {
+                remaining: 5,
+                semi_final_size: 8
+            },
+        ]
+    );
+    // Warnings never block the start.
+    start_tournament(&mut t).unwrap();
+    assert_eq!(t.state, TournamentState::GroupPlay);
+}
+
+#[test]
+fn feasibility_of_common_configs() {
+    assert!(setup_with(16, TournamentMode::TwoVTwo)
+        .start_feasibility()
+        .is_clean());
+    assert_eq!(
+        setup_with(4, TournamentMode::OneVOne)
+            .start_feasibility()
+            .warnings,
+        vec![FeasibilityWarning::NoGroupPlay]
+    );
+    // Too small to start: nothing to report (starting fails on its own).
+    assert!(setup_with(3, TournamentMode::OneVOne)
+        .start_feasibility()
+        .is_clean());
+}
+
+#[test]
+fn quickstart_adds_players_and_starts() {
+    let mut t = Tournament::new(3, TournamentMode::OneVOne);
+    let report = quickstart_tournament(&mut t, ["Ann", "Ben", "ann", "Cid", "Dee", "Eve"]);
+    assert_eq!(
+        report.rejected,
+        vec![("ann".to_string(), TournamentError::DuplicatePlayerName)]
+    );
+    assert_eq!(report.started, Ok(()));
+    assert_eq!(t.players.len(), 5);
+    assert_eq!(t.state, TournamentState::GroupPlay);
+}
+
+#[test]
+fn quickstart_with_too_few_players_stays_in_setup() {
+    let mut t = Tournament::new(3, TournamentMode::TwoVTwo);
+    let report = quickstart_tournament(&mut t, ["Ann", "Ben", "Cid"]);
+    assert!(report.rejected.is_empty());
+    assert_eq!(
+        report.started,
+        Err(TournamentError::NotEnoughPlayersToStart { required: 8 })
+    );
+    // The players are kept so the operator can add more and start again.
+    assert_eq!(t.players.len(), 3);
+    assert_eq!(t.state, TournamentState::Setup);
+}
+
+#[test]
+fn roster_csv_with_header_quotes_and_ratings() {
+    let csv = "name,rating\nAnn,1500\n\n\"Smith, Bob\",1320\nCid\n";
+    let parsed = parse_roster_csv(csv);
+    assert!(parsed.errors.is_empty());
+    assert_eq!(
+        parsed.rows,
+        vec![
+            ("Ann".to_string(), Some(1500)),
+            ("Smith, Bob".to_string(), Some(1320)),
+            ("Cid".to_string(), None),
+        ]
+    );
+
+    let mut t = Tournament::new(3, TournamentMode::OneVOne);
+    let import = import_roster_csv(&mut t, csv).unwrap();
+    assert_eq!(import.added, 3);
+    assert_eq!(t.players[1].rating, Some(1320));
+}
+
+#[test]
+fn roster_csv_quoted_name_may_span_lines() {
+    let csv = "\"Ann\nLee\",1500\nBen,oops\n";
+    let parsed = parse_roster_csv(csv);
+    assert_eq!(parsed.rows, vec![("Ann\nLee".to_string(), Some(1500))]);
+    // Line numbers still count the physical line a record starts on.
+    assert_eq!(parsed.errors.len(), 1);
+    assert_eq!(parsed.errors[0].line, 3);
+}
+
+#[test]
+fn roster_csv_reports_bad_rows_and_keeps_the_rest() {
+    let csv = "Ann,12x\nBen,1400\n,1000\nCid,1,2\nben\n\"Dee\n";
+    let mut t = Tournament::new(3, TournamentMode::OneVOne);
+    let import = import_roster_csv(&mut t, csv).unwrap();
+    let lines: Vec<usize> = import.malformed.iter().map(|e| e.line).collect();
+    assert_eq!(lines, vec![1, 3, 4, 6]);
+    assert_eq!(
+        import.malformed[0],
+        CsvRowError {
+            line: 1,
+            message: "Rating must be a whole number".to_string()
+        }
+    );
+    assert_eq!(
+        import.rejected,
+        vec![("ben".to_string(), TournamentError::DuplicatePlayerName)]
+    );
+    assert_eq!(import.added, 1);
+    assert_eq!(t.players[0].name, "Ben");
+}
+
+#[test]
+fn roster_csv_only_in_setup() {
+    let mut t = setup_with(4, TournamentMode::OneVOne);
+    start_tournament(&mut t).unwrap();
+    assert_eq!(
+        import_roster_csv(&mut t, "Eve"),
+        Err(TournamentError::InvalidState)
+    );
+}
+
+#[test]
+fn start_records_started_at_and_restart_clears_it() {
+    let mut t = setup_with(10, TournamentMode::TwoVTwo);
+    assert_eq!(t.started_at, None);
+    start_tournament(&mut t).unwrap();
+    assert!(t.started_at.is_some());
+    assert_eq!(t.duration(), None);
+
+    t.restart_tournament().unwrap();
+    assert_eq!(t.started_at, None);
+    assert_eq!(t.completed_at, None);
+}
+
+#[test]
+fn restart_keeps_every_setting() {
+    let mut t = setup_with(10, TournamentMode::TwoVTwo);
+    let mut config = t.config();
+    config.max_losses = 2;
+    config.reshuffle_every = 3;
+    config.num_boards = Some(2);
+    config.round_cap = Some(5);
+    config.auto_submit_after_secs = Some(30);
+    config
+        .scoring_modes
+        .insert(RoundType::Finals, ScoringMode::Legs);
+    t.set_config(config.clone()).unwrap();
+    start_tournament(&mut t).unwrap();
+
+    t.restart_tournament().unwrap();
+    assert_eq!(t.config(), config);
+    assert_eq!(t.players.len(), 10);
+}
+
+#[test]
+fn player_colors_must_be_hex() {
+    let mut t = setup_with(0, TournamentMode::OneVOne);
+    t.add_player_with_appearance("Ann", None, Some(" #1E90ff "), Some("https://x.test/a.png"))
+        .unwrap();
+    assert_eq!(t.players[0].color.as_deref(), Some("#1E90ff"));
+    assert_eq!(
+        t.players[0].avatar_url.as_deref(),
+        Some("https://x.test/a.png")
+    );
+
+    for bad in ["1e90ff", "#1e90f", "#ggg", "#1234567", "red"] {
+        assert_eq!(
+            t.add_player_with_appearance("Bob", None, Some(bad), None),
+            Err(TournamentError::InvalidColor),
+            "{bad}"
+        );
+    }
+    assert_eq!(t.players.len(), 1);
+
+    let id = t.players[0].id;
+    t.set_player_appearance(id, Some("#abc"), None).unwrap();
+    assert_eq!(t.players[0].color.as_deref(), Some("#abc"));
+    assert_eq!(t.players[0].avatar_url, None);
+    assert_eq!(
+        t.set_player_appearance(id, Some("#abcd"), None),
+        Err(TournamentError::InvalidColor)
+    );
+    assert_eq!(t.players[0].color.as_deref(), Some("#abc"));
+    t.set_player_appearance(id, Some(""), None).unwrap();
+    assert_eq!(t.players[0].color, None);
+}
+
+#[test]
+fn avatar_urls_must_be_http_and_within_the_limit() {
+    let mut t = setup_with(0, TournamentMode::OneVOne);
+    t.limits = InputLimits {
+        max_avatar_url_len: 24,
+        ..InputLimits::default()
+    };
+    t.add_player_with_appearance("Ann", None, None, Some(" HTTP://x.test/a.png "))
+        .unwrap();
+    assert_eq!(
+        t.players[0].avatar_url.as_deref(),
+        Some("HTTP://x.test/a.png")
+    );
+
+    for bad in [
+        "javascript:alert(1)",
+        "data:image/png;base64,AA",
+        "//x.test/a.png",
+        "ftp://x.test/a.png",
+        "x.test/a.png",
+    ] {
+        assert_eq!(
+            t.add_player_with_appearance("Bob", None, None, Some(bad)),
+            Err(TournamentError::InvalidAvatarUrl),
+            "{bad}"
+        );
+    }
+    let too_long = format!("https://x.test/{}", "a".repeat(10));
+    assert_eq!(
+        t.add_player_with_appearance("Bob", None, None, Some(&too_long)),
+        Err(TournamentError::AvatarUrlTooLong { max: 24 })
+    );
+    assert_eq!(t.players.len(), 1);
+
+    let id = t.players[0].id;
+    assert_eq!(
+        t.set_player_appearance(id, None, Some("javascript:alert(1)")),
+        Err(TournamentError::InvalidAvatarUrl)
+    );
+    assert_eq!(
+        t.players[0].avatar_url.as_deref(),
+        Some("HTTP://x.test/a.png")
+    );
+}
+
+#[test]
+fn zero_max_losses_is_rejected() {
+    let mut t = setup_with(8, TournamentMode::OneVOne);
+    assert_eq!(t.set_max_losses(0), Err(TournamentError::InvalidMaxLosses));
+    assert_eq!(t.max_losses, 3);
+    t.set_max_losses(1).unwrap();
+    assert_eq!(t.max_losses, 1);
+}
+
+#[test]
+#[should_panic(expected = "max_losses must be at least 1")]
+fn zero_max_losses_is_rejected_on_construction() {
+    Tournament::new(0, TournamentMode::OneVOne);
+}
+
+#[test]
+#[should_panic(expected = "max_losses must be at least 1")]
+fn zero_max_losses_is_rejected_with_players() {
+    Tournament::with_players(vec![Player::new("Ann")], 0, TournamentMode::OneVOne);
+}
+
+#[test]
+fn reorder_players_requires_a_permutation() {
+    let mut t = setup_with(4, TournamentMode::OneVOne);
+    let ids: Vec<_> = t.players.iter().map(|p| p.id).collect();
+    let reversed: Vec<_> = ids.iter().rev().copied().collect();
+
+    t.reorder_players(&reversed).unwrap();
+    let order: Vec<_> = t.public_view().players.iter().map(|p| p.id).collect();
+    assert_eq!(order, reversed);
+
+    let stranger = uuid::Uuid::new_v4();
+    for bad in [
+        ids[..3].to_vec(),
+        [ids.clone(), vec![stranger]].concat(),
+        vec![ids[0], ids[0], ids[1], ids[2]],
+        vec![ids[0], ids[1], ids[2], stranger],
+    ] {
+        assert_eq!(
+            t.reorder_players(&bad),
+            Err(TournamentError::InvalidPlayerOrder)
+        );
+    }
+    let order: Vec<_> = t.public_view().players.iter().map(|p| p.id).collect();
+    assert_eq!(order, reversed);
+
+    t.add_player("Late").unwrap();
+    assert_eq!(t.public_view().players.last().unwrap().name, "Late");
+}
+
+#[test]
+fn imported_stats_show_up_in_standings() {
+    let mut t = Tournament::new(3, TournamentMode::OneVOne);
+    t.add_player_with_stats("Ann", 4, 1, 0).unwrap();
+    t.add_player_with_stats("Bob", 2, 2, 1).unwrap();
+    t.add_player_with_stats("Cid", 1, 3, 0).unwrap();
+
+    let standings: Vec<(&str, u32, u32)> = t
+        .standings()
+        .iter()
+        .map(|p| (p.name.as_str(), p.wins, p.losses))
+        .collect();
+    assert_eq!(standings, vec![("Ann", 4, 1), ("Bob", 2, 2), ("Cid", 1, 3)]);
+
+    let bob = t.players.iter().find(|p| p.name == "Bob").unwrap();
+    assert_eq!(bob.matches_played, 4);
+    assert_eq!(bob.times_sat_out, 1);
+    assert_eq!(bob.internal_times_sat_out, 1);
+
+    assert_eq!(t.players.len(), 2);
+    assert_eq!(
+        t.eliminated_players.len(),
+        1,
+        "at max losses the player starts eliminated"
+    );
+    assert!(t.eliminated_players[0].eliminated);
+
+    assert!(matches!(
+        t.add_player_with_stats("Ann", 0, 0, 0),
+        Err(TournamentError::DuplicatePlayerName)
+    ));
+    t.state = TournamentState::GroupPlay;
+    assert!(matches!(
+        t.add_player_with_stats("Dee", 0, 0, 0),
+        Err(TournamentError::InvalidState)
+    ));
+}
+
+#[test]
+fn imported_stats_out_of_range_are_rejected() {
+    let mut t = Tournament::new(3, TournamentMode::OneVOne);
+    assert!(matches!(
+        t.add_player_with_stats("Ann", u32::MAX, 1, 0),
+        Err(TournamentError::InvalidImportedStats(
+            "wins + losses is too large"
+        ))
+    ));
+    assert!(matches!(
+        t.add_player_with_stats("Ann", i32::MAX as u32, 1, 0),
+        Err(TournamentError::InvalidImportedStats(_))
+    ));
+    assert!(matches!(
+        t.add_player_with_stats("Ann", 0, 0, u32::MAX),
+        Err(TournamentError::InvalidImportedStats(
+            "sat_out is too large"
+        ))
+    ));
+    assert!(t.players.is_empty());
+    t.add_player_with_stats("Ann", i32::MAX as u32, 0, i32::MAX as u32)
+        .unwrap();
+}
+
+#[test]
+fn bulk_config_update_applies_every_setting() {
+    let mut t = setup_with(4, TournamentMode::TwoVTwo);
+    let mut config = t.config();
+    config.max_losses = 4;
+    config.balance_teams = true;
+    config.reshuffle_every = 2;
+    config.legs_per_final_match = 3;
+    config.num_boards = Some(2);
+    config.strict_bye_rotation = true;
+    config
+        .scoring_modes
+        .insert(RoundType::Finals, ScoringMode::Winner);
+    t.set_config(config.clone()).unwrap();
+    assert_eq!(t.config(), config);
+    assert_eq!(t.max_losses, 4);
+    assert!(t.balance_teams);
+    assert_eq!(t.legs_per_final_match, 3);
+    assert_eq!(t.scoring_mode(RoundType::SemiFinals), ScoringMode::Legs);
+    assert_eq!(t.scoring_mode(RoundType::Finals), ScoringMode::Winner);
+
+    let mut config = t.config();
+    config.auto_submit_after_secs = Some(30);
+    t.set_config(config).unwrap();
+    assert_eq!(t.auto_submit_after_secs, Some(30));
+    let mut config = t.config();
+    config.auto_submit_after_secs = Some(0);
+    assert!(t.set_config(config).is_err());
+    assert_eq!(t.auto_submit_after_secs, Some(30));
+}
+
+#[test]
+fn bulk_config_update_is_all_or_nothing() {
+    let mut t = setup_with(4, TournamentMode::TwoVTwo);
+    let before = t.config();
+
+    let mut config = before.clone();
+    config.mode = TournamentMode::OneVOne;
+    config.balance_teams = true;
+    assert_eq!(
+        t.set_config(config.clone()),
+        Err(TournamentError::InvalidConfig("balance_teams needs 2v2"))
+    );
+    assert_eq!(t.config(), before);
+
+    let mut config = before.clone();
+    config.max_losses = 5;
+    config.legs_per_final_match = 2;
+    assert!(t.set_config(config).is_err());
+    assert_eq!(t.config(), before, "valid fields are not applied either");
+
+    let mut config = before.clone();
+    config
+        .scoring_modes
+        .insert(RoundType::GroupPlay, ScoringMode::Legs);
+    assert_eq!(
+        t.set_config(config),
+        Err(TournamentError::InvalidConfig(
+            "legs scoring needs semi_finals or finals"
+        ))
+    );
+    assert_eq!(t.config(), before);
+
+    t.state = TournamentState::GroupPlay;
+    assert_eq!(t.set_config(before), Err(TournamentError::InvalidState));
+}
+
+#[test]
+fn summary_reports_the_idle_time_since_last_activity() {
+    let t = setup_with(5, TournamentMode::OneVOne);
+    let touched = Instant::now();
+    let summary = t.summary(touched);
+    assert_eq!(summary.id, t.id);
+    assert_eq!(summary.active, 5);
+    assert_eq!(summary.state, TournamentState::Setup);
+    assert!(summary.last_activity_seconds_ago <= 1);
+
+    let earlier = touched - Duration::from_secs(90);
+    assert!(t.summary(earlier).last_activity_seconds_ago >= 90);
+}
+
+#[test]
+fn summary_filter_matches_state_and_mode() {
+    let one = setup_with(5, TournamentMode::OneVOne).summary(Instant::now());
+    let mut t = setup_with(8, TournamentMode::TwoVTwo);
+    t.state = TournamentState::GroupPlay;
+    let two = t.summary(Instant::now());
+
+    let group_play = Some(TournamentState::GroupPlay);
+    assert!(two.matches_filter(group_play, None));
+    assert!(!one.matches_filter(group_play, None));
+    assert!(one.matches_filter(None, Some(TournamentMode::OneVOne)));
+    assert!(!two.matches_filter(group_play, Some(TournamentMode::OneVOne)));
+    assert!(one.matches_filter(None, None) && two.matches_filter(None, None));
+}
@@ -0,0 +1,59 @@
+//! Tests for read-only share links.
+
+use dart_tournament_web::share::{shared_view, ShareLinks};
+use dart_tournament_web::{Tournament, TournamentMode};
+use uuid::Uuid;
+
+#[test]
+fn share_link_resolves_to_the_tournament() {
+    let mut links = ShareLinks::default();
+    let tournament = Uuid::new_v4();
+    let share_id = links.share(tournament);
+
+    assert_ne!(share_id, tournament);
+    assert_eq!(links.resolve(share_id), Some(tournament));
+    // Sharing again returns the same link.
+    assert_eq!(links.share(tournament), share_id);
+    assert_eq!(links.share_id(tournament), Some(share_id));
+}
+
+#[test]
+fn revoked_link_no_longer_resolves() {
+    let mut links = ShareLinks::default();
+    let tournament = Uuid::new_v4();
+    let other = Uuid::new_v4();
+    let share_id = links.share(tournament);
+    let other_share = links.share(other);
+
+    assert!(links.revoke(tournament));
+    assert_eq!(links.resolve(share_id), None);
+    assert!(!links.revoke(tournament));
+    assert_eq!(links.resolve(other_share), Some(other));
+
+    // A new link after revoking is a different id.
+    assert_ne!(links.share(tournament), share_id);
+}
+
+#[test]
+fn links_of_removed_tournaments_are_dropped() {
+    let mut links = ShareLinks::default();
+    let kept = Uuid::new_v4();
+    let removed = Uuid::new_v4();
+    let kept_share = links.share(kept);
+    let removed_share = links.share(removed);
+
+    links.retain_tournaments(|id| id == kept);
+    assert_eq!(links.resolve(kept_share), Some(kept));
+    assert_eq!(links.resolve(removed_share), None);
+}
+
+#[test]
+fn shared_view_hides_the_tournament_id() {
+    let mut t = Tournament::new(3, TournamentMode::OneVOne);
+    t.add_player("Alice").unwrap();
+    let json = shared_view(&t);
+
+    assert!(json.get("id").is_none());
+    assert!(!json.to_string().contains(&t.id.to_string()));
+    assert_eq!(json["players"][0]["name"], "Alice");
+}
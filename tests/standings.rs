@@ -0,0 +1,64 @@
+//! Integration tests for standings and points.
+
+use dart_tournament_web::{Player, PointsConfig, Tournament, TournamentMode};
+
+#[test]
+fn standings_order_by_wins_then_losses() {
+    let players: Vec<Player> = (0..3).map(|i| Player::new(format!("P{i}"))).collect();
+    let mut t = Tournament::with_players(players, 3, TournamentMode::OneVOne);
+    t.players[0].wins = 1;
+    t.players[1].wins = 2;
+    t.players[2].wins = 1;
+    t.players[2].losses = 1;
+    let names: Vec<&str> = t.standings().iter().map(|p| p.name.as_str()).collect();
+    assert_eq!(names, ["P1", "P0", "P2"]);
+}
+
+#[test]
+fn points_follow_the_config() {
+    let default = PointsConfig::default();
+    assert_eq!(default.points_for(4, 2, 3), 4);
+    let league = PointsConfig {
+        win: 3,
+        draw: 1,
+        loss: 0,
+    };
+    assert_eq!(league.points_for(4, 2, 3), 14);
+    let penalty = PointsConfig {
+        win: 2,
+        draw: 1,
+        loss: -1,
+    };
+    assert_eq!(penalty.points_for(1, 0, 3), -1);
+}
+
+#[test]
+fn points_saturate_instead_of_overflowing() {
+    let big = PointsConfig {
+        win: i32::MAX,
+        draw: 1,
+        loss: i32::MIN,
+    };
+    assert_eq!(big.points_for(2, 1, 0), i32::MAX);
+    assert_eq!(big.points_for(0, 0, u32::MAX), i32::MIN);
+    assert_eq!(PointsConfig::default().points_for(u32::MAX, 0, 0), i32::MAX);
+}
+
+#[test]
+fn standings_order_by_points() {
+    let players: Vec<Player> = (0..3).map(|i| Player::new(format!("P{i}"))).collect();
+    let mut t = Tournament::with_players(players, 3, TournamentMode::OneVOne);
+    t.points_config = PointsConfig {
+        win: 3,
+        draw: 1,
+        loss: 0,
+    };
+    // P0: 1 win (3 pts), P1: 4 draws (4 pts), P2: 1 win + 1 draw (4 pts, more wins).
+    t.players[0].wins = 1;
+    t.players[1].draws = 4;
+    t.players[2].wins = 1;
+    t.players[2].draws = 1;
+    let names: Vec<&str> = t.standings().iter().map(|p| p.name.as_str()).collect();
+    assert_eq!(names, ["P2", "P1", "P0"]);
+    assert_eq!(t.points(&t.players[1]), 4);
+}